@@ -0,0 +1,46 @@
+//! Compares the cost of updating a 1000-item `ForIndex` when a single item is appended,
+//! which only touches the tail, against changing the first item, which re-creates the view
+//! for that item while the remainder are skipped.
+//!
+//! Run with `cargo bench --bench for_index`.
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_reactor::*;
+
+const ITEMS: u32 = 1000;
+const ITERATIONS: u32 = 200;
+
+#[derive(Resource)]
+struct Items(Vec<u32>);
+
+fn measure(update: fn(&mut Vec<u32>)) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let mut world = World::default();
+        world.insert_resource(Items((0..ITEMS).collect()));
+        let parent = world.spawn_empty().id();
+        let view = ForIndex::new(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            |item, _| item.to_string(),
+        )
+        .into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+
+        world.increment_change_tick();
+        update(&mut world.resource_mut::<Items>().0);
+        let started = Instant::now();
+        run_reactions(&mut world);
+        total += started.elapsed();
+    }
+    total / ITERATIONS
+}
+
+fn main() {
+    let append = measure(|items| items.push(ITEMS));
+    let replace_first = measure(|items| items[0] = ITEMS);
+    println!("ForIndex, {} items:", ITEMS);
+    println!("  append one item:     {:?}", append);
+    println!("  replace first item:  {:?}", replace_first);
+}
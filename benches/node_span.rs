@@ -0,0 +1,43 @@
+//! Measures the cost of `View::nodes()` on the root of a tree of nested fragments with 1000
+//! display nodes. Each fragment clones the `NodeSpan` cached on its children's view entities
+//! instead of locking the child views, but the clone still copies every node; compare
+//! against the cost of cloning the cached `NodeSpan` component directly.
+//!
+//! Run with `cargo bench --bench node_span`.
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_reactor::*;
+
+const BRANCHING: usize = 10;
+const ITERATIONS: u32 = 1000;
+
+/// Build a tree of fragments `depth` levels deep, with `BRANCHING` children at each level.
+fn tree(depth: usize) -> ViewRef {
+    if depth == 0 {
+        return "Leaf".into_view();
+    }
+    Fragment::from_refs((0..BRANCHING).map(|_| tree(depth - 1)).collect()).into_view()
+}
+
+fn measure(f: impl Fn() -> NodeSpan) -> Duration {
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        assert_eq!(f().count(), BRANCHING.pow(3));
+    }
+    started.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let mut world = World::default();
+    let parent = world.spawn_empty().id();
+    let view = tree(3);
+    let entity = ViewHandle::spawn(&view, parent, &mut world);
+
+    let nodes = measure(|| view.lock().unwrap().nodes(&world));
+    let cached = measure(|| world.get::<NodeSpan>(entity).unwrap().clone());
+    println!("NodeSpan, {} nodes:", BRANCHING.pow(3));
+    println!("  View::nodes():       {:?}", nodes);
+    println!("  cached component:    {:?}", cached);
+}
@@ -0,0 +1,103 @@
+//! Compares the cost of a reaction pass with and without parallelism: over 10000 views, of
+//! which only one has changed dependencies, and over 1000 elements whose computed components
+//! all change, each doing some work before writing to its own entity.
+//!
+//! Run with `cargo bench --bench run_reactions`.
+
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPool},
+};
+use bevy_reactor::*;
+
+const VIEWS: u32 = 10000;
+const ITERATIONS: u32 = 100;
+const REACTIONS: u32 = 1000;
+
+#[derive(Component)]
+struct Value(u32);
+
+#[derive(Component, PartialEq)]
+struct Output(u64);
+
+/// Does some work on a value, standing in for an expensive computed property.
+fn hash(value: u32) -> u64 {
+    let mut hash = value as u64;
+    for _ in 0..10000 {
+        hash = hash
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+    }
+    hash
+}
+
+fn measure_checks(parallelism: bool) -> Duration {
+    let mut world = World::default();
+    world.insert_resource(ReactorSettings { parallelism });
+    let parent = world.spawn_empty().id();
+    let sources: Vec<Entity> = (0..VIEWS).map(|i| world.spawn(Value(i)).id()).collect();
+    for source in sources.iter().copied() {
+        let view =
+            TextComputed::new(move |re| re.use_component::<Value>(source).unwrap().0.to_string())
+                .into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+    }
+
+    let mut total = Duration::ZERO;
+    for i in 0..ITERATIONS {
+        world.increment_change_tick();
+        world.get_mut::<Value>(sources[0]).unwrap().0 = VIEWS + i;
+        let started = Instant::now();
+        run_reactions(&mut world);
+        total += started.elapsed();
+    }
+    total / ITERATIONS
+}
+
+fn measure_waves(parallelism: bool) -> Duration {
+    let mut world = World::default();
+    world.insert_resource(ReactorSettings { parallelism });
+    let sources: Vec<Entity> = (0..REACTIONS).map(|i| world.spawn(Value(i)).id()).collect();
+    let parent = world.spawn_empty().id();
+    for source in sources.iter().copied() {
+        // Each element's computed component is a reaction which only writes to the element.
+        let view = Element::<NodeBundle>::new()
+            .insert_producer(ComponentComputed::new(move |re| {
+                Output(hash(re.use_component::<Value>(source).unwrap().0))
+            }))
+            .into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+    }
+
+    let mut total = Duration::ZERO;
+    for i in 0..ITERATIONS {
+        world.increment_change_tick();
+        for source in sources.iter() {
+            world.get_mut::<Value>(*source).unwrap().0 += i;
+        }
+        let started = Instant::now();
+        run_reactions(&mut world);
+        total += started.elapsed();
+    }
+    total / ITERATIONS
+}
+
+fn main() {
+    ComputeTaskPool::get_or_init(TaskPool::default);
+    let sequential = measure_checks(false);
+    let parallel = measure_checks(true);
+    println!("run_reactions, {} views, one changed:", VIEWS);
+    println!("  sequential:  {:?}", sequential);
+    println!("  parallel:    {:?}", parallel);
+
+    let sequential = measure_waves(false);
+    let parallel = measure_waves(true);
+    println!(
+        "run_reactions, {} computed components, all changed:",
+        REACTIONS
+    );
+    println!("  sequential:  {:?}", sequential);
+    println!("  parallel:    {:?}", parallel);
+}
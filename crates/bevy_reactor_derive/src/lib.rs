@@ -0,0 +1,133 @@
+//! Derive macros for `bevy_reactor`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+mod view_macro;
+
+/// Derives `IntoView` for a type which implements `Presenter`. The type holds the presenter's
+/// properties, and is wrapped in a `PresenterView` which builds, reacts and razes the output
+/// of `Presenter::present`. Also adds a `new` constructor which converts the properties into a
+/// view; types which define their own `new` can leave it out with `#[presenter(no_new)]`.
+#[proc_macro_derive(Presenter, attributes(presenter))]
+pub fn derive_presenter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut no_new = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("presenter")) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("no_new") {
+                no_new = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown presenter attribute"))
+            }
+        });
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+    }
+    let constructor = (!no_new).then(|| {
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Construct a view which presents the given properties.
+                pub fn new(props: Self) -> ::bevy_reactor::ViewRef {
+                    ::bevy_reactor::IntoView::into_view(props)
+                }
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics ::bevy_reactor::IntoView for #name #ty_generics #where_clause {
+            fn into_view(self) -> ::bevy_reactor::ViewRef {
+                ::bevy_reactor::IntoView::into_view(::bevy_reactor::PresenterView::new(self))
+            }
+        }
+
+        #constructor
+    }
+    .into()
+}
+
+/// Derives `ThemeTokens` for a struct of design tokens, so that it can be provided to a view
+/// tree with `ThemeProvider`. The generated `THEME_CTX` context is named after the struct.
+/// Also adds an accessor for each named field, which reads that field from the nearest theme.
+#[proc_macro_derive(Theme)]
+pub fn derive_theme(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let accessors = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().unwrap();
+                    let ty = &field.ty;
+                    let doc = format!("Read `{}` from the nearest theme.", ident);
+                    quote! {
+                        #[doc = #doc]
+                        pub fn #ident<Props>(cx: &::bevy_reactor::Cx<Props>) -> #ty {
+                            cx.use_theme_of::<Self>().#ident
+                        }
+                    }
+                })
+                .collect::<Vec<_>>(),
+            _ => {
+                return syn::Error::new_spanned(
+                    name,
+                    "Theme can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into()
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(name, "Theme can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+    quote! {
+        impl #impl_generics ::bevy_reactor::ThemeTokens for #name #ty_generics #where_clause {
+            const THEME_CTX: ::bevy_reactor::Context<Self> = ::bevy_reactor::Context::new::<Self>();
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+    .into()
+}
+
+/// Builds a view from a JSX-like tree of elements:
+///
+/// ```ignore
+/// view! {
+///     <Element<NodeBundle> style=Style::default()>
+///         "Count: "
+///         <TextComputed text=|re| format!("{}", re.read_mutable_clone::<i32>(count)) />
+///     </Element<NodeBundle>>
+/// }
+/// ```
+///
+/// Each element `<Name a=x />` expands to `Name::new().a(x)`, so tag names are resolved by
+/// the caller's imports, and each attribute calls the builder method of the same name. The
+/// views in this crate whose constructors take arguments, such as `TextComputed`, take those
+/// arguments as attributes named after the constructor's parameters, in any order; leaving
+/// one out is an error. The `style` attribute is forwarded to `.insert()`, and `name` to
+/// `.named()`. Children are passed to `.children()`. String literals render as static text,
+/// and `{ expr }` accepts any value which implements `IntoView`. Sibling nodes at the top
+/// level are combined into a fragment.
+///
+/// Attribute values are literals, `{ expressions }`, or unbraced expressions such as closures;
+/// an unbraced expression which contains `>` must be wrapped in braces.
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as view_macro::ViewMacro)
+        .expand()
+        .into()
+}
@@ -0,0 +1,270 @@
+//! Parser and code generator for the `view!` macro.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    Expr, Ident, Lit, LitStr, Path, PathArguments, Token,
+};
+
+/// The constructor parameters of the crate's views, in order. Attributes with these names are
+/// passed to `new`, and all other attributes call the builder method of the same name.
+const CONSTRUCTORS: &[(&str, &[&str])] = &[
+    ("ErrorBoundary", &["child", "fallback_fn"]),
+    ("ImageStatic", &["image"]),
+    ("ImageView", &["image"]),
+    ("Portal", &["target", "view"]),
+    ("ScrollView", &["content"]),
+    ("ShowHide", &["visible_fn", "child"]),
+    ("Suspense", &["placeholder", "child"]),
+    ("TextComputed", &["text"]),
+    ("TextRich", &["sections"]),
+    ("TextStatic", &["text"]),
+    ("ThemeProvider", &["theme", "child"]),
+];
+
+/// The root of a `view!` invocation: a list of sibling nodes.
+pub(crate) struct ViewMacro {
+    nodes: Vec<Node>,
+}
+
+enum Node {
+    /// `<Name attr=value ...> children </Name>` or `<Name attr=value ... />`.
+    Element(Element),
+    /// A string literal, which renders as static text.
+    Text(LitStr),
+    /// `{ expr }`, where `expr` is anything which implements `IntoView`.
+    Block(Expr),
+}
+
+struct Element {
+    name: Path,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+struct Attr {
+    name: Ident,
+    value: AttrValue,
+}
+
+enum AttrValue {
+    Lit(Lit),
+    Expr(Expr),
+}
+
+impl Parse for ViewMacro {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut nodes = Vec::new();
+        while !input.is_empty() {
+            nodes.push(input.parse()?);
+        }
+        Ok(Self { nodes })
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(Node::Text(input.parse()?))
+        } else if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            Ok(Node::Block(content.parse()?))
+        } else if input.peek(Token![<]) {
+            Ok(Node::Element(input.parse()?))
+        } else {
+            Err(input.error("expected `<Element>`, string literal or `{ expression }`"))
+        }
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Path = input.parse()?;
+        let mut attrs = Vec::new();
+        loop {
+            if input.peek(Token![/]) && input.peek2(Token![>]) {
+                input.parse::<Token![/]>()?;
+                input.parse::<Token![>]>()?;
+                return Ok(Self {
+                    name,
+                    attrs,
+                    children: Vec::new(),
+                });
+            }
+            if input.peek(Token![>]) {
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            attrs.push(input.parse()?);
+        }
+
+        let mut children = Vec::new();
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(syn::Error::new_spanned(&name, "unclosed element"));
+            }
+            children.push(input.parse()?);
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let closing: Path = input.parse()?;
+        if closing.to_token_stream().to_string() != name.to_token_stream().to_string() {
+            return Err(syn::Error::new_spanned(
+                closing,
+                format!("closing tag does not match `<{}>`", name.to_token_stream()),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+        Ok(Self {
+            name,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(Lit) {
+            AttrValue::Lit(input.parse()?)
+        } else if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            AttrValue::Expr(content.parse()?)
+        } else {
+            // An unbraced expression, such as a closure, runs until the next attribute or the
+            // end of the tag.
+            let tokens = input.step(|cursor| {
+                let mut rest = *cursor;
+                let mut tokens = TokenStream::new();
+                while let Some((tt, next)) = rest.token_tree() {
+                    if is_attr_end(&tt, next) {
+                        break;
+                    }
+                    tokens.extend([tt]);
+                    rest = next;
+                }
+                Ok((tokens, rest))
+            })?;
+            AttrValue::Expr(syn::parse2(tokens)?)
+        };
+        Ok(Self { name, value })
+    }
+}
+
+/// Returns true if `tt` is the start of the next attribute (`ident =`), or the end of the tag
+/// (`>` or `/>`).
+fn is_attr_end(tt: &TokenTree, next: syn::buffer::Cursor) -> bool {
+    match tt {
+        TokenTree::Punct(p) if p.as_char() == '>' => true,
+        TokenTree::Punct(p) if p.as_char() == '/' => {
+            matches!(next.punct(), Some((p, _)) if p.as_char() == '>')
+        }
+        TokenTree::Ident(_) => match next.punct() {
+            // Exclude `ident == ...` and `ident => ...`.
+            Some((p, after)) if p.as_char() == '=' => {
+                !matches!(after.punct(), Some((p, _)) if p.as_char() == '=' || p.as_char() == '>')
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl ViewMacro {
+    pub(crate) fn expand(&self) -> TokenStream {
+        let view = expand_siblings(&self.nodes);
+        quote! { ::bevy_reactor::IntoView::into_view(#view) }
+    }
+}
+
+/// Expands a list of sibling nodes into a single value: the node itself if there is only
+/// one, otherwise a tuple, which renders as a fragment.
+fn expand_siblings(nodes: &[Node]) -> TokenStream {
+    match nodes {
+        [] => quote! { () },
+        [node] => node.expand(),
+        _ => {
+            let nodes = nodes.iter().map(Node::expand);
+            quote! { ( #(#nodes,)* ) }
+        }
+    }
+}
+
+impl Node {
+    fn expand(&self) -> TokenStream {
+        match self {
+            Node::Text(text) => text.to_token_stream(),
+            Node::Block(expr) => quote! { { #expr } },
+            Node::Element(element) => element.expand(),
+        }
+    }
+}
+
+impl Element {
+    fn expand(&self) -> TokenStream {
+        // Tag names are ordinary paths, and so are resolved by the caller's imports. Type
+        // arguments are written as `<Element<NodeBundle>>`, so convert them to turbofish form.
+        let mut name = self.name.clone();
+        for segment in name.segments.iter_mut() {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                args.colon2_token = Some(Default::default());
+            }
+        }
+
+        let tag = self.name.segments.last().unwrap().ident.to_string();
+        let params = CONSTRUCTORS
+            .iter()
+            .find(|(name, _)| *name == tag)
+            .map_or(&[][..], |(_, params)| *params);
+        let mut args: Vec<Option<TokenStream>> = vec![None; params.len()];
+        let mut modifiers = Vec::new();
+        for attr in self.attrs.iter() {
+            let value = match &attr.value {
+                // String literals are converted, so that they can be passed as `String`.
+                AttrValue::Lit(lit @ Lit::Str(_)) => quote! { ::core::convert::Into::into(#lit) },
+                AttrValue::Lit(lit) => lit.to_token_stream(),
+                AttrValue::Expr(expr) => expr.to_token_stream(),
+            };
+            let attr_name = attr.name.to_string();
+            if let Some(index) = params.iter().position(|param| *param == attr_name) {
+                if args[index].replace(value).is_some() {
+                    return syn::Error::new_spanned(&attr.name, "duplicate attribute")
+                        .to_compile_error();
+                }
+                continue;
+            }
+            let method = &attr.name;
+            match attr_name.as_str() {
+                "style" => modifiers.push(quote! { .insert(#value) }),
+                "name" => modifiers.push(quote! { .named(#value) }),
+                // Spanned so that an unknown attribute is reported as a missing method.
+                _ => modifiers.push(quote_spanned! { method.span()=> .#method(#value) }),
+            }
+        }
+        let mut constructor_args = Vec::new();
+        for (param, arg) in params.iter().zip(args) {
+            match arg {
+                Some(arg) => constructor_args.push(arg),
+                None => {
+                    return syn::Error::new_spanned(
+                        &self.name,
+                        format!("`<{}>` requires the attribute `{}`", tag, param),
+                    )
+                    .to_compile_error()
+                }
+            }
+        }
+        if !self.children.is_empty() {
+            let children = self.children.iter().map(Node::expand);
+            modifiers.push(quote! { .children(( #(#children,)* )) });
+        }
+        quote! { #name::new(#(#constructor_args),*) #(#modifiers)* }
+    }
+}
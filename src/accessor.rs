@@ -58,7 +58,7 @@ where
     }
 }
 
-/// Trait that allows reading the value from a signal via an immutable reference.
+// Trait that allows reading the value from a signal via an immutable reference.
 // trait ReadSignalRef<T: Copy> {
 //     fn as_ref(&self) -> &T;
 
@@ -0,0 +1,577 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, IntoView, TrackingScope, View,
+    ViewHandle, ViewRef,
+};
+
+/// How an [`Animation`] changes the display nodes of an [`AnimatedView`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Tween {
+    /// The nodes are hidden while the enter animation plays, and left unchanged while the exit
+    /// animation plays.
+    #[default]
+    None,
+    /// The alpha of the background and text colors of the nodes, and of their descendants,
+    /// fades in or out.
+    Fade,
+    /// The scale of the nodes grows from zero, or shrinks to zero. This assumes that the
+    /// nodes are not otherwise scaled.
+    Scale,
+}
+
+/// Describes an enter or exit animation for an [`AnimatedView`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Animation {
+    /// How long the animation lasts.
+    pub duration: Duration,
+
+    /// How the display nodes change while the animation plays.
+    pub tween: Tween,
+}
+
+impl Animation {
+    /// Construct an animation which lasts for the given duration. The nodes are not tweened;
+    /// an enter animation simply delays showing the view.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            tween: Tween::None,
+        }
+    }
+
+    /// Construct an animation which fades the nodes in or out over the given duration.
+    pub fn fade(duration: Duration) -> Self {
+        Self {
+            duration,
+            tween: Tween::Fade,
+        }
+    }
+
+    /// Construct an animation which scales the nodes up or down over the given duration.
+    pub fn scale(duration: Duration) -> Self {
+        Self {
+            duration,
+            tween: Tween::Scale,
+        }
+    }
+
+    /// An animation which completes immediately.
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AnimationPhase {
+    Entering,
+    Visible,
+    Exiting,
+}
+
+/// The original alpha of a node's background color and text sections, recorded so that a
+/// [`Tween::Fade`] can scale them.
+struct BaseAlpha {
+    background: Option<f32>,
+    text: Vec<f32>,
+}
+
+/// Component on the view entity of an [`AnimatedView`] which tracks the current animation.
+#[derive(Component)]
+pub(crate) struct AnimationState {
+    phase: AnimationPhase,
+    /// Elapsed time at which the current animation started.
+    start: Duration,
+    /// Elapsed time at which the current animation will be finished.
+    end: Duration,
+    /// The tween of the current animation.
+    tween: Tween,
+    /// The child view and its view entity.
+    child: (ViewRef, Entity),
+    /// Original alphas of the nodes being faded.
+    base_alpha: HashMap<Entity, BaseAlpha>,
+    /// While exiting, the display parent of the child's nodes and their index within it, so
+    /// that they can be put back if the parent's children are replaced.
+    slot: Option<(Entity, usize)>,
+}
+
+impl AnimationState {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        child_nodes(&self.child.0, self.child.1, world)
+    }
+}
+
+/// Apply a tween to the given nodes, where `amount` goes from 0 (not shown) to 1 (fully
+/// shown).
+fn apply_tween(
+    world: &mut World,
+    tween: Tween,
+    nodes: Vec<Entity>,
+    base_alpha: &mut HashMap<Entity, BaseAlpha>,
+    amount: f32,
+) {
+    match tween {
+        Tween::None => {}
+        Tween::Fade => {
+            let mut stack = nodes;
+            while let Some(node) = stack.pop() {
+                let base = base_alpha
+                    .entry(node)
+                    .or_insert_with(|| BaseAlpha::of(world, node));
+                if let (Some(alpha), Some(mut color)) =
+                    (base.background, world.get_mut::<BackgroundColor>(node))
+                {
+                    color.0.set_a(alpha * amount);
+                }
+                if let Some(mut text) = world.get_mut::<Text>(node) {
+                    for (section, alpha) in text.sections.iter_mut().zip(base.text.iter()) {
+                        section.style.color.set_a(alpha * amount);
+                    }
+                }
+                if let Some(children) = world.get::<Children>(node) {
+                    stack.extend(children.iter().copied());
+                }
+            }
+        }
+        Tween::Scale => {
+            for node in nodes {
+                if let Some(mut transform) = world.get_mut::<Transform>(node) {
+                    transform.scale = Vec3::splat(amount);
+                }
+            }
+        }
+    }
+}
+
+impl BaseAlpha {
+    fn of(world: &World, node: Entity) -> Self {
+        Self {
+            background: world.get::<BackgroundColor>(node).map(|color| color.0.a()),
+            text: world
+                .get::<Text>(node)
+                .map(|text| {
+                    text.sections
+                        .iter()
+                        .map(|section| section.style.color.a())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn elapsed(world: &World) -> Duration {
+    world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default()
+}
+
+/// A view which plays an animation when its child is mounted and unmounted. The enter
+/// animation plays after the child has been built, and razing the child is deferred until
+/// the exit animation has finished. While exiting, the child's display nodes are kept in
+/// their display parent, even though the view is no longer part of the view tree.
+///
+/// Animations are advanced by the [`update_animated_views`] system, and exiting nodes are
+/// kept attached by the [`attach_exiting_views`] system.
+pub struct AnimatedView {
+    child: ViewRef,
+    enter: Animation,
+    exit: Animation,
+    child_entity: Option<Entity>,
+}
+
+impl AnimatedView {
+    /// Construct a new `AnimatedView`.
+    pub fn new(child: impl IntoView, enter: Animation, exit: Animation) -> Self {
+        Self {
+            child: child.into_view(),
+            enter,
+            exit,
+            child_entity: None,
+        }
+    }
+
+    fn set_child_visibility(&self, world: &mut World, visibility: Visibility) {
+        for node in self.nodes(world).iter() {
+            if let Some(mut current) = world.get_mut::<Visibility>(node) {
+                if *current != visibility {
+                    *current = visibility;
+                }
+            }
+        }
+    }
+
+    /// Returns true if the child should be hidden, because it is waiting for an enter
+    /// animation which has no tween.
+    fn is_delayed(view_entity: Entity, world: &World) -> bool {
+        world
+            .get::<AnimationState>(view_entity)
+            .is_some_and(|s| s.phase == AnimationPhase::Entering && s.tween == Tween::None)
+    }
+}
+
+impl View for AnimatedView {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.child_entity {
+            Some(entity) => child_nodes(&self.child, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let child_entity = ViewHandle::spawn(&self.child, view_entity, world);
+        self.child_entity = Some(child_entity);
+        let phase = if self.enter.duration.is_zero() {
+            AnimationPhase::Visible
+        } else {
+            AnimationPhase::Entering
+        };
+        let start = elapsed(world);
+        let mut state = AnimationState {
+            phase,
+            start,
+            end: start + self.enter.duration,
+            tween: self.enter.tween,
+            child: (self.child.clone(), child_entity),
+            base_alpha: HashMap::default(),
+            slot: None,
+        };
+        if phase == AnimationPhase::Entering {
+            let nodes = state.nodes(world).flat_collect();
+            apply_tween(world, state.tween, nodes, &mut state.base_alpha, 0.);
+        }
+        world.entity_mut(view_entity).insert(state);
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let state_id = world.init_component::<AnimationState>();
+        tracking.add_component(view_entity, state_id);
+        if Self::is_delayed(view_entity, world) {
+            self.set_child_visibility(world, Visibility::Hidden);
+        } else if world
+            .get::<AnimationState>(view_entity)
+            .is_some_and(|s| s.phase != AnimationPhase::Exiting)
+        {
+            self.set_child_visibility(world, Visibility::Inherited);
+        }
+    }
+
+    fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
+        // New display nodes need to be hidden if the enter animation hasn't finished.
+        if Self::is_delayed(view_entity, world) {
+            self.set_child_visibility(world, Visibility::Hidden);
+        }
+        false
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let Some(child_entity) = self.child_entity.take() else {
+            return;
+        };
+        if self.exit.duration.is_zero() {
+            self.child.lock().unwrap().raze(child_entity, world);
+            world.despawn_owned_recursive(view_entity);
+            return;
+        }
+
+        // Remember where the nodes are displayed, so that they can stay there while the exit
+        // animation plays.
+        let nodes = child_nodes(&self.child, child_entity, world).flat_collect();
+        let slot = nodes.first().and_then(|first| {
+            let parent = world.get::<Parent>(*first)?.get();
+            let index = world
+                .get::<Children>(parent)?
+                .iter()
+                .position(|child| child == first)?;
+            Some((parent, index))
+        });
+
+        // Keep the view alive until the exit animation has finished.
+        let start = elapsed(world);
+        let mut entt = world.entity_mut(view_entity);
+        entt.remove_parent();
+        if let Some(mut state) = entt.get_mut::<AnimationState>() {
+            if state.phase == AnimationPhase::Entering {
+                // Cut the enter animation short.
+                state.base_alpha.clear();
+            }
+            state.phase = AnimationPhase::Exiting;
+            state.start = start;
+            state.end = start + self.exit.duration;
+            state.tween = self.exit.tween;
+            state.slot = slot;
+        }
+    }
+}
+
+impl IntoView for AnimatedView {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// System which advances the animations of [`AnimatedView`]s, and razes views whose exit
+/// animation has finished.
+pub fn update_animated_views(world: &mut World) {
+    let now = elapsed(world);
+    let mut query = world.query::<(Entity, &AnimationState)>();
+    let animating = query
+        .iter(world)
+        .filter(|(_, state)| state.phase != AnimationPhase::Visible)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    for entity in animating {
+        // Only a change of phase affects the view, so progress is made without triggering
+        // change detection.
+        let Some(mut state) = world.get_mut::<AnimationState>(entity) else {
+            continue;
+        };
+        let state = state.bypass_change_detection();
+        let (phase, start, end, tween) = (state.phase, state.start, state.end, state.tween);
+        let (child, child_entity) = state.child.clone();
+        let mut base_alpha = std::mem::take(&mut state.base_alpha);
+
+        let span = end.saturating_sub(start).as_secs_f32();
+        let t = if span > 0. {
+            (now.saturating_sub(start).as_secs_f32() / span).min(1.)
+        } else {
+            1.
+        };
+        let nodes = child_nodes(&child, child_entity, world).flat_collect();
+        let finished = now >= end;
+        match phase {
+            AnimationPhase::Entering => apply_tween(world, tween, nodes, &mut base_alpha, t),
+            AnimationPhase::Exiting if finished => {
+                // The display nodes may already have been despawned along with their display
+                // parent, as when an enclosing scroll view is razed. The child can't be razed
+                // then, so only its view entities are released.
+                if nodes.iter().all(|node| world.get_entity(*node).is_some()) {
+                    child.lock().unwrap().raze(child_entity, world);
+                } else {
+                    release_view_tree(world, child_entity);
+                    for node in nodes {
+                        if let Some(entt) = world.get_entity_mut(node) {
+                            entt.despawn_recursive();
+                        }
+                    }
+                }
+                world.despawn_owned_recursive(entity);
+                continue;
+            }
+            AnimationPhase::Exiting => apply_tween(world, tween, nodes, &mut base_alpha, 1. - t),
+            AnimationPhase::Visible => {}
+        }
+
+        let Some(mut state) = world.get_mut::<AnimationState>(entity) else {
+            continue;
+        };
+        if finished {
+            state.phase = AnimationPhase::Visible;
+        } else {
+            state.bypass_change_detection().base_alpha = base_alpha;
+        }
+    }
+}
+
+/// Release the tracking scopes of a view entity and its descendants, and despawn them, without
+/// razing the views.
+fn release_view_tree(world: &mut World, view_entity: Entity) {
+    let children = world
+        .get::<Children>(view_entity)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+    for child in children {
+        release_view_tree(world, child);
+    }
+    world.despawn_owned_recursive(view_entity);
+    if let Some(entt) = world.get_entity_mut(view_entity) {
+        entt.despawn_recursive();
+    }
+}
+
+/// System which puts the display nodes of exiting [`AnimatedView`]s back into their display
+/// parent, after the parent's children have been replaced by [`attach_child_views`]. The nodes
+/// are inserted at the position they had when the view was razed.
+///
+/// [`attach_child_views`]: crate::attach_child_views
+pub fn attach_exiting_views(world: &mut World) {
+    let mut query = world.query::<&AnimationState>();
+    let exiting = query
+        .iter(world)
+        .filter_map(|state| Some((state.slot?, state.nodes(world).flat_collect())))
+        .collect::<Vec<_>>();
+    for ((parent, index), nodes) in exiting {
+        let detached = nodes
+            .iter()
+            .any(|node| world.get::<Parent>(*node).map(|p| p.get()) != Some(parent));
+        if !detached || world.get_entity(parent).is_none() {
+            continue;
+        }
+        let len = world.get::<Children>(parent).map_or(0, |children| {
+            children
+                .iter()
+                .filter(|child| !nodes.contains(child))
+                .count()
+        });
+        world
+            .entity_mut(parent)
+            .insert_children(index.min(len), &nodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run_reactions;
+
+    use super::*;
+
+    fn advance(world: &mut World, millis: u64) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        world.increment_change_tick();
+        update_animated_views(world);
+        run_reactions(world);
+    }
+
+    #[test]
+    fn test_animated_view() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let parent = world.spawn_empty().id();
+        let view = AnimatedView::new(
+            "Hello",
+            Animation::new(Duration::from_millis(100)),
+            Animation::new(Duration::from_millis(200)),
+        )
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(&world) else {
+            panic!("Expected a single node");
+        };
+
+        // Hidden until the enter animation has finished.
+        assert_eq!(*world.get::<Visibility>(node).unwrap(), Visibility::Hidden);
+        advance(&mut world, 50);
+        assert_eq!(*world.get::<Visibility>(node).unwrap(), Visibility::Hidden);
+        advance(&mut world, 60);
+        assert_eq!(
+            *world.get::<Visibility>(node).unwrap(),
+            Visibility::Inherited
+        );
+
+        // Razing is delayed until the exit animation has finished.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(node).is_some());
+        advance(&mut world, 150);
+        assert!(world.get_entity(node).is_some());
+        advance(&mut world, 60);
+        assert!(world.get_entity(node).is_none());
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    fn alpha(world: &World, node: Entity) -> f32 {
+        world.get::<Text>(node).unwrap().sections[0].style.color.a()
+    }
+
+    #[test]
+    fn test_fade() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let parent = world.spawn_empty().id();
+        let view = AnimatedView::new(
+            "Hello",
+            Animation::fade(Duration::from_millis(100)),
+            Animation::fade(Duration::from_millis(100)),
+        )
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(&world) else {
+            panic!("Expected a single node");
+        };
+
+        // The node is shown while it fades in.
+        assert_eq!(alpha(&world, node), 0.);
+        advance(&mut world, 50);
+        assert_eq!(
+            *world.get::<Visibility>(node).unwrap(),
+            Visibility::Inherited
+        );
+        assert!((alpha(&world, node) - 0.5).abs() < 0.01);
+        advance(&mut world, 60);
+        assert_eq!(alpha(&world, node), 1.);
+
+        // And fades out again before it is razed.
+        view.lock().unwrap().raze(entity, &mut world);
+        advance(&mut world, 50);
+        assert!((alpha(&world, node) - 0.5).abs() < 0.01);
+        advance(&mut world, 60);
+        assert!(world.get_entity(node).is_none());
+    }
+
+    #[test]
+    fn test_exiting_nodes_stay_attached() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let parent = world.spawn_empty().id();
+        let view = AnimatedView::new(
+            "Hello",
+            Animation::none(),
+            Animation::new(Duration::from_millis(100)),
+        )
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(&world) else {
+            panic!("Expected a single node");
+        };
+        let sibling = world.spawn_empty().id();
+        let display = world.spawn_empty().push_children(&[node, sibling]).id();
+
+        // The parent view replaces the display children after the view is razed.
+        view.lock().unwrap().raze(entity, &mut world);
+        world.entity_mut(display).replace_children(&[sibling]);
+        attach_exiting_views(&mut world);
+        assert_eq!(
+            world.get::<Children>(display).unwrap().to_vec(),
+            vec![node, sibling]
+        );
+
+        advance(&mut world, 110);
+        assert!(world.get_entity(node).is_none());
+    }
+
+    #[test]
+    fn test_display_parent_razed_while_exiting() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let parent = world.spawn_empty().id();
+        let view = crate::ScrollView::new(AnimatedView::new(
+            "Hello",
+            Animation::none(),
+            Animation::new(Duration::from_millis(100)),
+        ))
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let animated = world.query::<(Entity, &AnimationState)>().single(&world).0;
+        let node = world.query_filtered::<Entity, With<Text>>().single(&world);
+
+        // Razing the scroll view despawns its viewport, and the exiting text node with it.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(node).is_none());
+        assert!(world.get_entity(animated).is_some());
+
+        // Once the exit animation has finished, the animated view is released.
+        advance(&mut world, 110);
+        assert!(world.get_entity(animated).is_none());
+        assert_eq!(world.query::<&TrackingScope>().iter(&world).count(), 0);
+    }
+}
@@ -1,8 +1,15 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    marker::PhantomData,
+    ops::Add,
+    sync::{Arc, Mutex},
+};
 
-use bevy::ecs::{bundle::Bundle, entity::Entity, world::World};
+use bevy::{
+    ecs::{bundle::Bundle, component::Component, entity::Entity, world::World},
+    utils::HashSet,
+};
 
-use crate::{scope::TrackingScope, Rcx, Reaction, ReactionHandle};
+use crate::{scope::TrackingScope, Rcx, Reaction, ReactionHandle, ReactionWrite};
 
 /// Trait that produces a bundle and inserts it into the target entity.
 pub trait BundleProducer: Sync + Send {
@@ -10,10 +17,73 @@ pub trait BundleProducer: Sync + Send {
     ///
     /// Arguments:
     /// - `owner`: The entity that tracks ownership of this reaction, the reaction
-    ///     will be deleted when the owner is deleted.
+    ///   will be deleted when the owner is deleted.
     /// - `target`: The entity that the bundle will be inserted into.
     /// - `world`: The Bevy world.
     fn start(&mut self, tracking: &mut TrackingScope, target: Entity, world: &mut World);
+
+    /// Stop updating the target entity, despawning any reaction created by
+    /// [`start`](BundleProducer::start). Does nothing by default.
+    ///
+    /// This is not called automatically. If the target entity is despawned while the owner is
+    /// still alive, [`stop_orphaned_bundle_reactions`] despawns the reaction entity directly,
+    /// so producers should not rely on `stop` for other cleanup.
+    fn stop(&mut self, _owner: Entity, _target: Entity, _world: &mut World) {}
+}
+
+/// Component on the entities that bundle producers insert bundles into.
+#[derive(Component)]
+pub(crate) struct BundleTarget;
+
+/// Component on the reaction entity of a bundle producer, which holds the target entity.
+#[derive(Component)]
+pub(crate) struct BundleReactionTarget(pub(crate) Entity);
+
+/// Spawn the entity which holds a bundle producer's reaction.
+pub(crate) fn spawn_reaction(
+    reaction: ReactionHandle,
+    target: Entity,
+    world: &mut World,
+) -> Entity {
+    world.entity_mut(target).insert(BundleTarget);
+    world.spawn((reaction, BundleReactionTarget(target))).id()
+}
+
+/// Despawn a bundle producer's reaction entity. Does nothing if the reaction is currently
+/// executing.
+pub(crate) fn stop_reaction<R>(
+    reaction: &Mutex<R>,
+    reaction_id: &mut Option<Entity>,
+    world: &mut World,
+) {
+    if reaction.try_lock().is_err() {
+        return;
+    }
+    if let Some(entt) = reaction_id.take().and_then(|id| world.get_entity_mut(id)) {
+        entt.despawn();
+    }
+}
+
+/// System which despawns the reactions of bundle producers whose target entity has been
+/// despawned, so that they no longer run. The producers themselves are owned by their views,
+/// and so [`BundleProducer::stop`] is not called.
+pub fn stop_orphaned_bundle_reactions(world: &mut World) {
+    let despawned: HashSet<Entity> = world
+        .removed::<BundleTarget>()
+        .filter(|target| world.get_entity(*target).is_none())
+        .collect();
+    if despawned.is_empty() {
+        return;
+    }
+    let mut query = world.query::<(Entity, &BundleReactionTarget)>();
+    let orphaned: Vec<Entity> = query
+        .iter(world)
+        .filter(|(_, target)| despawned.contains(&target.0))
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in orphaned {
+        world.entity_mut(entity).despawn();
+    }
 }
 
 /// Inserts a static, pre-constructed bundle into the target entity. No reactivity.
@@ -21,6 +91,15 @@ pub struct BundleStatic<B: Bundle> {
     pub(crate) bundle: Option<B>,
 }
 
+impl<B: Bundle> BundleStatic<B> {
+    /// Construct a new `BundleStatic` which inserts `bundle`.
+    pub fn new(bundle: B) -> Self {
+        Self {
+            bundle: Some(bundle),
+        }
+    }
+}
+
 impl<B: Bundle> BundleProducer for BundleStatic<B> {
     // For a static bundle, we can just insert it once.
     fn start(&mut self, _tracking: &mut TrackingScope, target: Entity, world: &mut World) {
@@ -31,15 +110,19 @@ impl<B: Bundle> BundleProducer for BundleStatic<B> {
 /// Produces a bundle reactively, returns the bundle as a result.
 pub struct BundleComputed<B: Bundle, F: FnMut(&mut Rcx) -> B> {
     reaction: Arc<Mutex<BundleComputedReaction<B, F>>>,
+    reaction_id: Option<Entity>,
 }
 
 impl<B: Bundle, F: FnMut(&mut Rcx) -> B> BundleComputed<B, F> {
-    pub(crate) fn new(factory: F) -> Self {
+    /// Construct a new `BundleComputed` which reactively inserts the bundle returned by
+    /// `factory`.
+    pub fn new(factory: F) -> Self {
         Self {
             reaction: Arc::new(Mutex::new(BundleComputedReaction {
                 target: None,
                 factory,
             })),
+            reaction_id: None,
         }
     }
 }
@@ -51,11 +134,29 @@ pub struct BundleComputedReaction<B: Bundle, F: FnMut(&mut Rcx) -> B> {
 }
 
 impl<B: Bundle, F: Sync + Send + FnMut(&mut Rcx) -> B> Reaction for BundleComputedReaction<B, F> {
-    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let write = self.compute(owner, world, tracking);
+        write(world);
+    }
+
+    fn write_set(&self) -> Option<Vec<Entity>> {
+        self.target.map(|target| vec![target])
+    }
+
+    fn compute(
+        &mut self,
+        _owner: Entity,
+        world: &World,
+        tracking: &mut TrackingScope,
+    ) -> ReactionWrite {
+        let target = self.target.unwrap();
         let mut re = Rcx::new(world, tracking);
         let b = (self.factory)(&mut re);
-        let mut entt = world.entity_mut(self.target.unwrap());
-        entt.insert(b);
+        Box::new(move |world| {
+            if let Some(mut entt) = world.get_entity_mut(target) {
+                entt.insert(b);
+            }
+        })
     }
 }
 
@@ -73,7 +174,8 @@ impl<B: Bundle, F: Sync + Send + 'static + FnMut(&mut Rcx) -> B> BundleProducer
         reaction.target = Some(target);
 
         // Store the reaction in a handle and add it to the world.
-        let reaction_id = world.spawn(ReactionHandle(self.reaction.clone())).id();
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
 
         // Call `react` the first time, update the scope with initial deps.
         reaction.react(reaction_id, world, &mut scope);
@@ -84,49 +186,626 @@ impl<B: Bundle, F: Sync + Send + 'static + FnMut(&mut Rcx) -> B> BundleProducer
         // Add the reaction id to the parent scope so that it can be despawned later.
         parent_scope.add_owned(reaction_id);
     }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+/// Allows reactively mutating a single component. Allows specifying the initial component value,
+/// as well as an update function the mutates the component in place.
+pub struct BundleComputedRef<C: Component, F1: FnOnce() -> C, F2: FnMut(&mut Rcx, &mut C)> {
+    init: Option<F1>,
+    reaction: Arc<Mutex<BundleComputedRefReaction<C, F2>>>,
+    reaction_id: Option<Entity>,
+}
+
+impl<C: Component, F1: FnOnce() -> C, F2: FnMut(&mut Rcx, &mut C)> BundleComputedRef<C, F1, F2> {
+    /// Construct a new `BundleComputedRef` which inserts the component returned by `init`,
+    /// and reactively updates it with `update`.
+    pub fn new(init: F1, update: F2) -> Self {
+        Self {
+            init: Some(init),
+            reaction: Arc::new(Mutex::new(BundleComputedRefReaction {
+                target: None,
+                update,
+                marker: PhantomData,
+            })),
+            reaction_id: None,
+        }
+    }
+}
+
+/// Reaction which updates a component in place.
+pub struct BundleComputedRefReaction<C: Component, F: FnMut(&mut Rcx, &mut C)> {
+    pub(crate) target: Option<Entity>,
+    pub(crate) update: F,
+    pub(crate) marker: PhantomData<C>,
+}
+
+impl<C: Component + Clone + PartialEq, F: Sync + Send + FnMut(&mut Rcx, &mut C)> Reaction
+    for BundleComputedRefReaction<C, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let write = self.compute(owner, world, tracking);
+        write(world);
+    }
+
+    fn write_set(&self) -> Option<Vec<Entity>> {
+        self.target.map(|target| vec![target])
+    }
+
+    fn compute(
+        &mut self,
+        _owner: Entity,
+        world: &World,
+        tracking: &mut TrackingScope,
+    ) -> ReactionWrite {
+        let target = self.target.unwrap();
+        // The update function is run on a copy of the component, since it needs to read from
+        // the world at the same time.
+        let Some(mut cmp) = world.get::<C>(target).cloned() else {
+            return Box::new(|_| {});
+        };
+        let mut re = Rcx::new(world, tracking);
+        (self.update)(&mut re, &mut cmp);
+        Box::new(move |world| {
+            // Only write back if the value changed, so as not to trigger change detection.
+            if let Some(mut current) = world.get_mut::<C>(target) {
+                if *current != cmp {
+                    *current = cmp;
+                }
+            }
+        })
+    }
+}
+
+impl<
+        C: Component + Clone + PartialEq,
+        F1: Sync + Send + FnOnce() -> C,
+        F2: Sync + Send + 'static + FnMut(&mut Rcx, &mut C),
+    > BundleProducer for BundleComputedRef<C, F1, F2>
+{
+    // Insert the initial component value, then start a reaction which updates it.
+    fn start(&mut self, parent_scope: &mut TrackingScope, target: Entity, world: &mut World) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        world
+            .entity_mut(target)
+            .insert((self.init.take().unwrap())());
+
+        let mut reaction = self.reaction.lock().unwrap();
+        reaction.target = Some(target);
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
+        reaction.react(reaction_id, world, &mut scope);
+        world.entity_mut(reaction_id).insert(scope);
+        parent_scope.add_owned(reaction_id);
+    }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+/// Reactively computes a single component and inserts it into the target entity. Unlike
+/// [`BundleComputed`], once the component has been inserted it is updated in place, and only
+/// when the computed value differs from the current one.
+pub struct ComponentComputed<C: Component, F: FnMut(&mut Rcx) -> C> {
+    reaction: Arc<Mutex<ComponentComputedReaction<C, F>>>,
+    reaction_id: Option<Entity>,
+}
+
+impl<C: Component, F: FnMut(&mut Rcx) -> C> ComponentComputed<C, F> {
+    /// Construct a new `ComponentComputed` which reactively sets the component returned by
+    /// `factory`.
+    pub fn new(factory: F) -> Self {
+        Self {
+            reaction: Arc::new(Mutex::new(ComponentComputedReaction {
+                target: None,
+                factory,
+            })),
+            reaction_id: None,
+        }
+    }
+}
+
+/// Reaction which computes a single component.
+pub struct ComponentComputedReaction<C: Component, F: FnMut(&mut Rcx) -> C> {
+    pub(crate) target: Option<Entity>,
+    pub(crate) factory: F,
+}
+
+impl<C: Component + PartialEq, F: Sync + Send + FnMut(&mut Rcx) -> C> Reaction
+    for ComponentComputedReaction<C, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let write = self.compute(owner, world, tracking);
+        write(world);
+    }
+
+    fn write_set(&self) -> Option<Vec<Entity>> {
+        self.target.map(|target| vec![target])
+    }
+
+    fn compute(
+        &mut self,
+        _owner: Entity,
+        world: &World,
+        tracking: &mut TrackingScope,
+    ) -> ReactionWrite {
+        let target = self.target.unwrap();
+        let mut re = Rcx::new(world, tracking);
+        let cmp = (self.factory)(&mut re);
+        Box::new(move |world| match world.get_mut::<C>(target) {
+            Some(mut current) => {
+                if *current != cmp {
+                    *current = cmp;
+                }
+            }
+            None => {
+                if let Some(mut entt) = world.get_entity_mut(target) {
+                    entt.insert(cmp);
+                }
+            }
+        })
+    }
+}
+
+impl<C: Component + PartialEq, F: Sync + Send + 'static + FnMut(&mut Rcx) -> C> BundleProducer
+    for ComponentComputed<C, F>
+{
+    fn start(&mut self, parent_scope: &mut TrackingScope, target: Entity, world: &mut World) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut reaction = self.reaction.lock().unwrap();
+        reaction.target = Some(target);
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
+        reaction.react(reaction_id, world, &mut scope);
+        world.entity_mut(reaction_id).insert(scope);
+        parent_scope.add_owned(reaction_id);
+    }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+/// Reactively mutates a single component in place. If the target entity does not yet have
+/// the component, the default value is inserted first.
+pub struct ComponentComputedRef<C: Component, F: FnMut(&mut Rcx, &mut C)> {
+    reaction: Arc<Mutex<BundleComputedRefReaction<C, F>>>,
+    reaction_id: Option<Entity>,
+}
+
+impl<C: Component, F: FnMut(&mut Rcx, &mut C)> ComponentComputedRef<C, F> {
+    /// Construct a new `ComponentComputedRef` which reactively modifies the component with
+    /// `update`.
+    pub fn new(update: F) -> Self {
+        Self {
+            reaction: Arc::new(Mutex::new(BundleComputedRefReaction {
+                target: None,
+                update,
+                marker: PhantomData,
+            })),
+            reaction_id: None,
+        }
+    }
+}
+
+impl<
+        C: Component + Clone + PartialEq + Default,
+        F: Sync + Send + 'static + FnMut(&mut Rcx, &mut C),
+    > BundleProducer for ComponentComputedRef<C, F>
+{
+    fn start(&mut self, parent_scope: &mut TrackingScope, target: Entity, world: &mut World) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        if world.get::<C>(target).is_none() {
+            world.entity_mut(target).insert(C::default());
+        }
+
+        let mut reaction = self.reaction.lock().unwrap();
+        reaction.target = Some(target);
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
+        reaction.react(reaction_id, world, &mut scope);
+        world.entity_mut(reaction_id).insert(scope);
+        parent_scope.add_owned(reaction_id);
+    }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+/// Inserts a bundle into the target entity while a reactive condition is true, and removes
+/// the components of the bundle when the condition becomes false.
+pub struct BundleConditional<B: Bundle + Clone, F: FnMut(&mut Rcx) -> bool> {
+    reaction: Arc<Mutex<BundleConditionalReaction<B, F>>>,
+    reaction_id: Option<Entity>,
+}
+
+impl<B: Bundle + Clone, F: FnMut(&mut Rcx) -> bool> BundleConditional<B, F> {
+    /// Construct a new `BundleConditional` which inserts `bundle` while `condition` returns
+    /// true.
+    pub fn new(condition: F, bundle: B) -> Self {
+        Self {
+            reaction: Arc::new(Mutex::new(BundleConditionalReaction {
+                target: None,
+                condition,
+                bundle,
+                inserted: false,
+            })),
+            reaction_id: None,
+        }
+    }
+}
+
+/// Reaction which inserts or removes a bundle.
+pub struct BundleConditionalReaction<B: Bundle + Clone, F: FnMut(&mut Rcx) -> bool> {
+    pub(crate) target: Option<Entity>,
+    pub(crate) condition: F,
+    pub(crate) bundle: B,
+    pub(crate) inserted: bool,
+}
+
+impl<B: Bundle + Clone, F: Sync + Send + FnMut(&mut Rcx) -> bool> Reaction
+    for BundleConditionalReaction<B, F>
+{
+    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let mut re = Rcx::new(world, tracking);
+        let condition = (self.condition)(&mut re);
+        // Only insert or remove when the condition changes, so as not to re-insert the bundle.
+        if condition == self.inserted {
+            return;
+        }
+        self.inserted = condition;
+        let Some(mut entt) = world.get_entity_mut(self.target.unwrap()) else {
+            return;
+        };
+        if condition {
+            entt.insert(self.bundle.clone());
+        } else {
+            entt.remove::<B>();
+        }
+    }
+}
+
+impl<B: Bundle + Clone, F: Sync + Send + 'static + FnMut(&mut Rcx) -> bool> BundleProducer
+    for BundleConditional<B, F>
+{
+    fn start(&mut self, parent_scope: &mut TrackingScope, target: Entity, world: &mut World) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut reaction = self.reaction.lock().unwrap();
+        reaction.target = Some(target);
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
+        reaction.react(reaction_id, world, &mut scope);
+        world.entity_mut(reaction_id).insert(scope);
+        parent_scope.add_owned(reaction_id);
+    }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+/// Runs several producers, in order, on the same target. Can also be constructed by adding
+/// producers together, e.g. `BundleStatic::new(layout) + BundleComputed::new(style)`.
+pub struct BundleSequence {
+    producers: Vec<Box<dyn BundleProducer>>,
+}
+
+impl BundleSequence {
+    /// Construct a new `BundleSequence` from a list of producers.
+    pub fn new(producers: Vec<Box<dyn BundleProducer>>) -> Self {
+        Self { producers }
+    }
+}
+
+impl BundleProducer for BundleSequence {
+    fn start(&mut self, tracking: &mut TrackingScope, target: Entity, world: &mut World) {
+        for producer in self.producers.iter_mut() {
+            producer.start(tracking, target, world);
+        }
+    }
+
+    fn stop(&mut self, owner: Entity, target: Entity, world: &mut World) {
+        for producer in self.producers.iter_mut() {
+            producer.stop(owner, target, world);
+        }
+    }
+}
+
+impl<R: BundleProducer + 'static> Add<R> for BundleSequence {
+    type Output = BundleSequence;
+
+    fn add(mut self, rhs: R) -> Self::Output {
+        self.producers.push(Box::new(rhs));
+        self
+    }
+}
+
+impl<B: Bundle, R: BundleProducer + 'static> Add<R> for BundleStatic<B> {
+    type Output = BundleSequence;
+
+    fn add(self, rhs: R) -> Self::Output {
+        BundleSequence::new(vec![Box::new(self), Box::new(rhs)])
+    }
 }
 
-// /// Allows reactively mutating a single component. Allows specifying the initial component value,
-// /// as well as an update function the mutates the component in place.
-// pub struct BundleComputedRef<C: Component, F1: FnMut() -> C, F2: FnMut(&mut Re, &mut C)> {
-//     pub(crate) target: Option<Entity>,
-//     pub(crate) init: F1,
-//     pub(crate) update: F2,
-//     pub(crate) marker: PhantomData<C>,
-//     pub(crate) tracker: Option<Entity>,
-// }
-
-// impl<C: Component, F1: Send + Sync + FnMut() -> C, F2: Send + Sync + FnMut(&mut Re, &mut C)>
-//     BundleProducer for BundleComputedRef<C, F1, F2>
-// {
-//     // Insert the bundle on build, then update.
-//     fn init(&mut self, target: Entity, world: &mut World) {
-//         assert!(self.tracker.is_none());
-//         let mut scope = TrackingScope::new(world.change_tick());
-//         let b = (self.init)();
-//         let mut entt = world.entity_mut(target);
-//         entt.insert(b);
-//         self.target = Some(target);
-//         self.react(target, world, &mut scope);
-//         let tracker = world.spawn(scope);
-//         self.tracker = Some(tracker.id());
-//     }
-// }
-
-// impl<C: Component, F1: Send + Sync + FnMut() -> C, F2: Send + Sync + FnMut(&mut Re, &mut C)>
-//     Reaction for BundleComputedRef<C, F1, F2>
-// {
-//     fn react(&mut self, target: Entity, world: &mut World, tracking: &mut TrackingScope) {
-//         let mut entt = world.entity_mut(target);
-//         let mut cmp = entt.get_mut::<C>().unwrap();
-//         let mut re = Re::new(world, tracking);
-//         todo!();
-//         // (self.update)(&mut re, &mut cmp);
-//     }
-
-//     fn cleanup(&mut self, owner: Entity, world: &mut World) {
-//         assert!(self.tracker.is_some());
-//         world.entity_mut(self.tracker.unwrap()).despawn();
-//         self.tracker = None;
-//     }
-// }
+impl<B: Bundle, F: Sync + Send + 'static + FnMut(&mut Rcx) -> B, R: BundleProducer + 'static> Add<R>
+    for BundleComputed<B, F>
+{
+    type Output = BundleSequence;
+
+    fn add(self, rhs: R) -> Self::Output {
+        BundleSequence::new(vec![Box::new(self), Box::new(rhs)])
+    }
+}
+
+impl<
+        C: Component + Clone + PartialEq,
+        F1: Sync + Send + 'static + FnOnce() -> C,
+        F2: Sync + Send + 'static + FnMut(&mut Rcx, &mut C),
+        R: BundleProducer + 'static,
+    > Add<R> for BundleComputedRef<C, F1, F2>
+{
+    type Output = BundleSequence;
+
+    fn add(self, rhs: R) -> Self::Output {
+        BundleSequence::new(vec![Box::new(self), Box::new(rhs)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{run_reactions, DespawnScopes, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Width(f32);
+
+    #[test]
+    fn test_sequence() {
+        let mut world = World::default();
+        world.insert_resource(Width(10.));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleStatic::new(Visibility::Hidden)
+            + BundleComputed::new(|re| Style {
+                width: Val::Px(re.use_resource::<Width>().0),
+                ..default()
+            });
+        producer.start(&mut scope, target, &mut world);
+        assert_eq!(
+            *world.get::<Visibility>(target).unwrap(),
+            Visibility::Hidden
+        );
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(10.));
+
+        // The reactive bundle updates without touching the static one.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Width>().0 = 20.;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(20.));
+        let ticks = world
+            .entity(target)
+            .get_change_ticks::<Visibility>()
+            .unwrap();
+        assert!(!ticks.is_changed(since, world.change_tick()));
+    }
+
+    #[derive(Resource)]
+    struct Hovered(bool);
+
+    #[derive(Component, Clone, Default, PartialEq)]
+    struct Background(Color);
+
+    fn hover_color(hovered: bool) -> Color {
+        if hovered {
+            Color::RED
+        } else {
+            Color::GRAY
+        }
+    }
+
+    #[test]
+    fn test_component_computed() {
+        let mut world = World::default();
+        world.insert_resource(Hovered(false));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer =
+            ComponentComputed::new(|re| Background(hover_color(re.use_resource::<Hovered>().0)));
+        producer.start(&mut scope, target, &mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::GRAY);
+
+        // Same value: the component is not modified.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Hovered>().0 = false;
+        run_reactions(&mut world);
+        let ticks = world
+            .entity(target)
+            .get_change_ticks::<Background>()
+            .unwrap();
+        assert!(!ticks.is_changed(since, world.change_tick()));
+
+        // New value: the component is updated in place.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Hovered>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::RED);
+        let ticks = world
+            .entity(target)
+            .get_change_ticks::<Background>()
+            .unwrap();
+        assert!(ticks.is_changed(since, world.change_tick()));
+        assert!(!ticks.is_added(since, world.change_tick()));
+    }
+
+    #[test]
+    fn test_component_computed_ref() {
+        let mut world = World::default();
+        world.insert_resource(Hovered(false));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = ComponentComputedRef::new(|re, color: &mut Background| {
+            color.0 = hover_color(re.use_resource::<Hovered>().0);
+        });
+        producer.start(&mut scope, target, &mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::GRAY);
+
+        // Same value: the component is not modified.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Hovered>().0 = false;
+        run_reactions(&mut world);
+        let ticks = world
+            .entity(target)
+            .get_change_ticks::<Background>()
+            .unwrap();
+        assert!(!ticks.is_changed(since, world.change_tick()));
+
+        world.increment_change_tick();
+        world.resource_mut::<Hovered>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::RED);
+    }
+
+    #[test]
+    fn test_conditional() {
+        let mut world = World::default();
+        world.insert_resource(Hovered(false));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleConditional::new(
+            |re| re.use_resource::<Hovered>().0,
+            (Background(Color::RED), Visibility::Visible),
+        );
+        producer.start(&mut scope, target, &mut world);
+        assert!(world.get::<Background>(target).is_none());
+
+        // Condition becomes true: the bundle is inserted.
+        world.clear_trackers();
+        world.resource_mut::<Hovered>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::RED);
+        assert!(world.get::<Visibility>(target).is_some());
+
+        // Condition stays true: the bundle is not inserted again.
+        world.get_mut::<Background>(target).unwrap().0 = Color::BLUE;
+        world.clear_trackers();
+        world.resource_mut::<Hovered>().0 = true;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Background>(target).unwrap().0, Color::BLUE);
+
+        // Condition becomes false: the bundle is removed.
+        world.clear_trackers();
+        world.resource_mut::<Hovered>().0 = false;
+        run_reactions(&mut world);
+        assert!(world.get::<Background>(target).is_none());
+        assert!(world.get::<Visibility>(target).is_none());
+    }
+
+    #[test]
+    fn test_computed_ref() {
+        let mut world = World::default();
+        world.insert_resource(Width(10.));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleComputedRef::new(Style::default, |re, style: &mut Style| {
+            style.width = Val::Px(re.use_resource::<Width>().0);
+        });
+        producer.start(&mut scope, target, &mut world);
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(10.));
+
+        // Same value: the component is not modified.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Width>().0 = 10.;
+        run_reactions(&mut world);
+        let ticks = world.entity(target).get_change_ticks::<Style>().unwrap();
+        assert!(!ticks.is_changed(since, world.change_tick()));
+
+        // New value: the component is updated in place, not re-inserted.
+        world.clear_trackers();
+        let since = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Width>().0 = 20.;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(20.));
+        let ticks = world.entity(target).get_change_ticks::<Style>().unwrap();
+        assert!(ticks.is_changed(since, world.change_tick()));
+        assert!(!ticks.is_added(since, world.change_tick()));
+    }
+
+    #[test]
+    fn test_stop_on_target_despawn() {
+        let mut world = World::default();
+        world.insert_resource(Width(10.));
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleComputed::new(|re| Style {
+            width: Val::Px(re.use_resource::<Width>().0),
+            ..default()
+        });
+        producer.start(&mut scope, target, &mut world);
+        let reaction_id = producer.reaction_id.unwrap();
+
+        world.entity_mut(target).despawn();
+        stop_orphaned_bundle_reactions(&mut world);
+        assert!(world.get_entity(reaction_id).is_none());
+
+        // Razing the owner afterwards does not panic.
+        let owner = world.spawn(scope).id();
+        world.despawn_owned_recursive(owner);
+    }
+
+    #[test]
+    fn test_stop() {
+        let mut world = World::default();
+        world.insert_resource(Width(10.));
+        let owner = world.spawn_empty().id();
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleComputed::new(|re| Style {
+            width: Val::Px(re.use_resource::<Width>().0),
+            ..default()
+        });
+        producer.start(&mut scope, target, &mut world);
+        let reaction_id = producer.reaction_id.unwrap();
+
+        // While the reaction is executing, stopping it does nothing.
+        let reaction = producer.reaction.clone();
+        let guard = reaction.lock().unwrap();
+        producer.stop(owner, target, &mut world);
+        assert!(world.get_entity(reaction_id).is_some());
+        drop(guard);
+
+        producer.stop(owner, target, &mut world);
+        assert!(world.get_entity(reaction_id).is_none());
+    }
+
+    #[test]
+    fn test_stop_static() {
+        let mut world = World::default();
+        let owner = world.spawn_empty().id();
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = BundleStatic::new(Visibility::Hidden);
+        producer.start(&mut scope, target, &mut world);
+        producer.stop(owner, target, &mut world);
+        assert!(world.get::<Visibility>(target).is_some());
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::{ecs::system::SystemId, log::warn, prelude::*};
+
+/// A handle to a one-shot system that was registered by a presenter via
+/// [`Cx::use_callback`](crate::Cx::use_callback). Handles are cheap to copy, and can be passed
+/// to child presenters or stored in components. The underlying system is unregistered when the
+/// presenter that created it is razed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CallbackHandle {
+    pub(crate) id: SystemId,
+}
+
+impl CallbackHandle {
+    /// Run the callback system immediately.
+    pub fn send(&self, world: &mut World) {
+        if let Err(err) = world.run_system(self.id) {
+            warn!("Callback could not be run: {:?}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, DespawnScopes, TrackingScope};
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[test]
+    fn test_callback() {
+        let mut world = World::default();
+        world.init_resource::<Counter>();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // Later runs of the presenter return the same handle.
+        let callback = Cx::new(&(), &mut world, entity, &mut scope)
+            .use_callback(|mut counter: ResMut<Counter>| counter.0 += 1);
+        let again = Cx::new(&(), &mut world, entity, &mut scope)
+            .use_callback(|mut counter: ResMut<Counter>| counter.0 += 100);
+        assert!(again == callback);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        // Each invocation runs the system exactly once.
+        callback.send(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        callback.send(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+
+        // Razing the presenter unregisters the system.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.run_system(callback.id).is_err());
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn test_use_system() {
+        let mut world = World::default();
+        world.init_resource::<Counter>();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // The system is only registered on the first run.
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+            let id = cx.run_system(|mut counter: ResMut<Counter>| counter.0 += 1);
+            let callback = cx.use_system(|mut counter: ResMut<Counter>| counter.0 += 10);
+            ids.push((id, callback));
+        }
+        assert!(ids.iter().all(|pair| *pair == ids[0]));
+        let (id, callback) = ids[0];
+        world.run_system(id).unwrap();
+        callback.send(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 11);
+
+        // Razing the presenter unregisters the systems.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.run_system(id).is_err());
+        assert!(world.run_system(callback.id).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{cx::Cx, fragment::Fragment, view_tuple::ViewTuple, EmptyView, IntoView, ViewRef};
+
+/// Child views which are passed to a wrapper presenter, such as a panel or a card, to be
+/// rendered somewhere inside of it. The wrapper places the views by calling [`Cx::slot`]; they
+/// are then built, updated and razed along with the view that contains them.
+///
+/// This is named `ChildViews` rather than `Children` so as not to collide with Bevy's
+/// [`Children`](bevy::hierarchy::Children) component.
+///
+/// `ChildViews` is deliberately not `Clone`: it holds a single view instance, which can only
+/// be mounted in one place at a time.
+pub struct ChildViews(pub ViewRef);
+
+impl ChildViews {
+    /// Construct a new `ChildViews` from a view, or a tuple of views.
+    pub fn new<V: ViewTuple>(views: V) -> Self {
+        Self(Fragment::new(views).into_view())
+    }
+}
+
+impl Default for ChildViews {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(EmptyView)))
+    }
+}
+
+/// Trait for presenter properties which contain child views. This is normally implemented
+/// using the [`component_with_children!`](crate::component_with_children) macro.
+pub trait WithChildren {
+    /// Returns the child views.
+    fn children(&self) -> &ChildViews;
+}
+
+/// Implements [`WithChildren`] for a props type, using the field `children`, or the named
+/// field if one is given.
+///
+/// ```ignore
+/// struct Card {
+///     title: String,
+///     children: ChildViews,
+/// }
+///
+/// component_with_children!(Card);
+/// ```
+#[macro_export]
+macro_rules! component_with_children {
+    ($props:ty) => {
+        $crate::component_with_children!($props, children);
+    };
+    ($props:ty, $field:ident) => {
+        impl $crate::WithChildren for $props {
+            fn children(&self) -> &$crate::ChildViews {
+                &self.$field
+            }
+        }
+    };
+}
+
+impl<'p, 'w, Props: WithChildren> Cx<'p, 'w, Props> {
+    /// Returns the child views passed in the presenter's properties, so that they can be placed
+    /// in the presenter's output.
+    pub fn slot(&self) -> &ViewRef {
+        &self.props.children().0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        testing::TestWorld, Element, Presenter, ReactiveContext, TextComputed, ViewHandle,
+    };
+
+    use super::*;
+
+    #[derive(crate::Presenter)]
+    struct Card {
+        children: ChildViews,
+    }
+
+    crate::component_with_children!(Card);
+
+    impl Presenter for Card {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            Element::<NodeBundle>::new()
+                .named("Card")
+                .insert(BorderColor(Color::WHITE))
+                .children(cx.slot().clone())
+        }
+    }
+
+    fn child_text(world: &TestWorld, node: Entity, index: usize) -> String {
+        let children = world.world().get::<Children>(node).unwrap();
+        world.world().get::<Text>(children[index]).unwrap().sections[0]
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_card() {
+        let mut world = TestWorld::new();
+        let count = world.create_mutable(1);
+        let id = count.id;
+        let entity = world.spawn_view(Card {
+            children: ChildViews::new(TextComputed::new(move |re| {
+                format!("{}", re.read_mutable::<i32>(id))
+            })),
+        });
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 1);
+        assert!(world.world().get::<BorderColor>(nodes[0]).is_some());
+        assert_eq!(child_text(&world, nodes[0], 0), "1");
+
+        // The children react to their own dependencies.
+        world.set_mutable(&count, 2);
+        world.tick();
+        assert_eq!(child_text(&world, nodes[0], 0), "2");
+
+        // Razing the card razes the children.
+        let child = world.world().get::<Children>(nodes[0]).unwrap()[0];
+        let view = world
+            .world()
+            .get::<ViewHandle>(entity)
+            .unwrap()
+            .view
+            .clone();
+        view.lock().unwrap().raze(entity, world.world_mut());
+        assert!(world.world().get_entity(nodes[0]).is_none());
+        assert!(world.world().get_entity(child).is_none());
+    }
+
+    /// A card whose title is read from a mutable, so that the card itself re-renders.
+    #[derive(crate::Presenter)]
+    struct TitledCard {
+        title: Entity,
+        children: ChildViews,
+    }
+
+    crate::component_with_children!(TitledCard);
+
+    impl Presenter for TitledCard {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let title = cx.read_mutable_clone::<String>(cx.props.title);
+            Element::<NodeBundle>::new().children((title, cx.slot().clone()))
+        }
+    }
+
+    #[test]
+    fn test_card_rerender() {
+        let mut world = TestWorld::new();
+        let title = world.create_mutable("First".to_string());
+        let count = world.create_mutable(1);
+        let id = count.id;
+        let entity = world.spawn_view(TitledCard {
+            title: title.id,
+            children: ChildViews::new(TextComputed::new(move |re| {
+                format!("{}", re.read_mutable::<i32>(id))
+            })),
+        });
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(child_text(&world, nodes[0], 0), "First");
+        assert_eq!(child_text(&world, nodes[0], 1), "1");
+
+        // Re-rendering the card razes the children, then builds them again.
+        world.set_mutable(&title, "Second".to_string());
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(child_text(&world, nodes[0], 0), "Second");
+        assert_eq!(child_text(&world, nodes[0], 1), "1");
+        assert_eq!(world.world().get::<Children>(nodes[0]).unwrap().len(), 2);
+
+        // The rebuilt children still react to their own dependencies.
+        world.set_mutable(&count, 2);
+        world.tick();
+        assert_eq!(child_text(&world, nodes[0], 1), "2");
+    }
+
+    #[test]
+    fn test_card_tuple() {
+        let mut world = TestWorld::new();
+        let entity = world.spawn_view(Card {
+            children: ChildViews::new(("Hello", "World")),
+        });
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(child_text(&world, nodes[0], 0), "Hello");
+        assert_eq!(child_text(&world, nodes[0], 1), "World");
+    }
+}
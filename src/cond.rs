@@ -5,7 +5,8 @@ use bevy::prelude::*;
 
 use crate::node_span::NodeSpan;
 use crate::{
-    DespawnScopes, DisplayNodeChanged, IntoView, Rcx, TrackingScope, View, ViewHandle, ViewRef,
+    view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx, TrackingScope, View,
+    ViewHandle, ViewRef,
 };
 
 // Cond
@@ -64,11 +65,11 @@ impl<
         NegFn: Fn() -> Neg,
     > View for Cond<Test, Pos, PosFn, Neg, NegFn>
 {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, world: &World) -> NodeSpan {
         match self.state {
             CondState::Unset => NodeSpan::Empty,
-            CondState::True(ref true_state) => true_state.0.lock().unwrap().nodes(),
-            CondState::False(ref false_state) => false_state.0.lock().unwrap().nodes(),
+            CondState::True((ref view, entity)) => child_nodes(view, entity, world),
+            CondState::False((ref view, entity)) => child_nodes(view, entity, world),
         }
     }
 
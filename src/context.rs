@@ -0,0 +1,108 @@
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
+
+use bevy::{prelude::*, utils::HashMap};
+
+/// Identifies a context value: the type of the value, plus the marker type of the context.
+pub(crate) type ContextKey = (TypeId, TypeId);
+
+/// Component which holds the context values provided by a presenter, keyed by [`Context`].
+#[derive(Component, Default)]
+pub(crate) struct ScopedValueMap(pub(crate) HashMap<ContextKey, Box<dyn Any + Send + Sync>>);
+
+/// A typed key for passing values implicitly from a presenter to its descendants. Values are
+/// provided with [`Cx::provide_context`](crate::Cx::provide_context) and retrieved with
+/// [`Cx::consume_context`](crate::Cx::consume_context).
+///
+/// Each context is identified by a marker type, and contexts are typically declared as
+/// constants:
+///
+/// ```ignore
+/// struct ThemeName;
+///
+/// const THEME: Context<String> = Context::new::<ThemeName>();
+/// ```
+///
+/// Two contexts with the same value type are distinguished by their marker types, so the value
+/// of one can never be mistaken for the value of the other, even if the marker types have the
+/// same name in different modules.
+pub struct Context<T: Clone + Send + Sync + 'static> {
+    id: fn() -> TypeId,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Context<T> {
+    /// Construct a new context key, identified by the marker type `M`.
+    pub const fn new<M: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<M>,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn key(&self) -> ContextKey {
+        (TypeId::of::<T>(), (self.id)())
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for Context<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Copy for Context<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, TrackingScope};
+
+    use super::*;
+
+    struct ThemeName;
+    struct FontName;
+
+    const THEME: Context<String> = Context::new::<ThemeName>();
+    const FONT: Context<String> = Context::new::<FontName>();
+
+    mod other {
+        /// A marker with the same name as the one above, in a different module.
+        pub struct ThemeName;
+    }
+
+    const OTHER_THEME: Context<String> = Context::new::<other::ThemeName>();
+
+    #[test]
+    fn test_context() {
+        let mut world = World::default();
+        let outer = world.spawn_empty().id();
+        let inner = world.spawn_empty().set_parent(outer).id();
+        let leaf = world.spawn_empty().set_parent(inner).id();
+
+        // No provider
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, leaf, &mut scope);
+        assert_eq!(cx.consume_context(THEME), None);
+
+        let mut provider = TrackingScope::new(world.change_tick());
+        Cx::new(&(), &mut world, outer, &mut provider).provide_context(THEME, "dark".to_string());
+        let cx = Cx::new(&(), &mut world, leaf, &mut scope);
+        assert_eq!(cx.consume_context(THEME), Some("dark".to_string()));
+        assert_eq!(cx.consume_context(FONT), None);
+        assert_eq!(cx.consume_context(OTHER_THEME), None);
+
+        // Inner provider shadows the outer one.
+        Cx::new(&(), &mut world, inner, &mut provider).provide_context(THEME, "light".to_string());
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, leaf, &mut scope);
+        assert_eq!(cx.consume_context(THEME), Some("light".to_string()));
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Consumer reacts when the provided value changes.
+        Cx::new(&(), &mut world, inner, &mut provider).provide_context(THEME, "blue".to_string());
+        assert!(scope.dependencies_changed(&world));
+    }
+}
@@ -1,16 +1,49 @@
 use std::{
     any::TypeId,
     cell::{RefCell, RefMut},
+    hash::Hash,
     marker::PhantomData,
-    sync::atomic::AtomicBool,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{Arc, RwLock},
+    time::Duration,
 };
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{AssetPath, LoadState},
+    core::FrameCount,
+    ecs::{
+        component::ComponentId,
+        event::{Event, Events},
+        query::{FilteredAccess, ROQueryItem, ReadOnlyWorldQuery, WorldQuery},
+        system::SystemId,
+        world::WorldId,
+    },
+    prelude::*,
+};
 
 use crate::{
-    mutable::{MutableValue, MutableValueNext},
-    scope::TrackingScope,
-    Mutable,
+    bundle::{BundleProducer, ComponentComputedRef},
+    callback::CallbackHandle,
+    context::{Context, ScopedValueMap},
+    event_reader::ReactiveEventReader,
+    event_writer::DeferredEvents,
+    hooks::{ComputedSlot, HookSlots, LoadedAssets, LocalResource, ReducerSlot, StableMutableSlot},
+    lifecycle::{LifecycleHook, MountCallbacks, Mounted, UnmountCallbacks},
+    localization::{format_localized, LocalizationResource},
+    mutable::{
+        after_write, check_writable, commit_pending, MutableBatch, MutableDebounce,
+        MutableValidator, MutableValue, MutableValueNext, ReadOnlyMutable,
+    },
+    mutable_map::{MutableMap, MutableMapValue, TrackedMapKey},
+    reducer::{apply_reducer, Dispatch, ReducerState},
+    scope::{TrackingScope, WrittenBy},
+    selector::{Selector, SelectorReaction},
+    store::{store_entity, StoreHandle},
+    suspense::{find_boundary, set_resumed, SuspendHandle},
+    theme::{Theme, ThemeTokens},
+    transition::{Transition, TransitionValue},
+    trigger::{fire_trigger, Trigger},
+    Mutable, MutableArc, ReactionHandle, ViewHandle, WorldRegistry,
 };
 
 /// An immutable reactive context, used for reactive closures such as derived signals.
@@ -19,7 +52,7 @@ pub trait ReactiveContext<'p> {
     fn world(&self) -> &World;
 
     /// Set of reactive resources referenced by the presenter.
-    fn tracking(&self) -> RefMut<&'p mut TrackingScope>;
+    fn tracking(&self) -> RefMut<'_, &'p mut TrackingScope>;
 
     /// Read the value of a mutable variable using Copy semantics. Calling this function adds the
     /// mutable to the current tracking scope.
@@ -54,6 +87,61 @@ pub trait ReactiveContext<'p> {
             .clone()
     }
 
+    /// Read the value of a [`MutableArc`]. The returned `Arc` remains valid after the mutable
+    /// is written. Calling this function adds the mutable to the current tracking scope.
+    fn read_mutable_arc<T>(&self, mutable: &MutableArc<T>) -> Arc<RwLock<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.read_mutable_clone::<Arc<RwLock<T>>>(mutable.id)
+    }
+
+    /// Read the value of the given key in a reactive map using Clone semantics. Calling this
+    /// function subscribes to changes to that key only.
+    fn map_get<K, V>(&self, map: &MutableMap<K, V>, key: &K) -> Option<V>
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        self.tracking().add_map_key(TrackedMapKey::<K, V> {
+            map: map.id,
+            key: key.clone(),
+            marker: PhantomData,
+        });
+        self.world()
+            .get::<MutableMapValue>(map.id)
+            .unwrap()
+            .inner::<K, V>()
+            .get(key)
+    }
+
+    /// Returns true if a reactive map contains the given key. Calling this function
+    /// subscribes to changes to that key only.
+    fn map_contains_key<K, V>(&self, map: &MutableMap<K, V>, key: &K) -> bool
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        self.tracking().add_map_key(TrackedMapKey::<K, V> {
+            map: map.id,
+            key: key.clone(),
+            marker: PhantomData,
+        });
+        self.world()
+            .get::<MutableMapValue>(map.id)
+            .unwrap()
+            .inner::<K, V>()
+            .contains_key(key)
+    }
+
+    /// Poll a [`Trigger`]. Calling this function adds the trigger to the current tracking
+    /// scope, so that the scope reacts whenever the trigger is fired.
+    fn poll_trigger(&self, trigger: &Trigger) {
+        if self.world().get::<MutableValue>(trigger.id).is_some() {
+            self.tracking().add_mutable(trigger.id);
+        }
+    }
+
     /// Return a reference to the resource of the given type. Calling this function
     /// adds the resource as a dependency of the current presenter invocation.
     fn use_resource<T: Resource>(&self) -> &T {
@@ -65,6 +153,208 @@ pub trait ReactiveContext<'p> {
         );
         self.world().resource::<T>()
     }
+
+    /// Return a reference to the non-send resource of the given type, such as a window or
+    /// audio device handle. Calling this function adds the resource as a dependency of the
+    /// current presenter invocation.
+    ///
+    /// # Panics
+    ///
+    /// Non-send resources can only be accessed from the main thread, so this panics if the
+    /// reaction is not running on the main thread, or if the resource does not exist.
+    fn use_non_send_resource<T: 'static>(&self) -> &T {
+        self.tracking().add_non_send_resource(
+            self.world()
+                .components()
+                .get_resource_id(TypeId::of::<T>())
+                .expect("Unknown resource type"),
+        );
+        self.world().non_send_resource::<T>()
+    }
+
+    /// Return the keyboard [`Input`] resource, or `None` if it is missing, as it is when the
+    /// app uses `MinimalPlugins`. If the resource type has been registered, it is added as a
+    /// dependency of the current presenter invocation either way, so the presenter reacts
+    /// when the resource is inserted.
+    fn use_keyboard_input(&self) -> Option<&Input<KeyCode>> {
+        if let Some(resource_id) = self
+            .world()
+            .components()
+            .get_resource_id(TypeId::of::<Input<KeyCode>>())
+        {
+            self.tracking().add_resource::<Input<KeyCode>>(resource_id);
+        }
+        self.world().get_resource::<Input<KeyCode>>()
+    }
+
+    /// Returns true if the given key is currently held down, or false if there is no keyboard
+    /// input. Calling this function adds the keyboard [`Input`] resource as a dependency of the
+    /// current presenter invocation.
+    ///
+    /// Note that the input resource is modified every frame, so the caller will react on
+    /// every frame, not only when the key changes state. For expensive presenters, consider
+    /// reading the input from an ordinary system and writing the result to a mutable instead.
+    fn use_input(&self, key: KeyCode) -> bool {
+        self.use_keyboard_input()
+            .is_some_and(|input| input.pressed(key))
+    }
+
+    /// Returns true if the given key was pressed during the current frame. See
+    /// [`use_input`](ReactiveContext::use_input).
+    fn use_input_just_pressed(&self, key: KeyCode) -> bool {
+        self.use_keyboard_input()
+            .is_some_and(|input| input.just_pressed(key))
+    }
+
+    /// Returns true if the given key was released during the current frame. See
+    /// [`use_input`](ReactiveContext::use_input).
+    fn use_input_just_released(&self, key: KeyCode) -> bool {
+        self.use_keyboard_input()
+            .is_some_and(|input| input.just_released(key))
+    }
+
+    /// Return the secondary world with the given id, which must have been added to the
+    /// [`WorldRegistry`]. Access to the secondary world is not tracked; use
+    /// [`use_secondary_resource`](ReactiveContext::use_secondary_resource) to react to
+    /// changes in it.
+    fn secondary_world(&self, world_id: WorldId) -> &World {
+        self.world()
+            .resource::<WorldRegistry>()
+            .get(world_id)
+            .expect("Unknown secondary world")
+    }
+
+    /// Return a reference to a resource in the secondary world with the given id. Calling
+    /// this function adds the resource as a dependency of the current presenter invocation,
+    /// so it will react when the resource changes in the secondary world.
+    fn use_secondary_resource<T: Resource>(&self, world_id: WorldId) -> &T {
+        let secondary = self.secondary_world(world_id);
+        self.tracking().add_secondary_resource(
+            secondary,
+            secondary
+                .components()
+                .get_resource_id(TypeId::of::<T>())
+                .expect("Unknown resource type"),
+        );
+        secondary.resource::<T>()
+    }
+
+    /// Return the current frame count. This adds the [`FrameCount`] resource as a dependency,
+    /// so the current scope will react every frame.
+    ///
+    /// [`FrameCount`]: bevy::core::FrameCount
+    fn use_frame_count(&self) -> u64 {
+        self.use_resource::<FrameCount>().0 as u64
+    }
+
+    /// Returns true if the current frame count is a multiple of `n`. The current scope will
+    /// react once every `n` frames, rather than every frame.
+    fn use_every_n_frames(&self, n: u64) -> bool {
+        let frame = self
+            .world()
+            .get_resource::<FrameCount>()
+            .map(|count| count.0 as u64)
+            .unwrap_or(0);
+        self.tracking().add_frame_interval(n, frame);
+        frame % n.max(1) == 0
+    }
+
+    /// Return a reference to the resource of the given type, like
+    /// [`use_resource`](ReactiveContext::use_resource), but limit the rate of reactions to at
+    /// most one per `min_interval`. Note that the throttle applies to all of the dependencies
+    /// of the current tracking scope.
+    fn use_throttled_resource<T: Resource>(&self, min_interval: Duration) -> &T {
+        self.tracking().set_throttle(min_interval);
+        self.use_resource::<T>()
+    }
+
+    /// Return the current value of the Bevy state `S`. The [`State<S>`] resource is added as
+    /// a dependency, so the current scope will react when the state changes.
+    fn use_bevy_state<S: States>(&self) -> &S {
+        self.use_resource::<State<S>>().get()
+    }
+
+    /// Return a reference to the [`Time`] resource. This does not add `Time` as a dependency,
+    /// so it does not cause the current scope to react every frame; use
+    /// [`use_resource::<Time>`](ReactiveContext::use_resource) for that.
+    fn use_time_untracked(&self) -> &Time {
+        self.world().resource::<Time>()
+    }
+
+    /// Return the time elapsed since startup, in seconds. Like
+    /// [`use_time_untracked`](ReactiveContext::use_time_untracked), this does not add any
+    /// dependencies.
+    fn use_elapsed_secs_untracked(&self) -> f32 {
+        self.use_time_untracked().elapsed_seconds()
+    }
+
+    /// Return the time elapsed since the previous frame, in seconds. Like
+    /// [`use_time_untracked`](ReactiveContext::use_time_untracked), this does not add any
+    /// dependencies.
+    fn use_delta_secs_untracked(&self) -> f32 {
+        self.use_time_untracked().delta_seconds()
+    }
+
+    /// Return a reference to the Component `C` on the given entity. Calling this function adds
+    /// the component as a dependency of the current presenter invocation, so it will react
+    /// when the component is added, removed or modified.
+    fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
+        let world = self.world();
+        let entt = world.get_entity(entity)?;
+        // If the component type has never been registered, no entity can have it.
+        let component = world.components().component_id::<C>()?;
+        let present = entt.contains_id(component);
+        let mut tracking = self.tracking();
+        tracking.add_component_presence(entity, component, present);
+        if present {
+            tracking.add_component(entity, component);
+        }
+        entt.get::<C>()
+    }
+
+    /// Return a reference to the Component `C` on the given entity. This version does not
+    /// add the component to the tracking scope, and is intended for components that update
+    /// frequently.
+    fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.world().get_entity(entity)?.get::<C>()
+    }
+
+    /// Return a reference to the asset with the given handle, or `None` if the asset has not
+    /// finished loading. Calling this function adds the asset collection as a dependency of the
+    /// current presenter invocation, so it will react when the asset is loaded or modified.
+    fn use_asset<A: Asset>(&self, handle: &Handle<A>) -> Option<&A> {
+        self.use_resource::<Assets<A>>().get(handle)
+    }
+
+    /// Return the load state of the asset with the given handle. Like
+    /// [`use_asset`](ReactiveContext::use_asset), this adds the asset collection as a
+    /// dependency, so the presenter will react when the asset finishes loading. The load state
+    /// itself is also a dependency, so the presenter reacts when the load fails.
+    fn use_asset_load_state<A: Asset>(&self, handle: &Handle<A>) -> LoadState {
+        self.use_resource::<Assets<A>>();
+        let state = self
+            .world()
+            .resource::<AssetServer>()
+            .get_load_state(handle.id())
+            .unwrap_or(LoadState::NotLoaded);
+        self.tracking().add_load_state(handle.id().untyped(), state);
+        state
+    }
+
+    /// Return the translation of `key` in the current locale, or `key` itself if there is no
+    /// translation. Calling this function adds the [`LocalizationResource`] as a dependency, so
+    /// the current scope will react when the locale changes.
+    fn use_localized<'a>(&'a self, key: &'a str) -> &'a str {
+        self.use_resource::<LocalizationResource>()
+            .translate(key)
+            .unwrap_or(key)
+    }
+
+    /// Like [`use_localized`](ReactiveContext::use_localized), but also replaces each `{name}`
+    /// placeholder in the translation with the corresponding argument.
+    fn use_localized_format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        format_localized(self.use_localized(key), args)
+    }
 }
 
 /// A mutable reactive context. This allows access to reactive data sources, and can also
@@ -80,10 +370,9 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
     {
         let mutable = self
             .world_mut()
-            .spawn((MutableValue {
-                changed: AtomicBool::new(false),
+            .spawn(MutableValue {
                 value: Box::new(init),
-            },))
+            })
             .id();
         self.tracking().add_owned(mutable);
         Mutable {
@@ -92,12 +381,310 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
         }
     }
 
+    /// Create a new [`MutableArc`] in this context. Unlike [`Mutable`], the value type need
+    /// not be `Copy` or `Clone`.
+    fn create_mutable_arc<T>(&mut self, init: T) -> MutableArc<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let mutable = self.create_mutable(Arc::new(RwLock::new(init)));
+        MutableArc {
+            id: mutable.id,
+            marker: PhantomData,
+        }
+    }
+
+    /// Create a new [`Trigger`] in this context, which can be fired to make the tracking
+    /// scopes which poll it react.
+    fn create_trigger(&mut self) -> Trigger {
+        Trigger {
+            id: self.create_mutable(0u64).id,
+        }
+    }
+
+    /// Fire a [`Trigger`], causing the tracking scopes which polled it to react on the next
+    /// update.
+    fn fire(&mut self, trigger: &mut Trigger) {
+        fire_trigger(self.world_mut(), trigger.id);
+    }
+
+    /// Create a new [`Mutable`] in this context, whose writes are not committed until no
+    /// further writes have happened for the `debounce` interval. Use [`Mutable::flush`] to
+    /// commit a pending write immediately.
+    fn create_mutable_debounced<T>(&mut self, init: T, debounce: Duration) -> Mutable<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let mutable = self.create_mutable(init);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert(MutableDebounce {
+                interval: debounce,
+                last_write: Duration::ZERO,
+            });
+        mutable
+    }
+
+    /// Create a [`Mutable`] which mirrors the resource `T`, as with [`Mutable::from_resource`].
+    /// The mutable is owned by this context, so mirroring stops when it is razed.
+    fn create_mutable_from_resource<T>(&mut self) -> Mutable<T>
+    where
+        T: Resource + Clone + PartialEq,
+    {
+        let mutable = Mutable::<T>::from_resource(self.world_mut());
+        self.tracking().add_owned(mutable.id);
+        mutable
+    }
+
+    /// Return a handle to the global store of type `S`, creating it with the default value
+    /// if it does not exist. All presenters which call `use_store::<S>()` share the same
+    /// store, which is not despawned when they are razed.
+    fn use_store<S>(&mut self) -> StoreHandle<S>
+    where
+        S: Default + Send + Sync + 'static,
+    {
+        StoreHandle {
+            id: store_entity::<S>(self.world_mut()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Create a new [`Mutable`] whose values are checked by `validator` each time a new value
+    /// is committed. Returns the mutable, plus a read-only mutable which indicates whether the
+    /// current value is valid. Invalid values are still stored.
+    fn create_mutable_validated<T>(
+        &mut self,
+        init: T,
+        validator: fn(&T) -> bool,
+    ) -> (Mutable<T>, Mutable<bool>)
+    where
+        T: Send + Sync + Clone + PartialEq + 'static,
+    {
+        let validity = self.create_mutable(validator(&init));
+        self.world_mut()
+            .entity_mut(validity.id)
+            .insert(ReadOnlyMutable);
+        let mutable = self.create_mutable(init);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert(MutableValidator {
+                validator: Box::new(move |value| {
+                    value.downcast_ref::<T>().map(validator).unwrap_or(false)
+                }),
+                validity: validity.id,
+            });
+        (mutable, validity)
+    }
+
+    /// Create a new, empty [`MutableMap`] in this context.
+    fn create_mutable_map<K, V>(&mut self) -> MutableMap<K, V>
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        let map = self.world_mut().spawn(MutableMapValue::new::<K, V>()).id();
+        self.tracking().add_owned(map);
+        MutableMap {
+            id: map,
+            marker: PhantomData,
+        }
+    }
+
+    /// Insert a value into a reactive map. The write is visible on the next update.
+    fn map_insert<K, V>(&mut self, map: &mut MutableMap<K, V>, key: K, value: V)
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.world_mut().get_mut::<MutableMapValue>(map.id) {
+            inner.inner_mut::<K, V>().write(key, Some(value));
+        }
+    }
+
+    /// Remove a key from a reactive map. The write is visible on the next update.
+    fn map_remove<K, V>(&mut self, map: &mut MutableMap<K, V>, key: K)
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        if let Some(mut inner) = self.world_mut().get_mut::<MutableMapValue>(map.id) {
+            inner.inner_mut::<K, V>().write(key, None);
+        }
+    }
+
+    /// Create a [`Selector`], a memoized value which is computed from other reactive data
+    /// sources. The selector is recomputed when its dependencies change, but readers of the
+    /// selector only react if the computed value is different from the previous one.
+    fn create_selector<T, F>(&mut self, func: F) -> Selector<T>
+    where
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&Rcx) -> T + Send + Sync + 'static,
+    {
+        let world = self.world_mut();
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let value = func(&Rcx::new(world, &mut tracking));
+        let selector = world
+            .spawn((
+                MutableValue {
+                    value: Box::new(value),
+                },
+                ReactionHandle::new(SelectorReaction {
+                    func,
+                    marker: PhantomData,
+                }),
+                tracking,
+            ))
+            .id();
+        // The selector writes its own value, so it must run before any stale readers.
+        world.entity_mut(selector).insert(WrittenBy(selector));
+        self.tracking().add_owned(selector);
+        Selector {
+            id: selector,
+            marker: PhantomData,
+        }
+    }
+
+    /// Return a mutable reference to the Component `C` on the given entity. Like
+    /// [`use_component`](ReactiveContext::use_component), the component is added as a
+    /// dependency, so the presenter will react when it is modified by someone else. Writes
+    /// made through the returned reference do not cause the presenter itself to react.
+    fn use_component_mut<C: Component>(&mut self, entity: Entity) -> Option<Mut<'_, C>> {
+        let component = self.world_mut().init_component::<C>();
+        let present = self.world().get_entity(entity)?.contains_id(component);
+        {
+            let mut tracking = self.tracking();
+            tracking.add_component_presence(entity, component, present);
+            if present {
+                tracking.add_component(entity, component);
+            }
+        }
+        self.world_mut().get_mut::<C>(entity)
+    }
+
+    /// Return a mutable reference to the resource of the given type. Unlike
+    /// [`use_resource`](ReactiveContext::use_resource), this does not add the resource as a
+    /// dependency, since writing to a resource should not cause the writer to react.
+    fn use_resource_mut<T: Resource>(&mut self) -> Mut<'_, T> {
+        self.world_mut().resource_mut::<T>()
+    }
+
+    /// Schedule a transition of the Bevy state `S` to `next`, via the [`NextState<S>`]
+    /// resource. The transition is applied by Bevy's state systems.
+    fn set_bevy_state<S: States>(&mut self, next: S) {
+        self.world_mut().resource_mut::<NextState<S>>().set(next);
+    }
+
+    /// Replace the value of the resource of the given type. Like
+    /// [`use_resource_mut`](ReactiveContextMut::use_resource_mut), this does not add the
+    /// resource as a dependency.
+    fn write_resource<T: Resource>(&mut self, value: T) {
+        self.world_mut().insert_resource(value);
+    }
+
+    /// Send an event of type `E`. The event is deferred until the reactions for the current
+    /// frame have finished, and is then sent by the
+    /// [`dispatch_deferred_events`](crate::dispatch_deferred_events) system.
+    fn send_event<E: Event>(&mut self, event: E) {
+        self.world_mut()
+            .get_resource_or_insert_with(DeferredEvents::default)
+            .push(event);
+    }
+
+    /// Run a query against the world and return the matching items. The set of matching
+    /// entities, as well as the components read by the query, are added as dependencies of
+    /// the current tracking scope.
+    fn use_query<Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, Q>> {
+        // Queries must be constructed up front, since they require exclusive world access.
+        let world = self.world_mut();
+        // The query itself acts as part of the filter, so that entities which lose one of
+        // the queried components stop matching.
+        let entities_query = world.query_filtered::<Entity, (Q, F)>();
+        let query = world.query_filtered::<(Entity, Q), F>();
+        let mut access = FilteredAccess::<ComponentId>::default();
+        let state = <Q as WorldQuery>::init_state(world);
+        <Q as WorldQuery>::update_component_access(&state, &mut access);
+
+        let world = self.world();
+        let mut tracking = self.tracking();
+        let mut entities = Vec::new();
+        let mut items = Vec::new();
+        for (entity, item) in query.iter_manual(world) {
+            for component in world.entity(entity).archetype().components() {
+                if access.access().has_read(component) {
+                    tracking.add_component(entity, component);
+                }
+            }
+            entities.push(entity);
+            items.push(item);
+        }
+        tracking.add_query(entities_query, entities);
+        items
+    }
+
+    /// Run a query against the world, and return the matching items. Unlike
+    /// [`use_query`](ReactiveContextMut::use_query), which subscribes to each matching entity
+    /// separately, the whole query is added to the tracking scope as a single dependency. The
+    /// scope reacts when an entity starts or stops matching the query, or when a component
+    /// read by the query changes on any matching entity. This is suited to aggregating over a
+    /// large number of entities.
+    fn use_world_query<Q: ReadOnlyWorldQuery + 'static>(&mut self) -> Vec<ROQueryItem<'_, Q>> {
+        let world = self.world_mut();
+        let entities_query = world.query_filtered::<Entity, Q>();
+        let query = world.query::<Q>();
+        let mut access = FilteredAccess::<ComponentId>::default();
+        let state = <Q as WorldQuery>::init_state(world);
+        <Q as WorldQuery>::update_component_access(&state, &mut access);
+        let components = access.access().reads().collect();
+
+        let world = self.world();
+        let entities = entities_query.iter_manual(world).collect();
+        self.tracking()
+            .add_world_query(entities_query, entities, components);
+        query.iter_manual(world).collect()
+    }
+
+    /// Run a query against the world and return the matching items. Unlike
+    /// [`use_query`](ReactiveContextMut::use_query), this does not add any dependencies.
+    fn use_query_untracked<Q: ReadOnlyWorldQuery + 'static, F: ReadOnlyWorldQuery + 'static>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, Q>> {
+        let query = self.world_mut().query_filtered::<Q, F>();
+        query.iter_manual(self.world()).collect()
+    }
+
+    /// Run `f`, and then commit all of the mutable writes made within it at once. This
+    /// ensures that readers observe all of the new values together. Batches may be nested, in
+    /// which case the writes are committed at the end of the outermost batch.
+    fn batch<R, F: FnOnce(&mut Self) -> R>(&mut self, f: F) -> R
+    where
+        Self: Sized,
+    {
+        self.world_mut()
+            .get_resource_or_insert_with(MutableBatch::default)
+            .depth += 1;
+        // The batch is closed even if `f` panics, so that later writes aren't held back.
+        let result = catch_unwind(AssertUnwindSafe(|| f(self)));
+        let world = self.world_mut();
+        let mut batch = world.resource_mut::<MutableBatch>();
+        batch.depth -= 1;
+        if batch.depth == 0 {
+            let written = std::mem::take(&mut batch.written);
+            if result.is_ok() {
+                commit_pending(world, Some(&written), false);
+            }
+        }
+        result.unwrap_or_else(|payload| resume_unwind(payload))
+    }
+
     /// Write the value of a mutable variable using Copy semantics. Does nothing if
     /// the value being set matches the existing value.
     fn write_mutable<T>(&mut self, mutable: Entity, value: T)
     where
         T: Send + Sync + Copy + PartialEq + 'static,
     {
+        check_writable(self.world(), mutable);
         let mut mutable_entity = self.world_mut().entity_mut(mutable);
         if let Some(mut next) = mutable_entity.get_mut::<MutableValueNext>() {
             *next.0.downcast_mut::<T>().unwrap() = value;
@@ -106,6 +693,7 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
                 mutable_entity.insert(MutableValueNext(Box::new(value)));
             }
         }
+        after_write(self.world_mut(), mutable);
     }
 
     /// Write the value of a mutable variable using Clone semantics. Does nothing if the
@@ -114,6 +702,7 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
     where
         T: Send + Sync + Clone + PartialEq + 'static,
     {
+        check_writable(self.world(), mutable);
         let mut mutable_entity = self.world_mut().entity_mut(mutable);
         if let Some(mut next) = mutable_entity.get_mut::<MutableValueNext>() {
             *next.0.downcast_mut::<T>().unwrap() = value;
@@ -122,6 +711,40 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
                 mutable_entity.insert(MutableValueNext(Box::new(value.clone())));
             }
         }
+        after_write(self.world_mut(), mutable);
+    }
+
+    /// Write a new value to a [`MutableArc`]. The value is placed in a new `Arc`, so the
+    /// readers of the mutable always react, even if the new value is equal to the old one.
+    fn write_mutable_arc<T>(&mut self, mutable: &MutableArc<T>, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        check_writable(self.world(), mutable.id);
+        let value: Arc<RwLock<T>> = Arc::new(RwLock::new(value));
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert(MutableValueNext(Box::new(value)));
+        after_write(self.world_mut(), mutable.id);
+    }
+
+    /// Like [`write_mutable_arc`](ReactiveContextMut::write_mutable_arc), but does nothing if
+    /// the new value is equal to the current value.
+    fn write_mutable_arc_eq<T>(&mut self, mutable: &MutableArc<T>, value: T)
+    where
+        T: Send + Sync + PartialEq + 'static,
+    {
+        let entt = self.world().entity(mutable.id);
+        let current = match entt.get::<MutableValueNext>() {
+            Some(next) => next.0.downcast_ref::<Arc<RwLock<T>>>(),
+            None => entt
+                .get::<MutableValue>()
+                .and_then(|current| current.value.downcast_ref::<Arc<RwLock<T>>>()),
+        };
+        if current.is_some_and(|current| *current.read().unwrap() == value) {
+            return;
+        }
+        self.write_mutable_arc(mutable, value);
     }
 
     /// Write the value of a mutable variable by modifying in place. Note that unlike the
@@ -131,12 +754,14 @@ pub trait ReactiveContextMut<'p>: ReactiveContext<'p> {
     where
         T: Send + Sync + Clone + PartialEq + 'static,
     {
+        check_writable(self.world(), mutable);
         let mut mutable_entity = self.world_mut().entity_mut(mutable);
         if let Some(mut next) = mutable_entity.get_mut::<MutableValueNext>() {
             mutator(next.0.downcast_mut::<T>().unwrap());
         } else if let Some(mut current_value) = mutable_entity.get_mut::<MutableValue>() {
             mutator(current_value.value.downcast_mut::<T>().unwrap());
         }
+        after_write(self.world_mut(), mutable);
     }
 }
 
@@ -150,6 +775,12 @@ pub struct Cx<'p, 'w, Props = ()> {
     /// Bevy World
     pub(crate) world: &'w mut World,
 
+    /// The view entity which holds the presenter's tracking scope.
+    pub(crate) entity: Entity,
+
+    /// Index of the next hook slot, incremented by each call to a hook.
+    pub(crate) hook_index: usize,
+
     /// Set of reactive resources referenced by the presenter.
     pub(crate) tracking: RefCell<&'p mut TrackingScope>,
 }
@@ -158,126 +789,682 @@ impl<'p, 'w, Props> Cx<'p, 'w, Props> {
     pub(crate) fn new(
         props: &'p Props,
         world: &'w mut World,
+        entity: Entity,
         tracking: &'p mut TrackingScope,
     ) -> Self {
         Self {
             props,
             world,
+            entity,
+            hook_index: 0,
             tracking: RefCell::new(tracking),
         }
     }
 
-    // /// Return a reference to the Component `C` on the given entity.
-    // pub fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
-    //     match self.bc.world.get_entity(entity) {
-    //         Some(c) => {
-    //             self.add_tracked_component::<C>(entity);
-    //             c.get::<C>()
-    //         }
-    //         None => None,
-    //     }
-    // }
+    /// Returns the view entity of the presenter. This is the entity which holds the
+    /// presenter's tracking scope, and it is despawned when the presenter is razed.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
 
-    // /// Return a reference to the Component `C` on the given entity. This version does not
-    // /// add the component to the tracking scope, and is intended for components that update
-    // /// frequently.
-    // pub fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
-    //     match self.bc.world.get_entity(entity) {
-    //         Some(c) => c.get::<C>(),
-    //         None => None,
-    //     }
-    // }
+    /// Returns the parent of the view entity, or `None` if it has no parent. Calling this
+    /// function adds the [`Parent`] component of the view entity as a dependency, so the
+    /// presenter will react when the view entity is re-parented.
+    pub fn use_parent(&mut self) -> Option<Entity> {
+        self.use_parent_of(self.entity)
+    }
 
-    // /// Return a reference to the Component `C` on the entity that contains the current
-    // /// presenter invocation.
-    // pub fn use_view_component<C: Component>(&self) -> Option<&C> {
-    //     self.add_tracked_component::<C>(self.bc.entity);
-    //     self.bc.world.entity(self.bc.entity).get::<C>()
-    // }
+    /// Returns the ancestors of the view entity, starting with its parent and ending with
+    /// the root. Like [`use_parent`](Cx::use_parent), the presenter will react when any
+    /// entity in the chain is re-parented.
+    pub fn use_ancestors(&mut self) -> Vec<Entity> {
+        let mut ancestors = Vec::new();
+        let mut entity = self.entity;
+        while let Some(parent) = self.use_parent_of(entity) {
+            ancestors.push(parent);
+            entity = parent;
+        }
+        ancestors
+    }
 
-    // /// Return a reference to the entity that holds the current presenter invocation.
-    // pub fn use_view_entity(&self) -> EntityRef<'_> {
-    //     self.bc.world.entity(self.bc.entity)
-    // }
+    /// Returns the topmost ancestor of the view entity, or the view entity itself if it has
+    /// no parent. The presenter will react when any entity in the chain is re-parented.
+    pub fn use_root(&mut self) -> Entity {
+        self.use_ancestors().pop().unwrap_or(self.entity)
+    }
 
-    // /// Return a mutable reference to the entity that holds the current presenter invocation.
-    // pub fn use_view_entity_mut(&mut self) -> EntityWorldMut<'_> {
-    //     self.bc.world.entity_mut(self.bc.entity)
-    // }
+    /// Returns the parent of `entity`, adding its [`Parent`] component to the tracking scope.
+    fn use_parent_of(&mut self, entity: Entity) -> Option<Entity> {
+        // Register the component so that the dependency is tracked even if no entity has
+        // been parented yet.
+        self.world.init_component::<Parent>();
+        self.use_component::<Parent>(entity)
+            .map(|parent| parent.get())
+    }
 
-    // /// Spawn an empty [`Entity`] which is owned by this presenter. The entity will be
-    // /// despawned when the presenter state is razed.
-    // pub fn create_entity(&mut self) -> Entity {
-    //     let mut tracking = self.tracking.borrow_mut();
-    //     let index = tracking.next_entity_index;
-    //     tracking.next_entity_index = index + 1;
-    //     match index.cmp(&tracking.owned_entities.len()) {
-    //         Ordering::Less => tracking.owned_entities[index],
-    //         Ordering::Equal => {
-    //             let id = self.bc.world.spawn_empty().id();
-    //             tracking.owned_entities.push(id);
-    //             id
-    //         }
-    //         Ordering::Greater => panic!("Invalid presenter entity index"),
-    //     }
-    // }
+    /// Return a reference to the asset with the given handle, or to a default value of the
+    /// asset type if the asset has not finished loading. Like
+    /// [`use_asset`](ReactiveContext::use_asset), this adds the asset collection as a
+    /// dependency, so the presenter will react when the asset is loaded. The default value is
+    /// kept in a hook slot while the asset is loading, and dropped once it has loaded. If the
+    /// asset type has not been registered with the app, the default value is returned.
+    pub fn use_asset_or_default<A: Asset + Default>(&mut self, handle: &Handle<A>) -> &A {
+        let index = self.next_hook_index();
+        if let Some(resource_id) = self
+            .world
+            .components()
+            .get_resource_id(TypeId::of::<Assets<A>>())
+        {
+            self.tracking
+                .borrow_mut()
+                .add_resource::<Assets<A>>(resource_id);
+        }
+        if self
+            .world
+            .get_resource::<Assets<A>>()
+            .is_some_and(|assets| assets.contains(handle))
+        {
+            if self
+                .world
+                .get::<HookSlots>(self.entity)
+                .and_then(|slots| slots.get_ref::<A>(index))
+                .is_some()
+            {
+                let mut slots = self.world.get_mut::<HookSlots>(self.entity).unwrap();
+                slots.clear(index);
+            }
+            return self.world.resource::<Assets<A>>().get(handle).unwrap();
+        }
+        if self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get_ref::<A>(index))
+            .is_none()
+        {
+            self.replace_hook_slot(index, A::default());
+        }
+        self.world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get_ref::<A>(index))
+            .unwrap()
+    }
 
-    // /// Create a scoped value. This can be used to pass data to child presenters.
-    // /// The value is accessible by all child presenters.
-    // pub fn define_scoped_value<T: Clone + Send + Sync + PartialEq + 'static>(
-    //     &mut self,
-    //     key: ScopedValueKey<T>,
-    //     value: T,
-    // ) {
-    //     let mut ec = self.bc.world.entity_mut(self.bc.entity);
-    //     match ec.get_mut::<ScopedValueMap>() {
-    //         Some(mut ctx) => {
-    //             if let Some(v) = ctx.0.get(&key.id()) {
-    //                 // Don't update if value hasn't changed
-    //                 if v.downcast_ref::<T>() == Some(&value) {
-    //                     return;
-    //                 }
-    //             }
-    //             ctx.0.insert(key.id(), Box::new(value));
-    //         }
-    //         None => {
-    //             let mut map = ScopedValueMap::default();
-    //             map.0.insert(key.id(), Box::new(value));
-    //             ec.insert(map);
-    //         }
-    //     }
-    // }
+    /// Compute a value from reactive data sources, memoized across runs of the presenter.
+    /// `key` identifies the call site; use the [`use_computed!`](crate::use_computed) macro to
+    /// generate it. The dependencies of `f` are tracked separately, and when the presenter runs
+    /// again, `f` is only called if they have changed; otherwise the stored value is returned.
+    /// If `f` is called and the result equals the stored value, the stored value is returned.
+    /// Like the other hooks, the value is stored in a hook slot, so this must be called in the
+    /// same order on each run.
+    pub fn use_computed<T: PartialEq + Clone + Send + Sync + 'static>(
+        &mut self,
+        key: usize,
+        f: impl FnOnce(&Rcx) -> T,
+    ) -> T {
+        let index = self.next_hook_index();
+        let stored = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<ComputedSlot<T>>(index))
+            .filter(|slot| slot.key == key);
+        if let Some(slot) = stored
+            .as_ref()
+            .filter(|slot| !slot.deps.dependencies_changed(self.world))
+        {
+            self.tracking.borrow_mut().add_nested(slot.deps.clone());
+            return slot.value.clone();
+        }
 
-    // /// Retrieve the value of a context variable.
-    // pub fn get_scoped_value<T: Clone + Send + Sync + 'static>(
-    //     &self,
-    //     key: ScopedValueKey<T>,
-    // ) -> Option<T> {
-    //     let mut entity = self.bc.entity;
-    //     loop {
-    //         let ec = self.bc.world.entity(entity);
-    //         if let Some(ctx) = ec.get::<ScopedValueMap>() {
-    //             if let Some(val) = ctx.0.get(&key.id()) {
-    //                 let cid = self
-    //                     .bc
-    //                     .world
-    //                     .component_id::<ScopedValueMap>()
-    //                     .expect("ScopedValueMap component type is not registered");
-    //                 self.tracking.borrow_mut().components.insert((entity, cid));
-    //                 return val.downcast_ref::<T>().cloned();
-    //             }
-    //         }
-    //         match ec.get::<Parent>() {
-    //             Some(parent) => entity = **parent,
-    //             _ => return None,
-    //         }
-    //     }
-    // }
+        let mut deps = TrackingScope::new(self.world.change_tick());
+        let value = f(&Rcx::new(self.world, &mut deps));
+        let value = match stored {
+            Some(slot) if slot.value == value => slot.value,
+            _ => value,
+        };
+        let deps = Arc::new(deps);
+        self.tracking.borrow_mut().add_nested(deps.clone());
+        self.replace_hook_slot(
+            index,
+            ComputedSlot {
+                key,
+                value: value.clone(),
+                deps,
+            },
+        );
+        value
+    }
+
+    /// Returns the first display node of this presenter: the view entity itself if it has a
+    /// [`ViewVisibility`], otherwise the first node of a descendant view. If there is no such
+    /// node, the children of the view entity are added as a dependency instead.
+    fn first_display_node(&self) -> Option<Entity> {
+        if self.world.get::<ViewVisibility>(self.entity).is_some() {
+            return Some(self.entity);
+        }
+        let mut stack = vec![self.entity];
+        while let Some(entity) = stack.pop() {
+            let Some(children) = self.world.get::<Children>(entity) else {
+                continue;
+            };
+            for child in children.iter() {
+                // The view may be locked if it is an ancestor which is currently reacting.
+                if let Some(handle) = self.world.get::<ViewHandle>(*child) {
+                    if let Ok(view) = handle.view.try_lock() {
+                        if let Some(node) = view.nodes(self.world).iter().next() {
+                            return Some(node);
+                        }
+                    }
+                }
+            }
+            stack.extend(children.iter().rev());
+        }
+        // The output of the presenter has not been built yet; react when it is.
+        self.use_component::<Children>(self.entity);
+        None
+    }
+
+    /// Returns true if the display nodes of this presenter were visible in any camera as of
+    /// the last frame, taking into account both the [`Visibility`] hierarchy and frustum
+    /// culling. The [`ViewVisibility`] of the first display node is added as a dependency, so
+    /// the presenter reacts when it comes into or out of view.
+    pub fn use_visible_in_camera(&self) -> bool {
+        self.first_display_node()
+            .and_then(|node| self.use_component::<ViewVisibility>(node))
+            .is_some_and(|visibility| visibility.get())
+    }
+
+    /// Returns the bounding rectangle, in logical pixels, of the first display node of this
+    /// presenter clipped to the viewport of the given camera, or `None` if the node is
+    /// entirely outside of the viewport. The node's layout is added as a dependency.
+    pub fn use_aabb_in_camera(&self, camera: Entity) -> Option<Rect> {
+        let node = self.first_display_node()?;
+        let size = self.use_component::<Node>(node)?.size();
+        let center = self.use_component::<GlobalTransform>(node)?.translation();
+        let viewport = self
+            .use_component::<Camera>(camera)?
+            .logical_viewport_rect()?;
+        let rect = Rect::from_center_size(center.truncate(), size).intersect(viewport);
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Register a one-shot system which can be invoked from event handlers, and return a
+    /// [`CallbackHandle`] that can be used to run it. The system is only registered the first
+    /// time the presenter runs; later runs return the same handle, and `system` is ignored.
+    /// The system is unregistered when the presenter is razed.
+    pub fn use_callback<Marker, S: IntoSystem<(), (), Marker> + 'static>(
+        &mut self,
+        system: S,
+    ) -> CallbackHandle {
+        CallbackHandle {
+            id: self.run_system(system),
+        }
+    }
+
+    /// Register a one-shot system, and return its [`SystemId`], which can be passed to
+    /// `World::run_system` from event handlers. As with [`use_callback`](Cx::use_callback),
+    /// the system is only registered the first time the presenter runs; later runs return the
+    /// same id, and `system` is ignored. The system is unregistered when the presenter is
+    /// razed.
+    pub fn run_system<Marker, S: IntoSystem<(), (), Marker> + 'static>(
+        &mut self,
+        system: S,
+    ) -> SystemId {
+        let index = self.next_hook_index();
+        if let Some(id) = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<SystemId>(index))
+        {
+            return id;
+        }
+        let id = self.world.register_system(system);
+        self.replace_hook_slot(index, id);
+        self.tracking.borrow_mut().add_hook_cleanup(move |world| {
+            let _ = world.remove_system(id);
+        });
+        id
+    }
+
+    /// Equivalent to [`use_callback`](Cx::use_callback): the system is only registered the
+    /// first time the presenter runs, so the returned handle is the same on every run.
+    pub fn use_system<Marker, S: IntoSystem<(), (), Marker> + 'static>(
+        &mut self,
+        system: S,
+    ) -> CallbackHandle {
+        self.use_callback(system)
+    }
+
+    /// Return a [`ReactiveEventReader`] for events of type `E`. The `Events<E>` resource is
+    /// added as a dependency, so the presenter will react when new events are sent. The reader
+    /// is created the first time the presenter runs, and later runs return the same reader, so
+    /// events are only delivered once.
+    pub fn use_event_reader<E: Event>(&mut self) -> ReactiveEventReader<E> {
+        self.tracking.borrow_mut().add_resource::<Events<E>>(
+            self.world
+                .components()
+                .get_resource_id(TypeId::of::<Events<E>>())
+                .expect("Unknown event type"),
+        );
+        let index = self.next_hook_index();
+        if let Some(reader) = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<ReactiveEventReader<E>>(index))
+        {
+            return reader;
+        }
+        let reader = ReactiveEventReader::new();
+        self.replace_hook_slot(index, reader.clone());
+        reader
+    }
+
+    /// Create a [`Mutable`] which is identified by `key`, or return the existing one if this
+    /// presenter has already created it. Unlike
+    /// [`create_mutable`](ReactiveContextMut::create_mutable), the value is preserved when the
+    /// presenter runs again. Like the other hooks, the mutable is stored in a hook slot, so this
+    /// must be called in the same order on each run; if the key or the value type differs from
+    /// the previous run, a new mutable is created. The mutable is despawned when the presenter
+    /// is razed.
+    pub fn create_stable_mutable<T: Send + Sync + 'static>(
+        &mut self,
+        key: &'static str,
+        init: T,
+    ) -> Mutable<T> {
+        let index = self.next_hook_index();
+        let existing = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<StableMutableSlot<T>>(index))
+            .filter(|slot| slot.key == key && self.world.get_entity(slot.id).is_some());
+        if let Some(slot) = existing {
+            return Mutable {
+                id: slot.id,
+                marker: PhantomData,
+            };
+        }
+        let mutable = self
+            .world
+            .spawn(MutableValue {
+                value: Box::new(init),
+            })
+            .id();
+        self.replace_hook_slot(
+            index,
+            StableMutableSlot::<T> {
+                key,
+                id: mutable,
+                marker: PhantomData,
+            },
+        );
+        self.tracking.borrow_mut().add_hook_owned(mutable);
+        Mutable {
+            id: mutable,
+            marker: PhantomData,
+        }
+    }
+
+    /// Create state which is updated by dispatching actions to a `reducer` function. Returns a
+    /// read handle for the state, and a [`Dispatch`] handle which applies actions. The state
+    /// is only created the first time the presenter runs; later runs return handles to the
+    /// same state, and `init` is ignored. The state is despawned when the presenter is razed.
+    pub fn use_reducer<S, A>(
+        &mut self,
+        init: S,
+        reducer: fn(S, A) -> S,
+    ) -> (ReducerState<S>, Dispatch<A>)
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        A: Send + Sync + 'static,
+    {
+        let index = self.next_hook_index();
+        let existing = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<ReducerSlot<S, A>>(index));
+        let slot = match existing {
+            Some(slot) => slot,
+            None => {
+                let id = self
+                    .world
+                    .spawn(MutableValue {
+                        value: Box::new(init),
+                    })
+                    .id();
+                self.tracking.borrow_mut().add_hook_owned(id);
+                let slot = ReducerSlot {
+                    id,
+                    dispatch: Dispatch {
+                        apply: Arc::new(move |world: &mut World, action: A| {
+                            apply_reducer(world, id, reducer, action)
+                        }),
+                    },
+                    marker: PhantomData,
+                };
+                self.replace_hook_slot(index, slot.clone());
+                slot
+            }
+        };
+        (
+            ReducerState {
+                mutable: Mutable {
+                    id: slot.id,
+                    marker: PhantomData,
+                },
+            },
+            slot.dispatch,
+        )
+    }
+
+    /// Create a [`Transition`] with the given initial state. Use [`Transition::go_to`] to
+    /// change the state over time. The transition is only created the first time the
+    /// presenter runs; later runs return the same transition, and `initial` is ignored. The
+    /// transition is despawned when the presenter is razed.
+    pub fn use_transition<S: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        initial: S,
+    ) -> Transition<S> {
+        let index = self.next_hook_index();
+        if let Some(transition) = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<Transition<S>>(index))
+        {
+            return transition;
+        }
+        let id = self.world.spawn(TransitionValue::new(initial)).id();
+        self.tracking.borrow_mut().add_hook_owned(id);
+        let transition = Transition {
+            id,
+            marker: PhantomData,
+        };
+        self.replace_hook_slot(index, transition);
+        transition
+    }
+
+    /// Returns the value that was passed to this hook the previous time the presenter ran,
+    /// or `None` on the first run, and stores `current` for the next run. The value is
+    /// stored on the view entity, so it is dropped when the presenter is razed.
+    pub fn use_previous<T: Clone + Send + Sync + 'static>(&mut self, current: T) -> Option<T> {
+        let index = self.next_hook_index();
+        self.replace_hook_slot(index, current)
+    }
+
+    /// Spawn an entity with the given bundle as a child of the view entity. The entity is only
+    /// spawned the first time the presenter runs; subsequent runs return the same entity, and
+    /// `bundle` is ignored. An entity which has been removed with
+    /// [`despawn_child`](Cx::despawn_child) is not spawned again, so the returned entity may no
+    /// longer exist. The entity is despawned when the presenter is razed.
+    pub fn spawn_child<B: Bundle>(&mut self, bundle: B) -> Entity {
+        let index = self.next_hook_index();
+        let existing = self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<Entity>(index));
+        if let Some(child) = existing {
+            return child;
+        }
+        let child = self.world.spawn(bundle).set_parent(self.entity).id();
+        self.replace_hook_slot(index, child);
+        self.tracking.borrow_mut().add_hook_cleanup(move |world| {
+            if let Some(entt) = world.get_entity_mut(child) {
+                entt.despawn_recursive();
+            }
+        });
+        child
+    }
+
+    /// Reactively modify the [`Style`] of `target` in place. `update` is re-run whenever its
+    /// dependencies change, and the style is only written if a field changed. Like
+    /// [`spawn_child`](Cx::spawn_child), the reaction is only created the first time the
+    /// presenter runs, and it is despawned when the presenter is razed. See [`StyleBuilder`]
+    /// for a per-field alternative.
+    ///
+    /// [`StyleBuilder`]: crate::StyleBuilder
+    pub fn use_computed_style(
+        &mut self,
+        target: Entity,
+        update: impl Fn(&Rcx, &mut Style) + Send + Sync + 'static,
+    ) {
+        let index = self.next_hook_index();
+        if self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<bool>(index))
+            .is_some()
+        {
+            return;
+        }
+        let mut producer =
+            ComponentComputedRef::new(move |re: &mut Rcx, style: &mut Style| update(re, style));
+        // The reaction is kept by later runs, so it is owned as a hook rather than by this run.
+        let mut owner = TrackingScope::new(self.world.change_tick());
+        producer.start(&mut owner, target, self.world);
+        self.tracking.borrow_mut().take_hook_owned(&mut owner);
+        self.replace_hook_slot(index, true);
+    }
+
+    /// Despawn a child entity which was spawned with [`spawn_child`](Cx::spawn_child). The
+    /// child stays despawned on later runs of the presenter.
+    pub fn despawn_child(&mut self, child: Entity) {
+        if let Some(entt) = self.world.get_entity_mut(child) {
+            entt.despawn_recursive();
+        }
+    }
+
+    /// Returns the index of the next hook slot.
+    fn next_hook_index(&mut self) -> usize {
+        let index = self.hook_index;
+        self.hook_index += 1;
+        index
+    }
+
+    /// Replace the value in the given hook slot, returning the previous value.
+    fn replace_hook_slot<T: Send + Sync + 'static>(&mut self, index: usize, value: T) -> Option<T> {
+        let mut entt = self.world.entity_mut(self.entity);
+        match entt.get_mut::<HookSlots>() {
+            Some(mut slots) => slots.replace(index, value),
+            None => {
+                let mut slots = HookSlots::default();
+                slots.replace(index, value);
+                entt.insert(slots);
+                None
+            }
+        }
+    }
+
+    /// Return a reference to a presenter-local singleton of type `T`, creating it with the
+    /// default value on the first call. The value is stored on the view entity, so it
+    /// survives reactions but not razing. The value is added to the tracking scope, so the
+    /// presenter reacts when it is modified via
+    /// [`use_local_resource_mut`](Cx::use_local_resource_mut).
+    pub fn use_local_resource<T: Default + Send + Sync + 'static>(&mut self) -> &T {
+        self.init_local_resource::<T>();
+        &self.world.get::<LocalResource<T>>(self.entity).unwrap().0
+    }
+
+    /// Return a mutable reference to a presenter-local singleton of type `T`, creating it
+    /// with the default value on the first call.
+    pub fn use_local_resource_mut<T: Default + Send + Sync + 'static>(&mut self) -> Mut<'_, T> {
+        self.init_local_resource::<T>();
+        self.world
+            .get_mut::<LocalResource<T>>(self.entity)
+            .unwrap()
+            .map_unchanged(|local| &mut local.0)
+    }
+
+    /// Insert the local resource of type `T` if it is absent, and add it to the tracking scope.
+    fn init_local_resource<T: Default + Send + Sync + 'static>(&mut self) {
+        let mut entt = self.world.entity_mut(self.entity);
+        if !entt.contains::<LocalResource<T>>() {
+            entt.insert(LocalResource(T::default()));
+        }
+        let cid = self.world.init_component::<LocalResource<T>>();
+        self.tracking.borrow_mut().add_component(self.entity, cid);
+    }
 
-    // // / Return an object which can be used to send a message to the current presenter.
-    // // pub fn use_callback<In, Marker>(&mut self, sys: impl IntoSystem<In, (), Marker>) {
-    // //     todo!()
-    // // }
+    /// Start loading the asset at `path`, and return its handle. Repeated calls with the same
+    /// path return the same handle, rather than starting another load. The handle is stored
+    /// on the view entity, so the asset stays loaded until the presenter is razed, or until
+    /// the presenter runs again without requesting it. Use
+    /// [`use_asset`](ReactiveContext::use_asset) to react when the asset has loaded.
+    pub fn load_asset<A: Asset>(&mut self, path: impl Into<AssetPath<'static>>) -> Handle<A> {
+        let path = path.into();
+        let key = (TypeId::of::<A>(), path.to_string());
+        let pass = self.tracking.borrow().id();
+        let mut entt = self.world.entity_mut(self.entity);
+        if !entt.contains::<LoadedAssets>() {
+            entt.insert(LoadedAssets::default());
+        }
+        let mut loaded = entt.get_mut::<LoadedAssets>().unwrap();
+        if let Some((id, handle)) = loaded.0.get_mut(&key) {
+            *id = pass;
+            return handle.clone().typed::<A>();
+        }
+        let handle: Handle<A> = self.world.resource::<AssetServer>().load(path);
+        self.world
+            .get_mut::<LoadedAssets>(self.entity)
+            .unwrap()
+            .0
+            .insert(key, (pass, handle.clone().untyped()));
+        handle
+    }
+
+    /// Mark this presenter as not ready, so that the nearest enclosing
+    /// [`Suspense`](crate::Suspense) displays its placeholder. Returns a [`SuspendHandle`]
+    /// which can be used to resume later, or `None` if there is no enclosing `Suspense`.
+    /// Suspension is cleared each time the presenter runs, so a presenter which is still not
+    /// ready should suspend again.
+    pub fn suspend(&mut self) -> Option<SuspendHandle> {
+        let handle = SuspendHandle {
+            boundary: find_boundary(self.world, self.entity)?,
+            entity: self.entity,
+        };
+        handle.suspend(self.world);
+        Some(handle)
+    }
+
+    /// Mark this presenter as ready, undoing a previous call to [`suspend`](Cx::suspend).
+    pub fn resume(&mut self) {
+        if let Some(boundary) = find_boundary(self.world, self.entity) {
+            set_resumed(self.world, boundary, self.entity);
+        }
+    }
+
+    /// Register a callback which runs once, after the view has been built. The callback is
+    /// run by the reactor systems, so it doesn't run if the view is razed in the same frame
+    /// that it was built. The callback is only registered the first time the presenter runs;
+    /// on later runs, `callback` is ignored.
+    pub fn on_mount(&mut self, callback: impl FnOnce(Entity, &mut World) + Send + Sync + 'static) {
+        if !self.use_lifecycle_hook() {
+            return;
+        }
+        let mut entt = self.world.entity_mut(self.entity);
+        match entt.get_mut::<MountCallbacks>() {
+            Some(mut callbacks) => callbacks.0.push(Box::new(callback)),
+            None => {
+                entt.insert(MountCallbacks(vec![Box::new(callback)]));
+            }
+        }
+    }
+
+    /// Register a callback which runs when the view is razed. The callback only runs if the
+    /// view's mount callbacks have run. Like [`on_mount`](Cx::on_mount), the callback is only
+    /// registered the first time the presenter runs.
+    pub fn on_unmount(
+        &mut self,
+        callback: impl FnOnce(Entity, &mut World) + Send + Sync + 'static,
+    ) {
+        if !self.use_lifecycle_hook() {
+            return;
+        }
+        let mut entt = self.world.entity_mut(self.entity);
+        if !entt.contains::<Mounted>() && !entt.contains::<MountCallbacks>() {
+            // Make sure that the mount pass will mark this view as mounted.
+            entt.insert(MountCallbacks::default());
+        }
+        match entt.get_mut::<UnmountCallbacks>() {
+            Some(mut callbacks) => callbacks.0.push(Box::new(callback)),
+            None => {
+                entt.insert(UnmountCallbacks(vec![Box::new(callback)]));
+            }
+        }
+    }
+
+    /// Claims a hook slot for a lifecycle callback. Returns true if this is the first run, in
+    /// which case the callback should be registered.
+    fn use_lifecycle_hook(&mut self) -> bool {
+        let index = self.next_hook_index();
+        if self
+            .world
+            .get::<HookSlots>(self.entity)
+            .and_then(|slots| slots.get::<LifecycleHook>(index))
+            .is_some()
+        {
+            return false;
+        }
+        self.replace_hook_slot(index, LifecycleHook);
+        true
+    }
+
+    /// Provide a value for the given [`Context`] to this presenter and its descendants.
+    /// Descendants which consume the context will react when the value changes.
+    pub fn provide_context<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        context: Context<T>,
+        value: T,
+    ) {
+        let mut entt = self.world.entity_mut(self.entity);
+        match entt.get_mut::<ScopedValueMap>() {
+            Some(mut map) => {
+                // Don't update if value hasn't changed
+                if let Some(prev) = map.0.get(&context.key()) {
+                    if prev.downcast_ref::<T>() == Some(&value) {
+                        return;
+                    }
+                }
+                map.0.insert(context.key(), Box::new(value));
+            }
+            None => {
+                let mut map = ScopedValueMap::default();
+                map.0.insert(context.key(), Box::new(value));
+                entt.insert(map);
+            }
+        }
+    }
+
+    /// Retrieve the value of the given [`Context`] from the nearest presenter which provides
+    /// it, or `None` if there is no provider. The provider is added to the tracking scope.
+    pub fn consume_context<T: Clone + Send + Sync + 'static>(
+        &self,
+        context: Context<T>,
+    ) -> Option<T> {
+        let mut entity = self.entity;
+        loop {
+            let entt = self.world.get_entity(entity)?;
+            if let Some(map) = entt.get::<ScopedValueMap>() {
+                if let Some(value) = map.0.get(&context.key()) {
+                    let cid = self
+                        .world
+                        .component_id::<ScopedValueMap>()
+                        .expect("ScopedValueMap component type is not registered");
+                    self.tracking.borrow_mut().add_component(entity, cid);
+                    return value.downcast_ref::<T>().cloned();
+                }
+            }
+            entity = entt.get::<Parent>()?.get();
+        }
+    }
+
+    /// Returns the [`Theme`] provided by the nearest [`ThemeProvider`](crate::ThemeProvider),
+    /// or the default theme if there is none.
+    pub fn use_theme(&self) -> Theme {
+        self.use_theme_of::<Theme>()
+    }
+
+    /// Like [`use_theme`](Cx::use_theme), but for a custom set of design tokens.
+    pub fn use_theme_of<T: ThemeTokens>(&self) -> T {
+        self.consume_context(T::THEME_CTX).unwrap_or_default()
+    }
 
     // fn add_tracked_component<C: Component>(&self, entity: Entity) {
     //     let cid = self
@@ -294,7 +1481,7 @@ impl<'p, 'w, Props> ReactiveContext<'p> for Cx<'p, 'w, Props> {
         self.world
     }
 
-    fn tracking(&self) -> RefMut<&'p mut TrackingScope> {
+    fn tracking(&self) -> RefMut<'_, &'p mut TrackingScope> {
         self.tracking.borrow_mut()
     }
 }
@@ -308,6 +1495,11 @@ impl<'p, 'w, Props> ReactiveContextMut<'p> for Cx<'p, 'w, Props> {
 /// Immutable reactive context, used for reactive closures such as derived signals.
 /// This is a stripped down version of [`Cx`] that does not allow creating new reactions,
 /// and which has no parameters.
+///
+/// `Rcx` is the only immutable context: it is passed to every reactive closure, including
+/// the item functions of lists, computed bundles and computed text. Code which only reads
+/// reactive data should be written against [`ReactiveContext`], which both `Rcx` and [`Cx`]
+/// implement, so that it can be called from either.
 pub struct Rcx<'p, 'w> {
     /// Bevy World
     pub(crate) world: &'w World,
@@ -330,7 +1522,7 @@ impl<'p, 'w> ReactiveContext<'p> for Rcx<'p, 'w> {
         self.world
     }
 
-    fn tracking(&self) -> RefMut<&'p mut TrackingScope> {
+    fn tracking(&self) -> RefMut<'_, &'p mut TrackingScope> {
         self.tracking.borrow_mut()
     }
 }
@@ -0,0 +1,429 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::TrackingScope;
+
+/// Plugin which helps to find over-subscribed and expensive presenters and reactions.
+///
+/// At a configurable interval, it logs the reactions which have the largest number of
+/// dependencies at debug level. It also warns about reactions whose most recent run took
+/// longer than the configured threshold.
+/// Execution statistics for each reaction are collected in the [`ReactorDiagnostics`]
+/// resource.
+///
+/// This is separate from [`ReactorPlugin`](crate::ReactorPlugin), and is intended to be added
+/// only while debugging.
+pub struct ReactorDiagnosticsPlugin {
+    /// Number of reactions to log each time.
+    count: usize,
+
+    /// Minimum time between logs of the reactions with the most dependencies.
+    interval: Duration,
+
+    /// Reactions which take longer than this are logged as warnings.
+    threshold: Duration,
+}
+
+impl ReactorDiagnosticsPlugin {
+    /// Construct a new `ReactorDiagnosticsPlugin`, which logs the top 5 reactions once a
+    /// second, and warns about reactions which take longer than 1ms.
+    pub fn new() -> Self {
+        Self {
+            count: 5,
+            interval: Duration::from_secs(1),
+            threshold: Duration::from_millis(1),
+        }
+    }
+
+    /// Set the number of reactions to log each time.
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Set the minimum time between logs of the reactions with the most dependencies.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the execution time above which reactions are logged as warnings.
+    pub fn with_threshold(mut self, threshold: Duration) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+impl Default for ReactorDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Settings for [`ReactorDiagnosticsPlugin`].
+#[derive(Resource)]
+struct ReactorDiagnosticsSettings {
+    count: usize,
+    interval: Duration,
+    threshold: Duration,
+}
+
+impl Plugin for ReactorDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReactorDiagnosticsSettings {
+            count: self.count,
+            interval: self.interval,
+            threshold: self.threshold,
+        })
+        .init_resource::<ReactorDiagnostics>()
+        .add_systems(
+            Last,
+            (
+                log_busiest_reactions,
+                log_slow_reactions,
+                prune_reaction_stats,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Diagnostic which measures the number of reactions run each frame, including views.
+pub const REACTION_COUNT: DiagnosticId =
+    DiagnosticId::from_u128(0x3f6b_19a2_7c4e_4d8a_9b1e_5a2c_d0f4_7e61);
+
+/// Diagnostic which measures the number of views rebuilt each frame, that is, views whose
+/// reaction replaced their output nodes.
+pub const REBUILD_COUNT: DiagnosticId =
+    DiagnosticId::from_u128(0x8c21_e4d7_0b5a_4f39_a6c8_13f2_9e7d_b450);
+
+/// Diagnostic which measures the number of mutables committed each frame.
+pub const MUTABLE_COMMIT_COUNT: DiagnosticId =
+    DiagnosticId::from_u128(0x51da_7e08_c3b9_4a62_8f17_e6b4_2d95_a3c8);
+
+/// Number of reactions, rebuilds and commits in the current frame. While this resource is
+/// present, the reactive systems count their work, which is reported to Bevy's diagnostics
+/// by [`ReactorPlugin::with_diagnostics`](crate::ReactorPlugin::with_diagnostics).
+#[derive(Resource, Default)]
+pub(crate) struct ReactorFrameCounts {
+    pub(crate) reactions: usize,
+    pub(crate) rebuilds: usize,
+    pub(crate) commits: usize,
+}
+
+/// Register the reactor's diagnostics, and the system which measures them.
+pub(crate) fn add_frame_diagnostics(app: &mut App) {
+    app.init_resource::<ReactorFrameCounts>()
+        .register_diagnostic(Diagnostic::new(
+            REACTION_COUNT,
+            "reactor.reaction_count",
+            20,
+        ))
+        .register_diagnostic(Diagnostic::new(REBUILD_COUNT, "reactor.rebuild_count", 20))
+        .register_diagnostic(Diagnostic::new(
+            MUTABLE_COMMIT_COUNT,
+            "reactor.mutable_commit_count",
+            20,
+        ))
+        .add_systems(Last, measure_frame_counts);
+}
+
+/// System which reports the counts for the current frame to Bevy's diagnostics.
+fn measure_frame_counts(mut diagnostics: Diagnostics, mut counts: ResMut<ReactorFrameCounts>) {
+    let counts = std::mem::take(&mut *counts);
+    diagnostics.add_measurement(REACTION_COUNT, || counts.reactions as f64);
+    diagnostics.add_measurement(REBUILD_COUNT, || counts.rebuilds as f64);
+    diagnostics.add_measurement(MUTABLE_COMMIT_COUNT, || counts.commits as f64);
+}
+
+/// Execution statistics for a single reaction.
+#[derive(Clone, Debug)]
+pub struct ReactionStat {
+    /// The reaction entity.
+    pub entity: Entity,
+
+    /// The `Name` of the reaction entity, or the entity id if it has no name.
+    pub label: String,
+
+    /// Total time spent running the reaction.
+    pub total: Duration,
+
+    /// Time taken by the most recent run of the reaction.
+    pub last: Duration,
+
+    /// Number of times the reaction has run.
+    pub calls: u32,
+}
+
+impl ReactionStat {
+    /// Returns the average time taken to run the reaction.
+    pub fn average(&self) -> Duration {
+        self.total / self.calls.max(1)
+    }
+}
+
+/// Resource which holds execution statistics for each reaction. While this resource is
+/// present, [`run_reactions`](crate::run_reactions) measures the execution time of each
+/// reaction. It is added by [`ReactorDiagnosticsPlugin`].
+#[derive(Resource, Default)]
+pub struct ReactorDiagnostics {
+    stats: HashMap<Entity, ReactionStat>,
+}
+
+impl ReactorDiagnostics {
+    /// Record a run of a reaction.
+    pub(crate) fn record(&mut self, entity: Entity, label: String, time: Duration) {
+        let stat = self.stats.entry(entity).or_insert_with(|| ReactionStat {
+            entity,
+            label,
+            total: Duration::ZERO,
+            last: Duration::ZERO,
+            calls: 0,
+        });
+        stat.total += time;
+        stat.last = time;
+        stat.calls += 1;
+    }
+
+    /// Returns the statistics for the `n` reactions with the highest average execution time,
+    /// slowest first.
+    pub fn top_n(&self, n: usize) -> Vec<ReactionStat> {
+        let mut stats: Vec<ReactionStat> = self.stats.values().cloned().collect();
+        stats.sort_by_key(|stat| std::cmp::Reverse(stat.average()));
+        stats.truncate(n);
+        stats
+    }
+
+    /// Returns the statistics for the given reaction entity.
+    pub fn get(&self, entity: Entity) -> Option<&ReactionStat> {
+        self.stats.get(&entity)
+    }
+}
+
+/// Returns the label used to identify a reaction in diagnostics.
+pub(crate) fn reaction_label(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", entity),
+    }
+}
+
+/// Returns the reactions with the most dependencies, in descending order.
+fn busiest_reactions<'a>(
+    scopes: impl Iterator<Item = (Entity, &'a TrackingScope, Option<&'a Name>)>,
+    count: usize,
+) -> Vec<(Entity, usize, Option<&'a Name>)> {
+    let mut reactions: Vec<_> = scopes
+        .map(|(entity, scope, name)| (entity, scope.dependency_count(), name))
+        .collect();
+    reactions.sort_by_key(|(_, count, _)| std::cmp::Reverse(*count));
+    reactions.truncate(count);
+    reactions
+}
+
+fn log_busiest_reactions(
+    settings: Res<ReactorDiagnosticsSettings>,
+    time: Option<Res<Time>>,
+    mut last_log: Local<Option<Duration>>,
+    scopes: Query<(Entity, &TrackingScope, Option<&Name>)>,
+) {
+    let now = time.map(|time| time.elapsed()).unwrap_or_default();
+    if last_log.is_some_and(|last| now.saturating_sub(last) < settings.interval) {
+        return;
+    }
+    *last_log = Some(now);
+    for (entity, count, name) in busiest_reactions(scopes.iter(), settings.count) {
+        match name {
+            Some(name) => debug!("{} {:?}: {} dependencies", name, entity, count),
+            None => debug!("{:?}: {} dependencies", entity, count),
+        }
+    }
+}
+
+fn log_slow_reactions(
+    settings: Res<ReactorDiagnosticsSettings>,
+    diagnostics: Res<ReactorDiagnostics>,
+) {
+    if !diagnostics.is_changed() {
+        return;
+    }
+    for stat in diagnostics.stats.values() {
+        if stat.last > settings.threshold {
+            warn!(
+                "Reaction {} took {:?} (average {:?} over {} runs)",
+                stat.label,
+                stat.last,
+                stat.average(),
+                stat.calls
+            );
+        }
+    }
+}
+
+/// System which removes the statistics of reactions which have been despawned.
+pub(crate) fn prune_reaction_stats(world: &mut World) {
+    let Some(diagnostics) = world.get_resource::<ReactorDiagnostics>() else {
+        return;
+    };
+    let despawned: Vec<Entity> = diagnostics
+        .stats
+        .keys()
+        .copied()
+        .filter(|entity| world.get_entity(*entity).is_none())
+        .collect();
+    if despawned.is_empty() {
+        return;
+    }
+    let mut diagnostics = world.resource_mut::<ReactorDiagnostics>();
+    for entity in despawned {
+        diagnostics.stats.remove(&entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, run_reactions, Reaction, ReactionHandle, ReactionId, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct A;
+
+    #[derive(Resource)]
+    struct B;
+
+    #[test]
+    fn test_busiest_reactions() {
+        let mut world = World::default();
+        world.insert_resource(A);
+        world.insert_resource(B);
+
+        let mut small = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut small);
+        cx.use_resource::<A>();
+        let mut large = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut large);
+        cx.use_resource::<A>();
+        cx.use_resource::<B>();
+        let e_small = world.spawn(small).id();
+        let e_large = world.spawn(large).id();
+
+        let mut query = world.query::<(Entity, &TrackingScope, Option<&Name>)>();
+        let top = busiest_reactions(query.iter(&world), 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].0, top[0].1), (e_large, 2));
+        let all = busiest_reactions(query.iter(&world), 5);
+        assert_eq!(all.len(), 2);
+        assert_eq!((all[1].0, all[1].1), (e_small, 1));
+    }
+
+    struct Noop;
+
+    impl Reaction for Noop {
+        fn react(&mut self, _owner: Entity, _world: &mut World, _tracking: &mut TrackingScope) {}
+    }
+
+    fn spawn_reaction(world: &mut World, name: &str) -> Entity {
+        let tick = world.change_tick();
+        let reaction = world
+            .spawn((
+                Name::new(name.to_string()),
+                ReactionHandle::new(Noop),
+                TrackingScope::new(tick),
+            ))
+            .id();
+        ReactionId(reaction).trigger(world);
+        reaction
+    }
+
+    #[test]
+    fn test_reaction_stats() {
+        let mut world = World::default();
+        world.init_resource::<ReactorDiagnostics>();
+        let slow = spawn_reaction(&mut world, "slow");
+        let fast = spawn_reaction(&mut world, "fast");
+        run_reactions(&mut world);
+
+        // Each run of a reaction is recorded.
+        let diagnostics = world.resource::<ReactorDiagnostics>();
+        assert_eq!(diagnostics.get(slow).unwrap().calls, 1);
+        assert_eq!(diagnostics.get(slow).unwrap().label, "slow");
+        assert_eq!(diagnostics.get(fast).unwrap().calls, 1);
+
+        // Reactions are ranked by their average time.
+        let mut diagnostics = world.resource_mut::<ReactorDiagnostics>();
+        diagnostics.record(slow, "slow".into(), Duration::from_millis(30));
+        diagnostics.record(fast, "fast".into(), Duration::from_millis(2));
+        let top = diagnostics.top_n(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].entity, slow);
+        assert_eq!(diagnostics.get(slow).unwrap().calls, 2);
+
+        // Statistics are removed when the reaction is despawned.
+        world.despawn(slow);
+        prune_reaction_stats(&mut world);
+        let diagnostics = world.resource::<ReactorDiagnostics>();
+        assert!(diagnostics.get(slow).is_none());
+        assert_eq!(diagnostics.top_n(5)[0].entity, fast);
+    }
+
+    #[derive(Resource, Default)]
+    struct Clock(u32);
+
+    fn tick(mut clock: ResMut<Clock>) {
+        clock.0 += 1;
+    }
+
+    struct ReadClock;
+
+    impl Reaction for ReadClock {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let cx = Cx::new(&(), world, Entity::PLACEHOLDER, tracking);
+            cx.use_resource::<Clock>();
+        }
+    }
+
+    fn clock_app(plugin: crate::ReactorPlugin) -> App {
+        let mut app = App::new();
+        app.init_resource::<Clock>()
+            .add_plugins(plugin)
+            .add_systems(First, tick);
+        for _ in 0..3 {
+            let mut reaction = ReadClock;
+            let mut scope = TrackingScope::new(app.world.change_tick());
+            let entity = app.world.spawn_empty().id();
+            reaction.react(entity, &mut app.world, &mut scope);
+            app.world
+                .entity_mut(entity)
+                .insert((ReactionHandle::new(reaction), scope));
+        }
+        app
+    }
+
+    #[test]
+    fn test_frame_diagnostics() {
+        let mut app = clock_app(crate::ReactorPlugin::new().with_diagnostics());
+        for _ in 0..10 {
+            app.update();
+        }
+        let store = app.world.resource::<bevy::diagnostic::DiagnosticsStore>();
+        let reactions = store.get(REACTION_COUNT).unwrap();
+        assert_eq!(reactions.name, "reactor.reaction_count");
+        assert!(reactions.average().unwrap() > 0.);
+        assert_eq!(store.get(REBUILD_COUNT).unwrap().average(), Some(0.));
+
+        // Without diagnostics, nothing is registered or counted.
+        let mut app = clock_app(crate::ReactorPlugin::new());
+        app.update();
+        assert!(!app.world.contains_resource::<ReactorFrameCounts>());
+        assert!(app
+            .world
+            .get_resource::<bevy::diagnostic::DiagnosticsStore>()
+            .is_none_or(|store| store.get(REACTION_COUNT).is_none()));
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// A view which renders a [`ViewRef`] that is chosen at runtime. The view function is called
+/// reactively, and if it returns a different view than before (compared by pointer), the old
+/// view is razed and the new one is built.
+pub struct DynamicView<ViewFn: Fn(&Rcx) -> ViewRef> {
+    view_fn: ViewFn,
+    state: Option<(ViewRef, Entity)>,
+}
+
+impl<ViewFn: Fn(&Rcx) -> ViewRef> DynamicView<ViewFn> {
+    /// Construct a new `DynamicView`.
+    pub fn new(view_fn: ViewFn) -> Self {
+        Self {
+            view_fn,
+            state: None,
+        }
+    }
+}
+
+impl<ViewFn: Fn(&Rcx) -> ViewRef> View for DynamicView<ViewFn> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.state {
+            Some((ref view, entity)) => child_nodes(view, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let view = (self.view_fn)(&Rcx::new(world, tracking));
+        if let Some((ref prev, _)) = self.state {
+            if Arc::ptr_eq(prev, &view) {
+                return;
+            }
+        }
+        if let Some((prev, entity)) = self.state.take() {
+            prev.lock().unwrap().raze(entity, world);
+        }
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.state = Some((view, entity));
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<ViewFn: Send + Sync + 'static + Fn(&Rcx) -> ViewRef> IntoView for DynamicView<ViewFn> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReactiveContext;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Current(ViewRef);
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    fn node(view: &ViewRef, world: &World) -> Entity {
+        match view.lock().unwrap().nodes(world) {
+            NodeSpan::Node(node) => node,
+            _ => panic!("Expected a single node"),
+        }
+    }
+
+    #[test]
+    fn test_dynamic_view() {
+        let mut world = World::default();
+        world.insert_resource(Current("first".into_view()));
+        let parent = world.spawn_empty().id();
+        let view = DynamicView::new(|cx| cx.use_resource::<Current>().0.clone()).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let first = node(&view, &world);
+
+        // Same view: nothing is rebuilt.
+        react(&view, entity, &mut world);
+        assert_eq!(node(&view, &world), first);
+
+        // Different view: the old view is razed.
+        world.resource_mut::<Current>().0 = "second".into_view();
+        react(&view, entity, &mut world);
+        let second = node(&view, &world);
+        assert_ne!(second, first);
+        assert!(world.get_entity(first).is_none());
+        assert_eq!(
+            world.get::<Text>(second).unwrap().sections[0].value,
+            "second"
+        );
+
+        // Razing the dynamic view razes the inner view.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(second).is_none());
+    }
+}
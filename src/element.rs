@@ -6,9 +6,9 @@ use std::{
 use bevy::prelude::*;
 
 use crate::{
-    bundle::{BundleComputed, BundleProducer, BundleStatic},
+    bundle::{BundleComputed, BundleComputedRef, BundleProducer, BundleStatic},
     node_span::NodeSpan,
-    view::View,
+    view::{child_nodes, View},
     view_tuple::ViewTuple,
     DespawnScopes, IntoView, Rcx, TrackingScope, ViewHandle, ViewRef,
 };
@@ -90,6 +90,14 @@ impl<B: Bundle + Default> Element<B> {
         self
     }
 
+    /// Add a producer of components to the element, such as a [`BundleSequence`].
+    ///
+    /// [`BundleSequence`]: crate::BundleSequence
+    pub fn insert_producer(mut self, producer: impl BundleProducer + 'static) -> Self {
+        self.producers.push(Box::new(producer));
+        self
+    }
+
     /// Add a computed bundle to the element.
     pub fn insert_computed<T: Bundle, F: Send + Sync + 'static + FnMut(&mut Rcx) -> T>(
         mut self,
@@ -99,37 +107,32 @@ impl<B: Bundle + Default> Element<B> {
         self
     }
 
-    // pub fn insert_update<
-    //     T: Component,
-    //     F1: Send + Sync + 'static + FnMut() -> T,
-    //     F2: Send + Sync + 'static + FnMut(&mut Re, &mut T),
-    // >(
-    //     mut self,
-    //     init: F1,
-    //     update: F2,
-    // ) -> Self {
-    //     self.producers.push(Arc::new(Mutex::new(BundleComputedRef {
-    //         target: None,
-    //         init,
-    //         update,
-    //         tracker: None,
-    //         marker: PhantomData,
-    //     })));
-    //     self
-    // }
+    /// Add a component to the element which is reactively updated in place. `init` produces
+    /// the initial value of the component, and `update` modifies it whenever its
+    /// dependencies change. This avoids replacing the entire component when only one field
+    /// changes.
+    pub fn insert_update<
+        T: Component + Clone + PartialEq,
+        F1: Send + Sync + 'static + FnOnce() -> T,
+        F2: Send + Sync + 'static + FnMut(&mut Rcx, &mut T),
+    >(
+        mut self,
+        init: F1,
+        update: F2,
+    ) -> Self {
+        self.producers
+            .push(Box::new(BundleComputedRef::new(init, update)));
+        self
+    }
 
     /// Attach the children to the node. Note that each child view may produce multiple nodes,
     /// or none.
     fn attach_children(&self, world: &mut World) {
-        let mut count: usize = 0;
-        for child in self.children.iter() {
-            count += child.view.lock().unwrap().nodes().count();
-        }
-
-        let mut flat: Vec<Entity> = Vec::with_capacity(count);
-        for child in self.children.iter() {
-            child.view.lock().unwrap().nodes().flatten(&mut flat);
-        }
+        let flat: Vec<Entity> = self
+            .children
+            .iter()
+            .flat_map(|child| child_nodes(&child.view, child.entity.unwrap(), world).flat_collect())
+            .collect();
 
         world
             .entity_mut(self.display.unwrap())
@@ -138,7 +141,15 @@ impl<B: Bundle + Default> Element<B> {
 }
 
 impl<B: Bundle + Default> View for Element<B> {
-    fn nodes(&self) -> NodeSpan {
+    fn debug_name(&self) -> String {
+        if self.debug_name.is_empty() {
+            "Element".to_string()
+        } else {
+            self.debug_name.clone()
+        }
+    }
+
+    fn nodes(&self, _world: &World) -> NodeSpan {
         match self.display {
             None => NodeSpan::Empty,
             Some(node) => NodeSpan::Node(node),
@@ -146,7 +157,6 @@ impl<B: Bundle + Default> View for Element<B> {
     }
 
     fn build(&mut self, view_entity: Entity, world: &mut World) {
-        world.entity_mut(view_entity).insert(Name::new("Element"));
         // Build element node
         assert!(self.display.is_none());
         let display = world
@@ -174,10 +184,12 @@ impl<B: Bundle + Default> View for Element<B> {
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.display.is_some());
-        // Raze all child views
-        for child in self.children.drain(..) {
+        // Raze all child views. The children are kept, so that the fragment can be built
+        // again, as when it is passed to a presenter which re-renders.
+        for child in self.children.iter_mut() {
             let inner = child.view.clone();
-            inner.lock().unwrap().raze(child.entity.unwrap(), world);
+            let entity = child.entity.take().unwrap();
+            inner.lock().unwrap().raze(entity, world);
             // Child raze() will despawn itself.
         }
 
@@ -202,3 +214,73 @@ impl<B: Bundle + Default> IntoView for Element<B> {
         Arc::new(Mutex::new(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, view, ReactiveContext, ShowHide, TextComputed, TextStatic};
+
+    use super::*;
+
+    #[test]
+    fn test_view_macro_single() {
+        let mut world = TestWorld::new();
+        let count = world.create_mutable(1);
+        let id = count.id;
+        let entity = world.spawn_view(view! {
+            <TextComputed text=move |re| format!("{}", re.read_mutable_clone::<i32>(id)) />
+        });
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 1);
+        let text = world.world().get::<Text>(nodes[0]).unwrap();
+        assert_eq!(text.sections[0].value, "1");
+    }
+
+    #[test]
+    fn test_view_macro_siblings() {
+        let mut world = TestWorld::new();
+        let entity = world.spawn_view(view! {
+            <TextStatic text="Hello" />
+            "World"
+            { "!" }
+        });
+        assert_eq!(world.get_node_entities(entity).len(), 3);
+    }
+
+    #[test]
+    fn test_view_macro_nested() {
+        let mut world = TestWorld::new();
+        let entity = world.spawn_view(view! {
+            <Element<NodeBundle> name="Outer" style=Style::default()>
+                <TextStatic text="Hello" />
+                <Element<NodeBundle>>
+                    "World"
+                </Element<NodeBundle>>
+            </Element<NodeBundle>>
+        });
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 1);
+        let children = world.world().get::<Children>(nodes[0]).unwrap();
+        assert_eq!(children.len(), 2);
+        let inner = world.world().get::<Children>(children[1]).unwrap();
+        assert_eq!(inner.len(), 1);
+        assert!(world.world().get::<Text>(inner[0]).is_some());
+    }
+
+    #[test]
+    fn test_view_macro_attributes() {
+        let mut world = TestWorld::new();
+        let entity = world.spawn_view(view! {
+            <ShowHide child={"Shown"} visible_fn=|_| true />
+            <Element<NodeBundle> insert=BackgroundColor(Color::RED) />
+        });
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 2);
+        assert!(world.world().get::<Text>(nodes[0]).is_some());
+        assert_eq!(
+            world.world().get::<BackgroundColor>(nodes[1]).unwrap().0,
+            Color::RED
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    any::Any,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// Marker component for the view entity of an [`ErrorBoundary`].
+#[derive(Component)]
+pub(crate) struct ErrorBoundaryMarker;
+
+/// Component which holds the message of a panic raised by a descendant of an [`ErrorBoundary`],
+/// along with the entity of the reaction which panicked.
+#[derive(Component)]
+pub(crate) struct CaughtPanic {
+    pub(crate) message: String,
+    pub(crate) source: Entity,
+}
+
+/// A view which catches panics raised while building or reacting its child, and displays
+/// a fallback view instead.
+///
+/// When a descendant reaction panics, the child is kept, but its display nodes are replaced by
+/// the fallback. Once the reaction that panicked runs again without panicking, for example
+/// because the data that caused the panic was corrected, the child is shown again. A child
+/// which panics while it is being built is despawned, and is built again once any of the data
+/// read by its presenters before the panic changes.
+pub struct ErrorBoundary<FB: IntoView, FallbackFn: Fn(String) -> FB> {
+    child: ViewRef,
+    child_entity: Option<Entity>,
+    fallback_fn: FallbackFn,
+    fallback: Option<(ViewRef, Entity)>,
+
+    /// The reaction whose panic is being displayed.
+    failed: Option<Entity>,
+
+    /// The dependencies of a child which panicked while being built, which is built again when
+    /// they change.
+    build_failed: Option<Arc<TrackingScope>>,
+}
+
+impl<FB: IntoView, FallbackFn: Fn(String) -> FB> ErrorBoundary<FB, FallbackFn> {
+    /// Construct a new `ErrorBoundary`. The fallback function is passed the panic message.
+    pub fn new(child: impl IntoView, fallback_fn: FallbackFn) -> Self {
+        Self {
+            child: child.into_view(),
+            child_entity: None,
+            fallback_fn,
+            fallback: None,
+            failed: None,
+            build_failed: None,
+        }
+    }
+
+    /// Returns true if the boundary is currently displaying the fallback view.
+    pub fn has_error(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    fn show_fallback(&mut self, message: String, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.fallback.take() {
+            raze_child(&view, entity, world);
+        }
+        // The fallback view is created anew for each error.
+        let view = (self.fallback_fn)(message).into_view();
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.fallback = Some((view, entity));
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    /// Build the child, showing the fallback instead if the build panics.
+    fn build_child(&mut self, view_entity: Entity, world: &mut World) {
+        match spawn_child(&self.child, view_entity, world) {
+            Ok(entity) => {
+                self.child_entity = Some(entity);
+                if let Some((view, entity)) = self.fallback.take() {
+                    raze_child(&view, entity, world);
+                    world.entity_mut(view_entity).insert(DisplayNodeChanged);
+                }
+            }
+            Err((message, deps)) => {
+                self.show_fallback(message, view_entity, world);
+                self.build_failed = Some(deps);
+            }
+        }
+    }
+}
+
+impl<FB: IntoView, FallbackFn: Fn(String) -> FB> View for ErrorBoundary<FB, FallbackFn> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match (&self.fallback, self.child_entity) {
+            (Some((view, entity)), _) => child_nodes(view, *entity, world),
+            (None, Some(entity)) => child_nodes(&self.child, entity, world),
+            (None, None) => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.child_entity.is_none());
+        world.entity_mut(view_entity).insert(ErrorBoundaryMarker);
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.build_child(view_entity, world);
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        // Subscribe to panics raised by descendant reactions.
+        let panic_id = world.init_component::<CaughtPanic>();
+        tracking.add_component(view_entity, panic_id);
+
+        // Build the child again once the data which made its build panic has changed.
+        if let Some(deps) = self.build_failed.take() {
+            if deps.dependencies_changed(world) {
+                self.build_child(view_entity, world);
+            } else {
+                self.build_failed = Some(deps);
+            }
+        }
+        if let Some(deps) = &self.build_failed {
+            tracking.add_nested(deps.clone());
+        }
+
+        match world.entity_mut(view_entity).take::<CaughtPanic>() {
+            Some(CaughtPanic { message, source }) => {
+                self.show_fallback(message, view_entity, world);
+                self.failed = Some(source);
+            }
+            None => {
+                // The failed reaction has run again without panicking.
+                if self.failed.take().is_some() {
+                    if let Some((view, entity)) = self.fallback.take() {
+                        raze_child(&view, entity, world);
+                    }
+                    world.entity_mut(view_entity).insert(DisplayNodeChanged);
+                }
+            }
+        }
+
+        // React again when the failed reaction runs.
+        if let Some(failed) = self.failed {
+            let scope_id = world.init_component::<TrackingScope>();
+            tracking.add_component(failed, scope_id);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.fallback.take() {
+            raze_child(&view, entity, world);
+        }
+        if let Some(entity) = self.child_entity.take() {
+            raze_child(&self.child, entity, world);
+        }
+        self.failed = None;
+        self.build_failed = None;
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<FB: IntoView + 'static, FallbackFn: Send + Sync + 'static + Fn(String) -> FB> IntoView
+    for ErrorBoundary<FB, FallbackFn>
+{
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// Same as [`ViewHandle::spawn`], except that a panic during build is caught and returned as
+/// an error, along with the dependencies of the scopes which the partial build left behind.
+/// The lock is held outside of the unwind boundary so that the view isn't poisoned.
+fn spawn_child(
+    view: &ViewRef,
+    parent: Entity,
+    world: &mut World,
+) -> Result<Entity, (String, Arc<TrackingScope>)> {
+    let entity = world
+        .spawn(ViewHandle::from_ref(view.clone()))
+        .set_parent(parent)
+        .id();
+    let mut inner = view.lock().unwrap();
+    match catch_unwind(AssertUnwindSafe(|| inner.build(entity, world))) {
+        Ok(()) => Ok(entity),
+        Err(payload) => {
+            let mut deps = TrackingScope::new(world.change_tick());
+            release_partial_scopes(entity, world, &mut deps);
+            if let Some(entt) = world.get_entity_mut(entity) {
+                entt.despawn_recursive();
+            }
+            Err((panic_message(payload.as_ref()), Arc::new(deps)))
+        }
+    }
+}
+
+/// Release the scopes on an entity and its descendants, left behind by a build which panicked,
+/// and add their dependencies to `deps`.
+fn release_partial_scopes(entity: Entity, world: &mut World, deps: &mut TrackingScope) {
+    // Children are visited first, since releasing a scope may despawn the children it owns.
+    let children = world
+        .get::<Children>(entity)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+    for child in children {
+        release_partial_scopes(child, world, deps);
+    }
+    let Some(mut scope) = world
+        .get_entity_mut(entity)
+        .and_then(|mut entt| entt.take::<TrackingScope>())
+    else {
+        return;
+    };
+    let mut nested = TrackingScope::new(scope.tick());
+    nested.take_subscriptions(&mut scope);
+    deps.add_nested(Arc::new(nested));
+    world.release_scope(scope);
+}
+
+/// Raze a child view which may have been left in an inconsistent state by a panic. If razing
+/// fails, then just despawn the view entities.
+fn raze_child(view: &ViewRef, entity: Entity, world: &mut World) {
+    let mut inner = view.lock().unwrap_or_else(PoisonError::into_inner);
+    if catch_unwind(AssertUnwindSafe(|| inner.raze(entity, world))).is_err() {
+        if let Some(entt) = world.get_entity_mut(entity) {
+            entt.despawn_recursive();
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown error".to_string()
+    }
+}
+
+/// Called when a reaction panics. Forwards the panic to the nearest enclosing [`ErrorBoundary`],
+/// or resumes unwinding if there is none.
+pub(crate) fn forward_panic(entity: Entity, world: &mut World, payload: Box<dyn Any + Send>) {
+    let mut e = entity;
+    loop {
+        // The entity which panicked may have been despawned by the panicking reaction, in which
+        // case there is nothing left to show the error.
+        let Some(entt) = world.get_entity(e) else {
+            error!(
+                "Reaction {:?} panicked after it was despawned: {}",
+                entity,
+                panic_message(payload.as_ref())
+            );
+            return;
+        };
+        if entt.contains::<ErrorBoundaryMarker>() {
+            world.entity_mut(e).insert(CaughtPanic {
+                message: panic_message(payload.as_ref()),
+                source: entity,
+            });
+            return;
+        }
+        e = match entt.get::<Parent>() {
+            Some(parent) => parent.get(),
+            None => resume_unwind(payload),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, Cx, Element, Presenter, ReactiveContext};
+
+    use super::*;
+
+    #[derive(crate::Presenter)]
+    struct Checked {
+        value: Entity,
+    }
+
+    impl Presenter for Checked {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let value = cx.read_mutable::<i32>(cx.props.value);
+            if value < 0 {
+                panic!("Negative");
+            }
+            format!("{}", value)
+        }
+    }
+
+    fn spawn_boundary(world: &mut TestWorld, value: Entity) -> Entity {
+        let root = world.spawn_view(
+            Element::<NodeBundle>::new()
+                .children(ErrorBoundary::new(Checked { value }, |message| {
+                    format!("Error: {}", message)
+                })),
+        );
+        world.tick();
+        world.get_node_entities(root)[0]
+    }
+
+    fn text(world: &TestWorld, element: Entity) -> String {
+        let children = world.world().get::<Children>(element).unwrap();
+        assert_eq!(children.len(), 1);
+        world.world().get::<Text>(children[0]).unwrap().sections[0]
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_panic_on_build() {
+        let mut world = TestWorld::new();
+        let value = world.create_mutable(-1);
+        let element = spawn_boundary(&mut world, value.id);
+        assert_eq!(text(&world, element), "Error: Negative");
+
+        // The fallback stays while the build keeps panicking.
+        world.set_mutable(&value, -2);
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world, element), "Error: Negative");
+
+        // The child is built once the value it read is corrected.
+        world.set_mutable(&value, 2);
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world, element), "2");
+
+        // The child keeps reacting after recovery.
+        world.set_mutable(&value, 3);
+        world.tick();
+        assert_eq!(text(&world, element), "3");
+    }
+
+    #[test]
+    fn test_panic_after_despawn() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        world.despawn(entity);
+        forward_panic(entity, &mut world, Box::new("Despawned"));
+    }
+
+    #[test]
+    fn test_panic_on_react() {
+        let mut world = TestWorld::new();
+        let value = world.create_mutable(1);
+        let element = spawn_boundary(&mut world, value.id);
+        assert_eq!(text(&world, element), "1");
+
+        // The panic is caught, and the fallback is shown.
+        world.set_mutable(&value, -1);
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world, element), "Error: Negative");
+
+        // The fallback stays while the child keeps panicking.
+        world.set_mutable(&value, -2);
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world, element), "Error: Negative");
+
+        // Recovery once the value is corrected.
+        world.set_mutable(&value, 2);
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world, element), "2");
+
+        // The child keeps reacting after recovery.
+        world.set_mutable(&value, 3);
+        world.tick();
+        assert_eq!(text(&world, element), "3");
+    }
+}
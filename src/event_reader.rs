@@ -0,0 +1,112 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    ecs::event::{Event, Events, ManualEventReader},
+    prelude::*,
+};
+
+/// An event reader which can be used from within a presenter. Created via
+/// [`Cx::use_event_reader`](crate::Cx::use_event_reader).
+///
+/// The read position is shared between clones of the reader, so events are only
+/// delivered once no matter which clone reads them.
+pub struct ReactiveEventReader<E: Event> {
+    pub(crate) reader: Arc<Mutex<ManualEventReader<E>>>,
+}
+
+impl<E: Event> ReactiveEventReader<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            reader: Arc::new(Mutex::new(ManualEventReader::default())),
+        }
+    }
+
+    /// Returns true if there are no unread events.
+    pub fn is_empty(&self, world: &World) -> bool {
+        let events = world.resource::<Events<E>>();
+        self.reader.lock().unwrap().is_empty(events)
+    }
+}
+
+impl<E: Event + Clone> ReactiveEventReader<E> {
+    /// Returns an iterator over copies of all events that have been sent since the
+    /// previous call.
+    pub fn iter(&self, world: &World) -> impl Iterator<Item = E> {
+        let events = world.resource::<Events<E>>();
+        let mut reader = self.reader.lock().unwrap();
+        reader.read(events).cloned().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<E: Event> Clone for ReactiveEventReader<E> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, TrackingScope};
+
+    use super::*;
+
+    #[derive(Event, Clone)]
+    struct TestEvent(u32);
+
+    #[test]
+    fn test_event_reader() {
+        let mut world = World::default();
+        world.init_resource::<Events<TestEvent>>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let entity = world.spawn_empty().id();
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        let reader = cx.use_event_reader::<TestEvent>();
+
+        // No events
+        assert!(reader.is_empty(&world));
+        assert_eq!(reader.iter(&world).count(), 0);
+
+        // Single event
+        world.send_event(TestEvent(1));
+        assert!(!reader.is_empty(&world));
+        let values: Vec<u32> = reader.iter(&world).map(|e| e.0).collect();
+        assert_eq!(values, vec![1]);
+        assert_eq!(reader.iter(&world).count(), 0);
+
+        // Multiple events, read after a frame boundary
+        world.send_event(TestEvent(2));
+        world.send_event(TestEvent(3));
+        world.resource_mut::<Events<TestEvent>>().update();
+        let values: Vec<u32> = reader.iter(&world).map(|e| e.0).collect();
+        assert_eq!(values, vec![2, 3]);
+
+        // Nothing left over on the next frame
+        world.resource_mut::<Events<TestEvent>>().update();
+        assert_eq!(reader.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn test_reader_persists() {
+        let mut world = World::default();
+        world.init_resource::<Events<TestEvent>>();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        world.send_event(TestEvent(1));
+
+        // Events sent before the first run are delivered.
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        let reader = cx.use_event_reader::<TestEvent>();
+        assert_eq!(reader.iter(&world).map(|e| e.0).collect::<Vec<_>>(), [1]);
+
+        // Later runs return the same reader, so events are not delivered again.
+        world.send_event(TestEvent(2));
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        let reader = cx.use_event_reader::<TestEvent>();
+        assert_eq!(reader.iter(&world).map(|e| e.0).collect::<Vec<_>>(), [2]);
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        let reader = cx.use_event_reader::<TestEvent>();
+        assert!(reader.is_empty(&world));
+    }
+}
@@ -0,0 +1,78 @@
+use std::any::type_name;
+
+use bevy::{
+    ecs::event::{Event, Events},
+    prelude::*,
+};
+
+type SendFn = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Resource which holds events sent by presenters, until they are dispatched by
+/// [`dispatch_deferred_events`]. Events are deferred because the presenter's world is
+/// already borrowed by the reactive context.
+#[derive(Resource, Default)]
+pub(crate) struct DeferredEvents(pub(crate) Vec<SendFn>);
+
+impl DeferredEvents {
+    /// Add an event to be sent later.
+    pub(crate) fn push<E: Event>(&mut self, event: E) {
+        self.0.push(Box::new(move |world: &mut World| {
+            match world.get_resource_mut::<Events<E>>() {
+                Some(mut events) => events.send(event),
+                None => panic!(
+                    "Event type {} was sent but is not registered; call `App::add_event` first",
+                    type_name::<E>()
+                ),
+            }
+        }));
+    }
+}
+
+/// System which sends the events that were sent by presenters, in the order that they were
+/// sent.
+pub fn dispatch_deferred_events(world: &mut World) {
+    let Some(mut deferred) = world.get_resource_mut::<DeferredEvents>() else {
+        return;
+    };
+    let pending = std::mem::take(&mut deferred.0);
+    for send in pending {
+        send(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, ReactiveContextMut, TrackingScope};
+
+    use super::*;
+
+    #[derive(Event, Clone, Debug, PartialEq)]
+    struct Navigate(&'static str);
+
+    #[test]
+    fn test_send_event() {
+        let mut world = World::default();
+        world.init_resource::<Events<Navigate>>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        cx.send_event(Navigate("home"));
+        cx.send_event(Navigate("settings"));
+
+        // Events are not sent until they are dispatched.
+        assert!(world.resource::<Events<Navigate>>().is_empty());
+        dispatch_deferred_events(&mut world);
+        let events = world.resource::<Events<Navigate>>();
+        let sent: Vec<_> = events.get_reader().read(events).cloned().collect();
+        assert_eq!(sent, vec![Navigate("home"), Navigate("settings")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered")]
+    fn test_unregistered_event() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        cx.send_event(Navigate("home"));
+        dispatch_deferred_events(&mut world);
+    }
+}
@@ -5,7 +5,7 @@ use bevy::ecs::entity::Entity;
 use bevy::ecs::world::World;
 use bevy::hierarchy::Parent;
 
-use crate::{lcs::lcs, View};
+use crate::{lcs::lcs, view::child_nodes, View};
 use crate::{DespawnScopes, IntoView, Rcx, TrackingScope, ViewHandle, ViewRef};
 
 use crate::node_span::NodeSpan;
@@ -207,11 +207,11 @@ impl<
         F: Fn(&Item) -> V + Send,
     > View for ForEach<Item, ItemIter, ItemFn, Cmp, V, F>
 {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, world: &World) -> NodeSpan {
         let child_spans: Vec<NodeSpan> = self
             .items
             .iter()
-            .map(|item| item.view.lock().unwrap().nodes())
+            .map(|item| child_nodes(&item.view, item.id, world))
             .collect();
         NodeSpan::Fragment(child_spans.into_boxed_slice())
     }
@@ -5,15 +5,66 @@ use bevy::ecs::world::World;
 use bevy::hierarchy::Parent;
 
 use crate::{
+    mutable::{after_write, MutableValue, MutableValueNext},
+    scope::ReactionCycleGuard,
+    view::child_nodes,
     DespawnScopes, DisplayNodeChanged, IntoView, Rcx, TrackingScope, View, ViewHandle, ViewRef,
 };
 
 use crate::node_span::NodeSpan;
 
+type SameItemFn<Item> = Box<dyn Fn(&Item, &Item) -> bool + Send + Sync>;
+type UpdateFn<Item> = Box<dyn Fn(&Item, usize) -> Option<ViewRef> + Send + Sync>;
+
 pub struct IndexedListItem<Item> {
     id: Entity,
     view: ViewRef,
     value: Item,
+    /// The host view, if the item's contents can be replaced in place.
+    host: Option<Arc<Mutex<ItemHost>>>,
+}
+
+/// Wrapper view which gives a list item a stable entity, so that the item's contents can be
+/// replaced without despawning it.
+struct ItemHost {
+    view: ViewRef,
+    inner: Option<Entity>,
+}
+
+impl ItemHost {
+    /// Raze the current contents and build `view` in their place.
+    fn replace(&mut self, view: ViewRef, host_entity: Entity, world: &mut World) {
+        if let Some(inner) = self.inner.take() {
+            self.view.lock().unwrap().raze(inner, world);
+        }
+        self.view = view;
+        self.inner = Some(ViewHandle::spawn(&self.view, host_entity, world));
+        world.entity_mut(host_entity).insert(DisplayNodeChanged);
+    }
+}
+
+impl View for ItemHost {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.inner {
+            Some(inner) => child_nodes(&self.view, inner, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        self.inner = Some(ViewHandle::spawn(&self.view, view_entity, world));
+        let tick = world.change_tick();
+        world
+            .entity_mut(view_entity)
+            .insert(TrackingScope::new(tick));
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(inner) = self.inner.take() {
+            self.view.lock().unwrap().raze(inner, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
 }
 
 #[doc(hidden)]
@@ -30,6 +81,19 @@ pub struct ForIndex<
     marker: std::marker::PhantomData<Item>,
     fallback: Option<ViewRef>,
     fallback_ent: Option<Entity>,
+    fallback_until_complete: bool,
+    /// Maximum number of item views to build per update, if any.
+    batch_size: Option<usize>,
+    /// Number of items which have not yet been built.
+    pending: usize,
+    /// Mutable which is written to schedule another update while items are pending.
+    progress: Option<Entity>,
+    /// If present, items at the same index are considered the same if this returns true,
+    /// rather than if they are equal.
+    same_item: Option<SameItemFn<Item>>,
+    /// If present, changed items are first offered to this function, which may return a new
+    /// view to build in place of the old one without despawning the item's entity.
+    update_fn: Option<UpdateFn<Item>>,
 }
 
 impl<
@@ -48,6 +112,12 @@ impl<
             marker: std::marker::PhantomData,
             fallback: None,
             fallback_ent: None,
+            fallback_until_complete: false,
+            batch_size: None,
+            pending: 0,
+            progress: None,
+            same_item: None,
+            update_fn: None,
         }
     }
 
@@ -56,6 +126,122 @@ impl<
         self.fallback = Some(fallback.into_view());
         self
     }
+
+    /// Keep displaying the fallback view until all of the items have been built, rather than
+    /// only until the first item is built. Only meaningful with [`with_batch_size`].
+    ///
+    /// [`with_batch_size`]: ForIndex::with_batch_size
+    pub fn with_fallback_until_complete(mut self) -> Self {
+        self.fallback_until_complete = true;
+        self
+    }
+
+    /// Limit the number of item views which are built in a single update. The remaining items
+    /// are built in batches on subsequent updates, so that a large list grows incrementally
+    /// rather than being built in a single frame.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be at least 1");
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Identify items by the key returned from `key_fn`, rather than by equality. An item
+    /// whose key is unchanged keeps its view, even if other fields of the item have changed.
+    /// This gives `ForIndex` the keyed behavior of [`For::each_cmp`](crate::For::each_cmp) for
+    /// items which stay at the same index.
+    pub fn with_item_key<K: PartialEq>(
+        mut self,
+        key_fn: impl Fn(&Item) -> K + Send + Sync + 'static,
+    ) -> Self {
+        self.same_item = Some(Box::new(move |a, b| key_fn(a) == key_fn(b)));
+        self
+    }
+
+    /// Allow changed items to be updated without despawning their entity. When the item at an
+    /// index changes, `update_fn` is called with the new item: if it returns `Some(view)`, the
+    /// new view replaces the contents of the existing item entity; if it returns `None`, the
+    /// item is razed and rebuilt as usual.
+    pub fn with_update_in_place(
+        mut self,
+        update_fn: impl Fn(&Item, usize) -> Option<V> + Send + Sync + 'static,
+    ) -> Self
+    where
+        V: 'static,
+    {
+        self.update_fn = Some(Box::new(move |item, index| {
+            update_fn(item, index).map(IntoView::into_view)
+        }));
+        self
+    }
+
+    /// Returns the number of items which have not yet been built.
+    pub fn pending_count(&self) -> usize {
+        self.pending
+    }
+
+    /// Returns the number of items which currently have a view, not including pending items.
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the item at `index`, or `None` if there is no view for that index.
+    pub fn item_at(&self, index: usize) -> Option<&Item> {
+        self.items.get(index).map(|entry| &entry.value)
+    }
+
+    fn is_same_item(&self, a: &Item, b: &Item) -> bool {
+        match self.same_item {
+            Some(ref same_item) => same_item(a, b),
+            None => a == b,
+        }
+    }
+
+    /// Build the view for a single item. If the list supports updating in place, the view is
+    /// wrapped in an [`ItemHost`].
+    fn spawn_item(
+        &self,
+        item: &Item,
+        index: usize,
+        view_entity: Entity,
+        world: &mut World,
+    ) -> IndexedListItem<Item> {
+        let view = (self.each_fn)(item, index).into_view();
+        let (view, host) = match self.update_fn {
+            Some(_) => {
+                let host = Arc::new(Mutex::new(ItemHost { view, inner: None }));
+                (host.clone() as ViewRef, Some(host))
+            }
+            None => (view, None),
+        };
+        IndexedListItem {
+            id: ViewHandle::spawn(&view, view_entity, world),
+            view,
+            value: item.clone(),
+            host,
+        }
+    }
+}
+
+/// Constructor for a [`ForIndex`] whose callback takes the index first, followed by the item,
+/// in the same order as [`Iterator::enumerate`].
+pub struct ForIndexed;
+
+impl ForIndexed {
+    /// Construct an index for loop for an array of items. The callback is called once for each
+    /// array element; its arguments are the array index and the item, and its result is a View.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<
+        Item: PartialEq + Clone + 'static,
+        ItemIter: Iterator<Item = Item>,
+        ItemFn: Fn(&Rcx) -> ItemIter,
+        V: IntoView,
+        F: Fn(usize, &Item) -> V,
+    >(
+        item_fn: ItemFn,
+        each_fn: F,
+    ) -> ForIndex<Item, ItemIter, ItemFn, V, impl Fn(&Item, usize) -> V> {
+        ForIndex::new(item_fn, move |item, index| each_fn(index, item))
+    }
 }
 
 impl<
@@ -66,17 +252,26 @@ impl<
         F: Fn(&Item, usize) -> V,
     > View for ForIndex<Item, ItemIter, ItemFn, V, F>
 {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, world: &World) -> NodeSpan {
         let child_spans: Vec<NodeSpan> = self
             .items
             .iter()
-            .map(|item| item.view.lock().unwrap().nodes())
+            .map(|item| child_nodes(&item.view, item.id, world))
             .collect();
         NodeSpan::Fragment(child_spans.into_boxed_slice())
     }
 
     fn build(&mut self, view_entity: bevy::prelude::Entity, world: &mut World) {
         let mut tracking = TrackingScope::new(world.change_tick());
+        if self.batch_size.is_some() {
+            let progress = world
+                .spawn(MutableValue {
+                    value: Box::new(0usize),
+                })
+                .id();
+            tracking.add_owned(progress);
+            self.progress = Some(progress);
+        }
         self.react(view_entity, world, &mut tracking);
         world.entity_mut(view_entity).insert(tracking);
         assert!(
@@ -91,56 +286,109 @@ impl<
         world: &mut World,
         tracking: &mut crate::TrackingScope,
     ) {
-        let iter = (self.item_fn)(&Rcx::new(world, tracking));
-        let mut prev_len = self.items.len();
+        let next: Vec<Item> = (self.item_fn)(&Rcx::new(world, tracking)).collect();
         let mut changed = false;
 
-        let mut index = 0usize;
-        for item in iter {
-            if index < prev_len {
-                // Overwrite existing items.
-                let entry = &mut self.items[index];
-                if item != entry.value {
-                    entry.view.lock().unwrap().raze(entry.id, world);
+        // Fast path: items in the common prefix are unchanged, and are skipped entirely. For
+        // appends to, or pops from, the tail of the list this is all but the last item.
+        let prefix = self
+            .items
+            .iter()
+            .zip(next.iter())
+            .take_while(|(entry, item)| self.is_same_item(&entry.value, item))
+            .count();
+        if self.same_item.is_some() {
+            for (entry, item) in self.items.iter_mut().zip(next.iter()).take(prefix) {
+                entry.value = item.clone();
+            }
+        }
+
+        // Items from `built` onwards are left for a later update, once the batch is used up.
+        let mut budget = self.batch_size.unwrap_or(usize::MAX);
+        let mut built = next.len();
+        for (index, item) in next.iter().enumerate().skip(prefix) {
+            if index < self.items.len() && self.is_same_item(&self.items[index].value, item) {
+                self.items[index].value = item.clone();
+                continue;
+            }
+            if budget == 0 {
+                built = index;
+                break;
+            }
+            budget -= 1;
+            if index < self.items.len() {
+                // Update the existing item in place, if possible.
+                let replacement = match (&self.update_fn, &self.items[index].host) {
+                    (Some(update_fn), Some(_)) => update_fn(item, index),
+                    _ => None,
+                };
+                if let Some(view) = replacement {
+                    let entry = &mut self.items[index];
+                    let host = entry.host.as_ref().unwrap();
+                    host.lock().unwrap().replace(view, entry.id, world);
                     entry.value = item.clone();
-                    entry.view = (self.each_fn)(&entry.value, index).into_view();
-                    entry.id = ViewHandle::spawn(&entry.view, view_entity, world);
-                    changed = true;
+                } else {
+                    // Overwrite existing items.
+                    let entry = &self.items[index];
+                    entry.view.lock().unwrap().raze(entry.id, world);
+                    self.items[index] = self.spawn_item(item, index, view_entity, world);
                 }
+                changed = true;
             } else {
                 // Append new items.
-                let view = (self.each_fn)(&item, index).into_view();
-                let id = ViewHandle::spawn(&view, view_entity, world);
-                self.items.push(IndexedListItem {
-                    id,
-                    view,
-                    value: item.clone(),
-                });
+                let entry = self.spawn_item(item, index, view_entity, world);
+                self.items.push(entry);
                 changed = true;
             }
-            index += 1;
         }
 
         // Raze surplus items.
-        while index < prev_len {
-            prev_len -= 1;
-            let entry = &mut self.items[prev_len];
+        while self.items.len() > next.len() {
+            let entry = self.items.pop().unwrap();
             entry.view.lock().unwrap().raze(entry.id, world);
-            self.items.pop();
+            changed = true;
+        }
+        self.pending = next.len() - built;
+
+        // While items are pending, schedule another update by writing to the progress mutable,
+        // which this reaction depends on. Re-triggering itself is the intent rather than a
+        // cycle, so the write is not recorded by the cycle guard.
+        if let Some(progress) = self.progress {
+            tracking.add_mutable(progress);
+            if self.pending > 0 {
+                let count = *world
+                    .get::<MutableValue>(progress)
+                    .unwrap()
+                    .value
+                    .downcast_ref::<usize>()
+                    .unwrap();
+                world
+                    .entity_mut(progress)
+                    .insert(MutableValueNext(Box::new(count + 1)));
+                after_write(world, progress);
+                if let Some(mut guard) = world.get_resource_mut::<ReactionCycleGuard>() {
+                    guard.written.retain(|mutable| *mutable != progress);
+                }
+            }
         }
 
+        let show_fallback =
+            self.items.is_empty() || (self.fallback_until_complete && self.pending > 0);
+
         // Handle fallback
         if let Some(ref mut fallback) = self.fallback {
             match self.fallback_ent {
-                // If there are > 0 items, destroy fallback if present.
-                Some(fb_ent) if index > 0 => {
+                // If there are items to show, destroy fallback if present.
+                Some(fb_ent) if !show_fallback => {
                     fallback.lock().unwrap().raze(fb_ent, world);
                     self.fallback_ent = None;
+                    changed = true;
                 }
 
                 // If there are no items, render fallback unless already rendered.
-                None if index == 0 => {
+                None if show_fallback => {
                     self.fallback_ent = Some(ViewHandle::spawn(fallback, view_entity, world));
+                    changed = true;
                 }
 
                 // Otherwise, no change.
@@ -157,6 +405,11 @@ impl<
         for entry in self.items.drain(..) {
             entry.view.lock().unwrap().raze(entry.id, world);
         }
+        if let (Some(fallback), Some(fb_ent)) = (&self.fallback, self.fallback_ent.take()) {
+            fallback.lock().unwrap().raze(fb_ent, world);
+        }
+        self.pending = 0;
+        self.progress = None;
         world.despawn_owned_recursive(view_entity);
     }
 }
@@ -175,3 +428,270 @@ where
         Arc::new(Mutex::new(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{testing::TestWorld, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Items(Vec<u32>);
+
+    fn displayed_nodes(view: &ViewRef, world: &World) -> Vec<Entity> {
+        view.lock().unwrap().nodes(world).flat_collect()
+    }
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        world.entity_mut(entity).remove::<DisplayNodeChanged>();
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    fn spawn_list(world: &mut World, len: u32) -> (ViewRef, Entity) {
+        world.insert_resource(Items((0..len).collect()));
+        let parent = world.spawn_empty().id();
+        let view = ForIndex::new(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            |item, _| item.to_string(),
+        )
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, world);
+        (view, entity)
+    }
+
+    #[test]
+    fn test_append_and_pop() {
+        let mut world = World::default();
+        let (view, entity) = spawn_list(&mut world, 100);
+        let before = displayed_nodes(&view, &world);
+        assert_eq!(before.len(), 100);
+
+        // Appending spawns a single entity.
+        world.resource_mut::<Items>().0.push(100);
+        react(&view, entity, &mut world);
+        let after = displayed_nodes(&view, &world);
+        assert_eq!(after.len(), 101);
+        assert_eq!(after[..100], before[..]);
+        assert!(world.get::<DisplayNodeChanged>(entity).is_some());
+
+        // Popping despawns a single entity.
+        world.resource_mut::<Items>().0.pop();
+        react(&view, entity, &mut world);
+        assert_eq!(displayed_nodes(&view, &world), before);
+        assert!(world.get_entity(after[100]).is_none());
+        assert!(world.get::<DisplayNodeChanged>(entity).is_some());
+
+        // No change, no notification.
+        react(&view, entity, &mut world);
+        assert_eq!(displayed_nodes(&view, &world), before);
+        assert!(world.get::<DisplayNodeChanged>(entity).is_none());
+    }
+
+    #[test]
+    fn test_middle_insert() {
+        let mut world = World::default();
+        let (view, entity) = spawn_list(&mut world, 100);
+        let before = displayed_nodes(&view, &world);
+
+        // Inserting shifts every later item, so they are re-created from that point.
+        world.resource_mut::<Items>().0.insert(50, 1000);
+        react(&view, entity, &mut world);
+        let after = displayed_nodes(&view, &world);
+        assert_eq!(after.len(), 101);
+        assert_eq!(after[..50], before[..50]);
+        assert!(after[50..].iter().all(|node| !before.contains(node)));
+        assert_eq!(
+            world.get::<Text>(after[50]).unwrap().sections[0].value,
+            "1000"
+        );
+    }
+
+    fn spawn_batched(world: &mut TestWorld, len: u32) -> Entity {
+        world.world_mut().insert_resource(Items((0..len).collect()));
+        world.spawn_view(
+            ForIndex::new(
+                |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+                |item, _| item.to_string(),
+            )
+            .with_batch_size(10),
+        )
+    }
+
+    #[test]
+    fn test_batch_size() {
+        let mut world = TestWorld::new();
+        let entity = spawn_batched(&mut world, 100);
+
+        // Each frame builds exactly one more batch, and leaves the earlier items alone.
+        let mut before = world.get_node_entities(entity);
+        assert_eq!(before.len(), 10);
+        for frame in 2..=10 {
+            world.tick();
+            let after = world.get_node_entities(entity);
+            assert_eq!(after.len(), frame * 10);
+            assert_eq!(after[..before.len()], before[..]);
+            before = after;
+        }
+
+        // Once complete, nothing more is built.
+        world.tick();
+        assert_eq!(world.get_node_entities(entity), before);
+    }
+
+    #[test]
+    fn test_batch_cancel() {
+        let mut world = TestWorld::new();
+        let entity = spawn_batched(&mut world, 100);
+        world.tick();
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 20);
+
+        // Razing the view part way through despawns every item built so far, and no further
+        // batches are built.
+        let view = world
+            .world()
+            .get::<ViewHandle>(entity)
+            .unwrap()
+            .view
+            .clone();
+        view.lock().unwrap().raze(entity, world.world_mut());
+        assert!(world.world().get_entity(entity).is_none());
+        assert!(nodes
+            .iter()
+            .all(|node| world.world().get_entity(*node).is_none()));
+        let count = world.world().entities().len();
+        world.tick();
+        assert_eq!(world.world().entities().len(), count);
+    }
+
+    #[test]
+    fn test_indexed() {
+        let mut world = World::default();
+        world.insert_resource(Items(vec![10, 20, 30]));
+        let parent = world.spawn_empty().id();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let seen = calls.clone();
+        let view = ForIndexed::new(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            move |index, item| {
+                seen.lock().unwrap().push((index, *item));
+                item.to_string()
+            },
+        );
+        let view = Arc::new(Mutex::new(view));
+        let view_ref: ViewRef = view.clone();
+        let entity = ViewHandle::spawn(&view_ref, parent, &mut world);
+        assert_eq!(*calls.lock().unwrap(), vec![(0, 10), (1, 20), (2, 30)]);
+        assert_eq!(view.lock().unwrap().item_count(), 3);
+        assert_eq!(view.lock().unwrap().item_at(1), Some(&20));
+        assert_eq!(view.lock().unwrap().item_at(3), None);
+
+        world.resource_mut::<Items>().0 = vec![10, 25];
+        react(&view_ref, entity, &mut world);
+        assert_eq!(calls.lock().unwrap()[3..], [(1, 25)]);
+        assert_eq!(view.lock().unwrap().item_count(), 2);
+        assert_eq!(view.lock().unwrap().item_at(1), Some(&25));
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct Row {
+        id: u32,
+        label: &'static str,
+    }
+
+    #[derive(Resource)]
+    struct Rows(Vec<Row>);
+
+    #[test]
+    fn test_item_key() {
+        let mut world = World::default();
+        world.insert_resource(Rows(vec![
+            Row { id: 1, label: "a" },
+            Row { id: 2, label: "b" },
+        ]));
+        let parent = world.spawn_empty().id();
+        let view = ForIndex::new(
+            |cx| cx.use_resource::<Rows>().0.clone().into_iter(),
+            |row, _| row.label,
+        )
+        .with_item_key(|row| row.id);
+        let view = Arc::new(Mutex::new(view));
+        let view_ref: ViewRef = view.clone();
+        let entity = ViewHandle::spawn(&view_ref, parent, &mut world);
+        let before = displayed_nodes(&view_ref, &world);
+
+        // Changing a row without changing its key keeps the view, but updates the item.
+        world.resource_mut::<Rows>().0[1].label = "c";
+        react(&view_ref, entity, &mut world);
+        assert_eq!(displayed_nodes(&view_ref, &world), before);
+        assert_eq!(view.lock().unwrap().item_at(1).unwrap().label, "c");
+
+        // Changing the key replaces the view.
+        world.resource_mut::<Rows>().0[1].id = 3;
+        react(&view_ref, entity, &mut world);
+        let after = displayed_nodes(&view_ref, &world);
+        assert_eq!(after[0], before[0]);
+        assert_ne!(after[1], before[1]);
+    }
+
+    #[test]
+    fn test_update_in_place() {
+        let mut world = World::default();
+        world.insert_resource(Items(vec![1, 2]));
+        let parent = world.spawn_empty().id();
+        // Even items can be updated in place, odd items are rebuilt.
+        let view = ForIndex::new(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            |item, _| item.to_string(),
+        )
+        .with_update_in_place(|item, _| (item % 2 == 0).then(|| item.to_string()));
+        let view = Arc::new(Mutex::new(view));
+        let view_ref: ViewRef = view.clone();
+        let entity = ViewHandle::spawn(&view_ref, parent, &mut world);
+        let ids = |view: &Arc<Mutex<ForIndex<_, _, _, _, _>>>| -> Vec<Entity> {
+            view.lock()
+                .unwrap()
+                .items
+                .iter()
+                .map(|item| item.id)
+                .collect()
+        };
+        let before = ids(&view);
+
+        // Updating in place keeps the item entity, but replaces its contents.
+        world.resource_mut::<Items>().0[0] = 4;
+        react(&view_ref, entity, &mut world);
+        assert_eq!(ids(&view), before);
+        let nodes = displayed_nodes(&view_ref, &world);
+        assert_eq!(world.get::<Text>(nodes[0]).unwrap().sections[0].value, "4");
+        assert!(world.get::<DisplayNodeChanged>(entity).is_some());
+
+        // If the update function declines, the item is replaced.
+        world.resource_mut::<Items>().0[1] = 3;
+        react(&view_ref, entity, &mut world);
+        let after = ids(&view);
+        assert_eq!(after[0], before[0]);
+        assert_ne!(after[1], before[1]);
+        assert!(world.get_entity(before[1]).is_none());
+        let nodes = displayed_nodes(&view_ref, &world);
+        assert_eq!(world.get::<Text>(nodes[1]).unwrap().sections[0].value, "3");
+
+        // Removing an item despawns its entity.
+        world.resource_mut::<Items>().0.pop();
+        react(&view_ref, entity, &mut world);
+        assert_eq!(ids(&view), before[..1]);
+        assert!(world.get_entity(after[1]).is_none());
+
+        // Adding an item spawns a new entity.
+        world.resource_mut::<Items>().0.push(6);
+        react(&view_ref, entity, &mut world);
+        let added = ids(&view);
+        assert_eq!(added.len(), 2);
+        assert!(!after.contains(&added[1]));
+        let nodes = displayed_nodes(&view_ref, &world);
+        assert_eq!(world.get::<Text>(nodes[1]).unwrap().sections[0].value, "6");
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+struct RangeItem {
+    view: ViewRef,
+    entity: Entity,
+}
+
+/// A view which renders a child view for each index in a reactive range. Unlike
+/// [`ForIndex`](crate::ForIndex), items are identified purely by index, so the child view for
+/// a given index is never rebuilt while the index remains in range.
+pub struct ForRange<RangeFn: Fn(&Rcx) -> Range<usize>, V: IntoView, F: Fn(usize) -> V> {
+    range_fn: RangeFn,
+    each_fn: F,
+    range: Range<usize>,
+    items: Vec<RangeItem>,
+    fallback: Option<ViewRef>,
+    fallback_ent: Option<Entity>,
+}
+
+impl<RangeFn: Fn(&Rcx) -> Range<usize>, V: IntoView, F: Fn(usize) -> V> ForRange<RangeFn, V, F> {
+    /// Construct a new `ForRange`, which calls `each_fn` to render each index in the range
+    /// returned by `range_fn`.
+    pub fn new(range_fn: RangeFn, each_fn: F) -> Self {
+        Self {
+            range_fn,
+            each_fn,
+            range: 0..0,
+            items: Vec::new(),
+            fallback: None,
+            fallback_ent: None,
+        }
+    }
+
+    /// Allow specifying a fallback view to render if the range is empty.
+    pub fn with_fallback<FB: IntoView>(mut self, fallback: FB) -> Self {
+        self.fallback = Some(fallback.into_view());
+        self
+    }
+}
+
+impl<RangeFn: Fn(&Rcx) -> Range<usize>, V: IntoView, F: Fn(usize) -> V> View
+    for ForRange<RangeFn, V, F>
+{
+    fn nodes(&self, world: &World) -> NodeSpan {
+        if let (Some(fallback), Some(entity)) = (&self.fallback, self.fallback_ent) {
+            return child_nodes(fallback, entity, world);
+        }
+        let child_spans: Vec<NodeSpan> = self
+            .items
+            .iter()
+            .map(|item| child_nodes(&item.view, item.entity, world))
+            .collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let range = (self.range_fn)(&Rcx::new(world, tracking));
+        let prev_range = std::mem::replace(&mut self.range, range.clone());
+        let mut prev: Vec<Option<RangeItem>> = self.items.drain(..).map(Some).collect();
+        let mut changed = false;
+
+        // Keep the items whose index is still in range, and spawn the rest.
+        for index in range.clone() {
+            let existing = if prev_range.contains(&index) {
+                prev[index - prev_range.start].take()
+            } else {
+                None
+            };
+            let item = existing.unwrap_or_else(|| {
+                changed = true;
+                let view = (self.each_fn)(index).into_view();
+                let entity = ViewHandle::spawn(&view, view_entity, world);
+                RangeItem { view, entity }
+            });
+            self.items.push(item);
+        }
+
+        // Raze items which are no longer in range.
+        for item in prev.into_iter().flatten() {
+            item.view.lock().unwrap().raze(item.entity, world);
+            changed = true;
+        }
+
+        // Handle fallback
+        if let Some(ref fallback) = self.fallback {
+            match self.fallback_ent {
+                Some(fb_ent) if !range.is_empty() => {
+                    fallback.lock().unwrap().raze(fb_ent, world);
+                    self.fallback_ent = None;
+                    changed = true;
+                }
+                None if range.is_empty() => {
+                    self.fallback_ent = Some(ViewHandle::spawn(fallback, view_entity, world));
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if changed {
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        for item in self.items.drain(..) {
+            item.view.lock().unwrap().raze(item.entity, world);
+        }
+        if let (Some(fallback), Some(fb_ent)) = (&self.fallback, self.fallback_ent.take()) {
+            fallback.lock().unwrap().raze(fb_ent, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<
+        RangeFn: Send + Sync + 'static + Fn(&Rcx) -> Range<usize>,
+        V: 'static + IntoView,
+        F: Send + Sync + 'static + Fn(usize) -> V,
+    > IntoView for ForRange<RangeFn, V, F>
+{
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+type CountRangeFn = Box<dyn Fn(&Rcx) -> Range<usize> + Send + Sync>;
+
+/// A view which renders `count` copies of a view produced by a factory function, where
+/// `count` is computed reactively. When the count increases, new items are appended; when it
+/// decreases, surplus items are razed from the end.
+pub struct RepeatView<V: IntoView, F: Fn(usize) -> V> {
+    inner: ForRange<CountRangeFn, V, F>,
+}
+
+impl<V: IntoView, F: Fn(usize) -> V> RepeatView<V, F> {
+    /// Construct a new `RepeatView`, which calls `factory` with the index of each item.
+    pub fn new(count_fn: impl Fn(&Rcx) -> usize + Send + Sync + 'static, factory: F) -> Self {
+        Self {
+            inner: ForRange::new(Box::new(move |cx: &Rcx| 0..count_fn(cx)), factory),
+        }
+    }
+
+    /// Allow specifying a fallback view to render if the count is zero.
+    pub fn with_fallback<FB: IntoView>(mut self, fallback: FB) -> Self {
+        self.inner = self.inner.with_fallback(fallback);
+        self
+    }
+}
+
+impl<V: IntoView, F: Fn(usize) -> V> View for RepeatView<V, F> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        self.inner.nodes(world)
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        self.inner.build(view_entity, world);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        self.inner.react(view_entity, world, tracking);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        self.inner.raze(view_entity, world);
+    }
+}
+
+impl<V: 'static + IntoView, F: Send + Sync + 'static + Fn(usize) -> V> IntoView
+    for RepeatView<V, F>
+{
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReactiveContext;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Pips(Range<usize>);
+
+    fn displayed_nodes(view: &ViewRef, world: &World) -> Vec<Entity> {
+        view.lock().unwrap().nodes(world).flat_collect()
+    }
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    #[test]
+    fn test_for_range() {
+        let mut world = World::default();
+        world.insert_resource(Pips(0..0));
+        let parent = world.spawn_empty().id();
+        let view = ForRange::new(|cx| cx.use_resource::<Pips>().0.clone(), |i| i.to_string())
+            .with_fallback("empty")
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+
+        // Empty range shows the fallback.
+        let nodes = displayed_nodes(&view, &world);
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(
+            world.get::<Text>(nodes[0]).unwrap().sections[0].value,
+            "empty"
+        );
+
+        world.resource_mut::<Pips>().0 = 0..3;
+        react(&view, entity, &mut world);
+        let three = displayed_nodes(&view, &world);
+        assert_eq!(three.len(), 3);
+
+        // Growing only spawns the new items.
+        world.resource_mut::<Pips>().0 = 0..5;
+        react(&view, entity, &mut world);
+        let five = displayed_nodes(&view, &world);
+        assert_eq!(five.len(), 5);
+        assert_eq!(five[..3], three[..]);
+        assert_eq!(world.get::<Text>(five[4]).unwrap().sections[0].value, "4");
+
+        // Shrinking leaves the remaining items untouched.
+        world.resource_mut::<Pips>().0 = 0..2;
+        react(&view, entity, &mut world);
+        let two = displayed_nodes(&view, &world);
+        assert_eq!(two[..], five[..2]);
+        assert!(world.get_entity(five[2]).is_none());
+        assert!(world.get_entity(five[4]).is_none());
+    }
+
+    #[derive(Resource)]
+    struct Stars(usize);
+
+    #[test]
+    fn test_repeat_view() {
+        let mut world = World::default();
+        world.insert_resource(Stars(0));
+        let parent = world.spawn_empty().id();
+        let view = RepeatView::new(|cx| cx.use_resource::<Stars>().0, |_| "*")
+            .with_fallback("no stars")
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let fallback = displayed_nodes(&view, &world);
+        assert_eq!(fallback.len(), 1);
+
+        world.resource_mut::<Stars>().0 = 3;
+        react(&view, entity, &mut world);
+        let three = displayed_nodes(&view, &world);
+        assert_eq!(three.len(), 3);
+        assert!(world.get_entity(fallback[0]).is_none());
+
+        world.resource_mut::<Stars>().0 = 1;
+        react(&view, entity, &mut world);
+        let one = displayed_nodes(&view, &world);
+        assert_eq!(one[..], three[..1]);
+        assert!(world.get_entity(three[1]).is_none());
+        assert!(world.get_entity(three[2]).is_none());
+
+        // Same count: no change.
+        react(&view, entity, &mut world);
+        assert_eq!(displayed_nodes(&view, &world), one);
+
+        world.resource_mut::<Stars>().0 = 0;
+        react(&view, entity, &mut world);
+        let nodes = displayed_nodes(&view, &world);
+        assert!(world.get_entity(one[0]).is_none());
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(
+            world.get::<Text>(nodes[0]).unwrap().sections[0].value,
+            "no stars"
+        );
+    }
+}
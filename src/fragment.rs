@@ -3,8 +3,10 @@ use std::sync::{Arc, Mutex};
 use bevy::prelude::*;
 
 use crate::{
-    node_span::NodeSpan, view::View, view_tuple::ViewTuple, DespawnScopes, IntoView, ViewHandle,
-    ViewRef,
+    node_span::NodeSpan,
+    view::{child_nodes, View},
+    view_tuple::ViewTuple,
+    DespawnScopes, IntoView, ViewHandle, ViewRef,
 };
 
 struct FragmentChild {
@@ -24,24 +26,26 @@ impl Fragment {
     pub fn new<V: ViewTuple>(views: V) -> Self {
         let mut child_views: Vec<ViewRef> = Vec::new();
         views.get_handles(&mut child_views);
+        Self::from_refs(child_views)
+    }
+
+    /// Construct a new `Fragment` from a list of view references.
+    pub fn from_refs(views: Vec<ViewRef>) -> Self {
         Self {
-            children: child_views
-                .iter()
-                .map(|v| FragmentChild {
-                    view: v.clone(),
-                    entity: None,
-                })
+            children: views
+                .into_iter()
+                .map(|view| FragmentChild { view, entity: None })
                 .collect(),
         }
     }
 }
 
 impl View for Fragment {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, world: &World) -> NodeSpan {
         let child_spans: Vec<NodeSpan> = self
             .children
             .iter()
-            .map(|item| item.view.lock().unwrap().nodes())
+            .map(|item| child_nodes(&item.view, item.entity.unwrap(), world))
             .collect();
         NodeSpan::Fragment(child_spans.into_boxed_slice())
     }
@@ -54,10 +58,12 @@ impl View for Fragment {
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
-        // Raze all child views
-        for child in self.children.drain(..) {
+        // Raze all child views. The children are kept, so that the fragment can be built
+        // again, as when it is passed to a presenter which re-renders.
+        for child in self.children.iter_mut() {
             let inner = child.view.clone();
-            inner.lock().unwrap().raze(child.entity.unwrap(), world);
+            let entity = child.entity.take().unwrap();
+            inner.lock().unwrap().raze(entity, world);
             // Child raze() will despawn itself.
         }
 
@@ -70,3 +76,9 @@ impl IntoView for Fragment {
         Arc::new(Mutex::new(self))
     }
 }
+
+impl<V: IntoView> IntoView for Vec<V> {
+    fn into_view(self) -> ViewRef {
+        Fragment::from_refs(self.into_iter().map(|v| v.into_view()).collect()).into_view()
+    }
+}
@@ -0,0 +1,597 @@
+use std::{
+    any::{Any, TypeId},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use bevy::{asset::UntypedHandle, prelude::*, utils::HashMap};
+
+use crate::{reducer::Dispatch, TrackingScope};
+
+/// Component which stores per-presenter state for hooks such as
+/// [`Cx::use_previous`](crate::Cx::use_previous). Slots are identified by the order in which
+/// the hooks are called, so hooks must be called in the same order each time the presenter
+/// runs. The slots are dropped along with the view entity when the presenter is razed.
+#[derive(Component, Default)]
+pub(crate) struct HookSlots(pub(crate) Vec<Option<Box<dyn Any + Send + Sync>>>);
+
+impl HookSlots {
+    /// Returns a copy of the value in the given slot, if it is present and of type `T`.
+    pub(crate) fn get<T: Clone + 'static>(&self, index: usize) -> Option<T> {
+        self.get_ref(index).cloned()
+    }
+
+    /// Returns a reference to the value in the given slot, if it is present and of type `T`.
+    pub(crate) fn get_ref<T: 'static>(&self, index: usize) -> Option<&T> {
+        self.0.get(index)?.as_ref()?.downcast_ref::<T>()
+    }
+
+    /// Empty the given slot, dropping its value.
+    pub(crate) fn clear(&mut self, index: usize) {
+        if let Some(slot) = self.0.get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    /// Replace the value in the given slot, returning the previous value.
+    pub(crate) fn replace<T: Send + Sync + 'static>(
+        &mut self,
+        index: usize,
+        value: T,
+    ) -> Option<T> {
+        if self.0.len() <= index {
+            self.0.resize_with(index + 1, || None);
+        }
+        self.0[index]
+            .replace(Box::new(value))
+            .and_then(|prev| prev.downcast::<T>().ok())
+            .map(|prev| *prev)
+    }
+}
+
+/// Component which stores a presenter-local singleton, created by
+/// [`Cx::use_local_resource`](crate::Cx::use_local_resource).
+#[derive(Component, Default)]
+pub(crate) struct LocalResource<T: Send + Sync + 'static>(pub(crate) T);
+
+/// Hook slot which holds a mutable created by
+/// [`Cx::create_stable_mutable`](crate::Cx::create_stable_mutable), along with its key. The
+/// value type is part of the slot type, so a slot is never read as a mutable of another type.
+pub(crate) struct StableMutableSlot<T> {
+    pub(crate) key: &'static str,
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for StableMutableSlot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            id: self.id,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Hook slot which holds the state created by [`Cx::use_reducer`](crate::Cx::use_reducer),
+/// along with its dispatch handle. As with [`StableMutableSlot`], the state type is part of
+/// the slot type.
+pub(crate) struct ReducerSlot<S, A> {
+    pub(crate) id: Entity,
+    pub(crate) dispatch: Dispatch<A>,
+    pub(crate) marker: PhantomData<fn() -> S>,
+}
+
+impl<S, A> Clone for ReducerSlot<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            dispatch: self.dispatch.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Hook slot which holds a value computed by [`Cx::use_computed`](crate::Cx::use_computed),
+/// along with the key of its call site and the dependencies of the computation.
+#[derive(Clone)]
+pub(crate) struct ComputedSlot<T> {
+    pub(crate) key: usize,
+    pub(crate) value: T,
+    pub(crate) deps: Arc<TrackingScope>,
+}
+
+/// Component which holds the asset handles loaded by
+/// [`Cx::load_asset`](crate::Cx::load_asset), keyed by asset type and path. Each handle is
+/// stored along with the id of the tracking scope in which it was last requested. Holding the
+/// handles keeps the assets loaded until the presenter is razed, or until a run of the
+/// presenter no longer requests them.
+#[derive(Component, Default)]
+pub(crate) struct LoadedAssets(pub(crate) HashMap<(TypeId, String), (u64, UntypedHandle)>);
+
+/// Drop the asset handles of the presenter on `entity` which were not requested during the
+/// run with the given scope id.
+pub(crate) fn drop_unused_assets(world: &mut World, entity: Entity, pass: u64) {
+    if let Some(mut loaded) = world.get_mut::<LoadedAssets>(entity) {
+        if loaded.0.values().any(|(id, _)| *id != pass) {
+            loaded.0.retain(|_, (id, _)| *id == pass);
+        }
+    }
+}
+
+/// Returns the key used by [`use_computed!`](crate::use_computed) for a call site.
+#[doc(hidden)]
+pub fn call_site_key(file: &str, line: u32, column: u32) -> usize {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (file, line, column).hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+/// Calls [`Cx::use_computed`](crate::Cx::use_computed) with a key derived from the file, line
+/// and column of the macro invocation, so that each call site has its own memoized value.
+/// As with `use_computed`, the function is only called again once its dependencies change.
+///
+/// ```ignore
+/// let total = use_computed!(cx, |re| re.use_resource::<Inventory>().total());
+/// ```
+#[macro_export]
+macro_rules! use_computed {
+    ($cx:expr, $f:expr) => {
+        $cx.use_computed($crate::call_site_key(file!(), line!(), column!()), $f)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::{
+        cx::Cx, testing::TestWorld, DespawnScopes, Rcx, ReactiveContext, ReactiveContextMut,
+        TrackingScope,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_use_previous() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // Each presenter invocation sees the value from the previous invocation.
+        for (current, expected) in [(1, None), (2, Some(1)), (3, Some(2))] {
+            let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+            assert_eq!(cx.use_previous(current), expected);
+            assert_eq!(cx.use_previous(current * 10), expected.map(|e| e * 10));
+        }
+
+        // Razing the presenter drops the stored values.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    fn spawn_children(
+        world: &mut World,
+        entity: Entity,
+        scope: &mut TrackingScope,
+        drop_second: bool,
+    ) -> (Entity, Entity) {
+        let mut cx = Cx::new(&(), world, entity, scope);
+        let first = cx.spawn_child(Name::new("first"));
+        let second = cx.spawn_child(Name::new("second"));
+        if drop_second {
+            cx.despawn_child(second);
+        }
+        (first, second)
+    }
+
+    #[test]
+    fn test_spawn_child() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // Children are only spawned the first time.
+        let (a, b) = spawn_children(&mut world, entity, &mut scope, false);
+        assert_eq!(world.get::<Parent>(a).unwrap().get(), entity);
+        assert_eq!(
+            spawn_children(&mut world, entity, &mut scope, false),
+            (a, b)
+        );
+        assert_eq!(world.get::<Children>(entity).unwrap().len(), 2);
+
+        // Despawning a child.
+        spawn_children(&mut world, entity, &mut scope, true);
+        assert_eq!(&world.get::<Children>(entity).unwrap()[..], &[a]);
+
+        // The despawned child is not spawned again on the next run.
+        assert_eq!(
+            spawn_children(&mut world, entity, &mut scope, false),
+            (a, b)
+        );
+        assert!(world.get_entity(b).is_none());
+        assert_eq!(&world.get::<Children>(entity).unwrap()[..], &[a]);
+
+        // Razing the presenter despawns the remaining children.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(b).is_none());
+    }
+
+    #[derive(Default)]
+    struct Cache(Vec<u32>);
+
+    #[test]
+    fn test_use_local_resource() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        // The initial value is the default.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert!(cx.use_local_resource::<Cache>().0.is_empty());
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Mutations persist across reactions, and cause the presenter to react.
+        let mut next = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut next);
+        cx.use_local_resource_mut::<Cache>().0.push(1);
+        assert!(scope.dependencies_changed(&world));
+        scope.take_deps(&mut next);
+        world.increment_change_tick();
+        let mut next = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut next);
+        assert_eq!(cx.use_local_resource::<Cache>().0, vec![1]);
+
+        // Razing the presenter drops the local resource.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[derive(Resource)]
+    struct Price(u32);
+
+    #[derive(Resource)]
+    struct Tax(u32);
+
+    static DOUBLE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(crate::Presenter)]
+    struct Total;
+
+    impl crate::Presenter for Total {
+        fn present(cx: &mut Cx<Self>) -> impl crate::IntoView {
+            let double = crate::use_computed!(cx, |re| {
+                DOUBLE_CALLS.fetch_add(1, Ordering::Relaxed);
+                re.use_resource::<Price>().0 * 2
+            });
+            let label = crate::use_computed!(cx, |_| "total");
+            let tax = cx.use_resource::<Tax>().0;
+            format!("{} {}", label, double + tax)
+        }
+    }
+
+    #[test]
+    fn test_use_computed() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Price(2));
+        world.world_mut().insert_resource(Tax(1));
+        world.tick();
+        let view = world.spawn_view(Total);
+        world.tick();
+        let text = |world: &TestWorld| {
+            let node = world.get_node_entities(view)[0];
+            world.world().get::<Text>(node).unwrap().sections[0]
+                .value
+                .clone()
+        };
+        assert_eq!(text(&world), "total 5");
+        assert_eq!(DOUBLE_CALLS.load(Ordering::Relaxed), 1);
+
+        // The presenter runs again, but the computation's dependencies are unchanged.
+        world.world_mut().resource_mut::<Tax>().0 = 2;
+        world.tick();
+        assert_eq!(text(&world), "total 6");
+        assert_eq!(DOUBLE_CALLS.load(Ordering::Relaxed), 1);
+
+        // A change to the computation's dependencies re-runs both it and the presenter.
+        world.world_mut().resource_mut::<Price>().0 = 3;
+        world.tick();
+        assert_eq!(text(&world), "total 8");
+        assert_eq!(DOUBLE_CALLS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_call_site_key() {
+        let key = call_site_key("src/a.rs", 10, 5);
+        assert_eq!(key, call_site_key("src/a.rs", 10, 5));
+        assert_ne!(key, call_site_key("src/b.rs", 10, 5));
+        assert_ne!(key, call_site_key("src/a.rs", 11, 5));
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Level;
+
+    /// Returns the paths whose handles are held by the presenter on `entity`. The asset
+    /// server also holds handles while loading, so the handles' reference counts are not used.
+    fn loaded_paths(world: &World, entity: Entity) -> Vec<String> {
+        let mut paths: Vec<String> = world
+            .get::<LoadedAssets>(entity)
+            .map(|loaded| loaded.0.keys().map(|(_, path)| path.clone()).collect())
+            .unwrap_or_default();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_load_asset() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Level>();
+        let world = &mut app.world;
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // Loading the same path again returns the same handle.
+        let mut cx = Cx::new(&(), world, entity, &mut scope);
+        let first = cx.load_asset::<Level>("first.level");
+        assert_eq!(cx.load_asset::<Level>("first.level"), first);
+        assert_ne!(cx.load_asset::<Level>("second.level"), first);
+        assert_eq!(loaded_paths(world, entity), ["first.level", "second.level"]);
+
+        // A later run which no longer requests a path drops its handle.
+        let mut next = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, entity, &mut next);
+        assert_eq!(cx.load_asset::<Level>("first.level"), first);
+        drop_unused_assets(world, entity, next.id());
+        assert_eq!(loaded_paths(world, entity), ["first.level"]);
+
+        // Razing the presenter drops the stored handles.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(loaded_paths(world, entity).is_empty());
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Score(u32);
+
+    #[test]
+    fn test_use_asset() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Score>();
+        let world = &mut app.world;
+        let handle = world
+            .resource::<Assets<Score>>()
+            .get_handle_provider()
+            .reserve_handle()
+            .typed::<Score>();
+        let read = |world: &World, handle: &Handle<Score>| {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let re = Rcx::new(world, &mut scope);
+            let value = re.use_asset(handle).map(|score| score.0);
+            let state = re.use_asset_load_state(handle);
+            (value, state, scope)
+        };
+
+        // The asset has not been loaded yet.
+        let (value, state, scope) = read(world, &handle);
+        assert_eq!(value, None);
+        assert_eq!(state, bevy::asset::LoadState::NotLoaded);
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(world));
+
+        // The asset finishing loading is a change.
+        world
+            .resource_mut::<Assets<Score>>()
+            .insert(&handle, Score(1));
+        assert!(scope.dependencies_changed(world));
+        let (value, _, scope) = read(world, &handle);
+        assert_eq!(value, Some(1));
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(world));
+
+        // Modifying the asset, as a hot reload does, is a change.
+        world
+            .resource_mut::<Assets<Score>>()
+            .get_mut(&handle)
+            .unwrap()
+            .0 = 2;
+        assert!(scope.dependencies_changed(world));
+        let (value, _, _) = read(world, &handle);
+        assert_eq!(value, Some(2));
+
+        // Reading a different handle returns the other asset.
+        let other = world.resource_mut::<Assets<Score>>().add(Score(3));
+        let (value, _, _) = read(world, &other);
+        assert_eq!(value, Some(3));
+    }
+
+    #[test]
+    fn test_use_asset_failed() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Score>();
+        // There is no loader for scores, so the load fails.
+        let handle: Handle<Score> = app.world.resource::<AssetServer>().load("missing.score");
+        let mut scope = TrackingScope::new(app.world.change_tick());
+        let state = Rcx::new(&app.world, &mut scope).use_asset_load_state(&handle);
+        assert_ne!(state, bevy::asset::LoadState::Failed);
+
+        // The failure is reported by a load task, and processed by a later update.
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while app.world.resource::<AssetServer>().load_state(&handle)
+            != bevy::asset::LoadState::Failed
+        {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "Timed out waiting for the asset load to fail"
+            );
+            std::thread::yield_now();
+            app.update();
+        }
+
+        // The failure is a change, even though `Assets<Score>` is untouched.
+        app.world.clear_trackers();
+        assert!(scope.dependencies_changed(&app.world));
+        let state = Rcx::new(&app.world, &mut scope).use_asset_load_state(&handle);
+        assert_eq!(state, bevy::asset::LoadState::Failed);
+    }
+
+    #[test]
+    fn test_use_hierarchy() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        // A detached entity has no parent or ancestors.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert_eq!(cx.use_parent(), None);
+        assert!(cx.use_ancestors().is_empty());
+        assert_eq!(cx.use_root(), entity);
+        world.increment_change_tick();
+
+        // Attaching the entity is a change.
+        let root = world.spawn_empty().id();
+        let parent = world.spawn_empty().id();
+        world.entity_mut(root).add_child(parent);
+        world.entity_mut(parent).add_child(entity);
+        assert!(scope.dependencies_changed(&world));
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert_eq!(cx.use_parent(), Some(parent));
+        assert_eq!(cx.use_ancestors(), vec![parent, root]);
+        assert_eq!(cx.use_root(), root);
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Re-parenting an ancestor is a change.
+        let other = world.spawn_empty().id();
+        world.entity_mut(other).add_child(parent);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_use_input() {
+        let mut world = World::default();
+        world.init_resource::<Input<KeyCode>>();
+        let read = |world: &World| {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let re = Rcx::new(world, &mut scope);
+            (
+                re.use_input(KeyCode::Space),
+                re.use_input_just_pressed(KeyCode::Space),
+                re.use_input_just_released(KeyCode::Space),
+            )
+        };
+        assert_eq!(read(&world), (false, false, false));
+
+        // Pressing is an edge only on the first frame.
+        world.resource_mut::<Input<KeyCode>>().press(KeyCode::Space);
+        assert_eq!(read(&world), (true, true, false));
+        world.resource_mut::<Input<KeyCode>>().clear();
+        assert_eq!(read(&world), (true, false, false));
+
+        // Releasing is also an edge.
+        world
+            .resource_mut::<Input<KeyCode>>()
+            .release(KeyCode::Space);
+        assert_eq!(read(&world), (false, false, true));
+        world.resource_mut::<Input<KeyCode>>().clear();
+        assert_eq!(read(&world), (false, false, false));
+    }
+
+    #[test]
+    fn test_use_input_deps() {
+        let mut world = World::default();
+        world.init_resource::<Input<KeyCode>>();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let re = Rcx::new(&world, &mut scope);
+        assert!(!re.use_input(KeyCode::Space));
+        assert!(!re.use_input_just_pressed(KeyCode::Space));
+        assert_eq!(scope.dependency_count(), 1);
+
+        // Pressing a key is a change to the dependency.
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(&world));
+        world.resource_mut::<Input<KeyCode>>().press(KeyCode::Space);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_use_input_missing() {
+        // Without the keyboard input resource, as with `MinimalPlugins`, no keys are held.
+        let world = World::default();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let re = Rcx::new(&world, &mut scope);
+        assert!(!re.use_input(KeyCode::Space));
+        assert!(!re.use_input_just_pressed(KeyCode::Space));
+        assert!(!re.use_input_just_released(KeyCode::Space));
+        assert_eq!(scope.dependency_count(), 0);
+    }
+
+    #[derive(Resource, Default)]
+    struct Clicks(u32);
+
+    #[derive(Resource, Default)]
+    struct WriterRuns(u32);
+
+    #[derive(crate::Presenter)]
+    struct PriceWriter;
+
+    impl crate::Presenter for PriceWriter {
+        fn present(cx: &mut Cx<Self>) -> impl crate::IntoView {
+            let clicks = cx.use_resource::<Clicks>().0;
+            cx.use_resource_mut::<WriterRuns>().0 += 1;
+            if clicks > 0 {
+                cx.use_resource_mut::<Price>().0 = clicks * 10;
+            }
+            if clicks > 1 {
+                cx.write_resource(Price(clicks * 100));
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_resource_mut() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Price(1));
+        world.world_mut().init_resource::<Clicks>();
+        world.world_mut().init_resource::<WriterRuns>();
+        // Inserting the resources is not a change which the presenters should react to.
+        world.tick();
+        let reader = world.spawn_view(crate::TextComputed::new(|re| {
+            format!("{}", re.use_resource::<Price>().0)
+        }));
+        world.spawn_view(PriceWriter);
+        world.tick();
+        let text = |world: &TestWorld| {
+            let node = world.get_node_entities(reader)[0];
+            world.world().get::<Text>(node).unwrap().sections[0]
+                .value
+                .clone()
+        };
+        assert_eq!(text(&world), "1");
+        assert_eq!(world.world().resource::<WriterRuns>().0, 1);
+
+        // Writing the resource re-renders the reader, but not the writer.
+        world.world_mut().resource_mut::<Clicks>().0 = 1;
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world), "10");
+        assert_eq!(world.world().resource::<WriterRuns>().0, 2);
+
+        // Replacing the resource also re-renders the reader.
+        world.world_mut().resource_mut::<Clicks>().0 = 2;
+        world.tick();
+        world.tick();
+        assert_eq!(text(&world), "200");
+        assert_eq!(world.world().resource::<WriterRuns>().0, 3);
+    }
+}
@@ -0,0 +1,235 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, scope::TrackingScope, view::View, DespawnScopes, IntoView, Rcx, ViewRef,
+};
+
+type ColorFn = Box<dyn Fn(&Rcx) -> Color + Send + Sync>;
+
+/// A UI element that displays an image.
+pub struct ImageStatic {
+    /// The visible UI node for this element.
+    node: Option<Entity>,
+
+    /// The image to display
+    image: Handle<Image>,
+}
+
+impl ImageStatic {
+    /// Construct a new static image view.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self { node: None, image }
+    }
+}
+
+impl View for ImageStatic {
+    fn nodes(&self, _world: &World) -> NodeSpan {
+        NodeSpan::Node(self.node.unwrap())
+    }
+
+    fn build(&mut self, _view_entity: Entity, world: &mut World) {
+        assert!(self.node.is_none());
+        self.node = Some(
+            world
+                .spawn(ImageBundle {
+                    image: UiImage::new(self.image.clone()),
+                    ..default()
+                })
+                .id(),
+        );
+    }
+
+    fn raze(&mut self, _view_entity: Entity, world: &mut World) {
+        let display = self.node.take().expect("Razing unbuilt ImageStatic");
+        world.entity_mut(display).remove_parent();
+        world.entity_mut(display).despawn();
+    }
+}
+
+impl IntoView for ImageStatic {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// Creates a static image view.
+pub fn image_static(image: Handle<Image>) -> ImageStatic {
+    ImageStatic::new(image)
+}
+
+/// A UI element that displays an image which is dynamically computed, optionally with a
+/// computed tint color.
+pub struct ImageView<F: Fn(&Rcx) -> Handle<Image>> {
+    /// The visible UI node for this element.
+    node: Option<Entity>,
+
+    /// Computes the image to display.
+    image: F,
+
+    /// Computes the tint color. The tint is applied via the node's [`BackgroundColor`].
+    color: Option<ColorFn>,
+
+    /// Whether to flip the image horizontally and vertically.
+    flip: (bool, bool),
+}
+
+impl<F: Fn(&Rcx) -> Handle<Image>> ImageView<F> {
+    /// Construct a new computed image view.
+    pub fn new(image: F) -> Self {
+        Self {
+            node: None,
+            image,
+            color: None,
+            flip: (false, false),
+        }
+    }
+
+    /// Tint the image with a computed color.
+    pub fn with_color(mut self, color: impl Fn(&Rcx) -> Color + Send + Sync + 'static) -> Self {
+        self.color = Some(Box::new(color));
+        self
+    }
+
+    /// Flip the image horizontally and/or vertically.
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip = (flip_x, flip_y);
+        self
+    }
+}
+
+impl<F: Fn(&Rcx) -> Handle<Image>> View for ImageView<F> {
+    fn nodes(&self, _world: &World) -> NodeSpan {
+        NodeSpan::Node(self.node.unwrap())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.node.is_none());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let re = Rcx::new(world, &mut tracking);
+        let image = (self.image)(&re);
+        let color = self.color.as_ref().map_or(Color::WHITE, |color| color(&re));
+        let node = world
+            .spawn(ImageBundle {
+                image: UiImage {
+                    texture: image,
+                    flip_x: self.flip.0,
+                    flip_y: self.flip.1,
+                },
+                background_color: color.into(),
+                ..default()
+            })
+            .id();
+        self.node = Some(node);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let image = (self.image)(&re);
+        let color = self.color.as_ref().map(|color| color(&re));
+        let mut entt = world.entity_mut(self.node.unwrap());
+        // Only write when the value differs, so that change detection doesn't fire needlessly.
+        let mut ui_image = entt.get_mut::<UiImage>().unwrap();
+        if ui_image.texture != image {
+            ui_image.texture = image;
+        }
+        if let Some(color) = color {
+            let mut background = entt.get_mut::<BackgroundColor>().unwrap();
+            if background.0 != color {
+                background.0 = color;
+            }
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let display = self.node.take().expect("Razing unbuilt ImageView");
+        world.entity_mut(display).remove_parent();
+        world.entity_mut(display).despawn();
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<F: Send + Sync + 'static + Fn(&Rcx) -> Handle<Image>> IntoView for ImageView<F> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReactiveContext, ViewHandle};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Icon(Handle<Image>, Color);
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    #[test]
+    fn test_image_view() {
+        let mut world = World::default();
+        let first = Handle::weak_from_u128(1);
+        let second = Handle::weak_from_u128(2);
+        world.insert_resource(Icon(first.clone(), Color::WHITE));
+        let parent = world.spawn_empty().id();
+        let view = ImageView::new(|cx| cx.use_resource::<Icon>().0.clone())
+            .with_color(|cx| cx.use_resource::<Icon>().1)
+            .with_flip(true, false)
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let node = view.lock().unwrap().nodes(&world).flat_collect()[0];
+        let image = world.get::<UiImage>(node).unwrap();
+        assert_eq!(image.texture, first);
+        assert!(image.flip_x);
+        assert!(!image.flip_y);
+
+        // Unchanged values don't trigger change detection.
+        let last = world.change_tick();
+        world.increment_change_tick();
+        react(&view, entity, &mut world);
+        let this = world.change_tick();
+        let entt = world.entity(node);
+        assert!(!entt
+            .get_ref::<UiImage>()
+            .unwrap()
+            .last_changed()
+            .is_newer_than(last, this));
+        assert!(!entt
+            .get_ref::<BackgroundColor>()
+            .unwrap()
+            .last_changed()
+            .is_newer_than(last, this));
+
+        // Changed handle and color are written to the node.
+        world.resource_mut::<Icon>().0 = second.clone();
+        world.resource_mut::<Icon>().1 = Color::RED;
+        react(&view, entity, &mut world);
+        assert_eq!(world.get::<UiImage>(node).unwrap().texture, second);
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, Color::RED);
+
+        // Razing despawns the node.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(node).is_none());
+    }
+
+    #[test]
+    fn test_image_static() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = image_static(Handle::weak_from_u128(3)).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let node = view.lock().unwrap().nodes(&world).flat_collect()[0];
+        assert_eq!(
+            world.get::<UiImage>(node).unwrap().texture,
+            Handle::weak_from_u128(3)
+        );
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(node).is_none());
+    }
+}
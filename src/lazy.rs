@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+type FactoryFn = Box<dyn FnOnce() -> ViewRef + Send + Sync>;
+
+/// A view which defers constructing its child until it first becomes visible. Once built, the
+/// child is kept even if it stops being visible; use [`Cond`](crate::Cond) if the child
+/// should be razed when hidden.
+///
+/// The factory is called at most once. If it panics, the lazy view remains empty.
+pub struct LazyView<VisFn: Fn(&Rcx) -> bool> {
+    is_visible: VisFn,
+    factory: Option<FactoryFn>,
+    state: Option<(ViewRef, Entity)>,
+}
+
+impl<VisFn: Fn(&Rcx) -> bool> LazyView<VisFn> {
+    /// Construct a new `LazyView`. The child is constructed by `factory` the first time that
+    /// `is_visible` returns true.
+    pub fn new<V: IntoView, F: FnOnce() -> V + Send + Sync + 'static>(
+        is_visible: VisFn,
+        factory: F,
+    ) -> Self {
+        Self {
+            is_visible,
+            factory: Some(Box::new(move || factory().into_view())),
+            state: None,
+        }
+    }
+}
+
+impl<VisFn: Fn(&Rcx) -> bool> View for LazyView<VisFn> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.state {
+            Some((ref view, entity)) => child_nodes(view, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        // Once the child has been built there is nothing left to do, so we stop tracking
+        // the visibility.
+        if self.state.is_some() || !(self.is_visible)(&Rcx::new(world, tracking)) {
+            return;
+        }
+        if let Some(factory) = self.factory.take() {
+            let view = factory();
+            let entity = ViewHandle::spawn(&view, view_entity, world);
+            self.state = Some((view, entity));
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<VisFn: Send + Sync + 'static + Fn(&Rcx) -> bool> IntoView for LazyView<VisFn> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::ReactiveContext;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Visible(bool);
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    #[test]
+    fn test_lazy_view() {
+        let mut world = World::default();
+        world.insert_resource(Visible(false));
+        let parent = world.spawn_empty().id();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let factory_calls = calls.clone();
+        let view = LazyView::new(
+            |cx| cx.use_resource::<Visible>().0,
+            move || {
+                factory_calls.fetch_add(1, Ordering::Relaxed);
+                "content"
+            },
+        )
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+
+        // Nothing is spawned while hidden.
+        let count = world.entities().len();
+        react(&view, entity, &mut world);
+        assert_eq!(world.entities().len(), count);
+        assert_eq!(view.lock().unwrap().nodes(&world), NodeSpan::Empty);
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        // Becoming visible spawns the child.
+        world.resource_mut::<Visible>().0 = true;
+        react(&view, entity, &mut world);
+        assert!(matches!(
+            view.lock().unwrap().nodes(&world),
+            NodeSpan::Node(_)
+        ));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // The child is kept, and not rebuilt.
+        let count = world.entities().len();
+        world.resource_mut::<Visible>().0 = false;
+        react(&view, entity, &mut world);
+        world.resource_mut::<Visible>().0 = true;
+        react(&view, entity, &mut world);
+        assert_eq!(world.entities().len(), count);
+        assert!(matches!(
+            view.lock().unwrap().nodes(&world),
+            NodeSpan::Node(_)
+        ));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
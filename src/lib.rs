@@ -2,42 +2,163 @@
 
 #![warn(missing_docs)]
 
+// Allows the derive macros to refer to this crate by name from within the crate.
+extern crate self as bevy_reactor;
+
 mod accessor;
+mod animated;
 mod bundle;
+mod callback;
+mod children;
 mod cond;
+mod context;
 mod cx;
+mod diagnostics;
+mod dynamic;
 mod element;
+mod error_boundary;
+mod event_reader;
+mod event_writer;
 mod r#for;
 mod for_each;
 mod for_index;
+mod for_range;
 mod fragment;
+mod hooks;
+mod image;
+mod lazy;
 mod lcs;
+mod lifecycle;
+mod localization;
 mod mutable;
+mod mutable_map;
 mod node_span;
 mod plugin;
+mod portal;
 mod presenter;
 mod reaction;
+mod reducer;
 mod scope;
+mod scroll_view;
+mod selector;
+mod show_hide;
+mod store;
+mod style_builder;
+mod suspense;
+mod switch;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod text;
+mod theme;
+mod transition;
+mod transition_view;
+mod trigger;
 mod view;
+mod view_group;
 mod view_tuple;
+mod virtual_list;
+mod world_registry;
 
+pub use animated::attach_exiting_views;
+pub use animated::update_animated_views;
+pub use animated::AnimatedView;
+pub use animated::Animation;
+pub use animated::Tween;
+pub use bevy_reactor_derive::view;
+pub use bevy_reactor_derive::Presenter;
+pub use bevy_reactor_derive::Theme;
+pub use bundle::stop_orphaned_bundle_reactions;
+pub use bundle::BundleComputed;
+pub use bundle::BundleComputedRef;
+pub use bundle::BundleConditional;
+pub use bundle::BundleProducer;
+pub use bundle::BundleSequence;
+pub use bundle::BundleStatic;
+pub use bundle::ComponentComputed;
+pub use bundle::ComponentComputedRef;
+pub use callback::CallbackHandle;
+pub use children::ChildViews;
+pub use children::WithChildren;
 pub use cond::cond;
 pub use cond::Cond;
+pub use context::Context;
 pub use cx::Cx;
 pub use cx::Rcx;
 pub use cx::ReactiveContext;
 pub use cx::ReactiveContextMut;
+pub use diagnostics::ReactionStat;
+pub use diagnostics::ReactorDiagnostics;
+pub use diagnostics::ReactorDiagnosticsPlugin;
+pub use diagnostics::MUTABLE_COMMIT_COUNT;
+pub use diagnostics::REACTION_COUNT;
+pub use diagnostics::REBUILD_COUNT;
+pub use dynamic::DynamicView;
 pub use element::Element;
+pub use error_boundary::ErrorBoundary;
+pub use event_reader::ReactiveEventReader;
+pub use event_writer::dispatch_deferred_events;
 pub use for_each::ForEach;
 pub use for_index::ForIndex;
+pub use for_index::ForIndexed;
+pub use for_range::ForRange;
+pub use for_range::RepeatView;
 pub use fragment::Fragment;
+#[doc(hidden)]
+pub use hooks::call_site_key;
+pub use image::image_static;
+pub use image::ImageStatic;
+pub use image::ImageView;
+pub use lazy::LazyView;
+pub use lifecycle::run_mount_callbacks;
+pub use localization::LocalizationResource;
+pub use mutable::commit_mutables;
+pub use mutable::sync_resource_mutables;
+pub use mutable::MappedMutable;
 pub use mutable::Mutable;
+pub use mutable::MutableArc;
+pub use mutable::MutableSnapshot;
+pub use mutable::WatchHandle;
+pub use mutable_map::MutableMap;
+pub use node_span::NodeSpan;
+pub use node_span::NodeSpanIter;
+pub use plugin::ReactorPaused;
 pub use plugin::ReactorPlugin;
+pub use plugin::ReactorSettings;
+pub use portal::Portal;
 pub use presenter::*;
 pub use r#for::For;
 pub use reaction::*;
+pub use reducer::Dispatch;
+pub use reducer::ReducerState;
+pub use scope::run_reactions;
 pub(crate) use scope::DespawnScopes;
-pub(crate) use scope::TrackingScope;
+pub use scope::TrackingScope;
+pub use scroll_view::scroll_on_mouse_wheel;
+pub use scroll_view::ScrollView;
+pub use scroll_view::ScrollableContent;
+pub use scroll_view::ScrollbarThumb;
+pub use scroll_view::ScrollbarTrack;
+pub use selector::Selector;
+pub use show_hide::ShowHide;
+pub use store::StoreHandle;
+pub use style_builder::StyleBuilder;
+pub use suspense::SuspendHandle;
+pub use suspense::Suspense;
+pub use switch::switch;
+pub use switch::Switch;
 pub use text::*;
+pub use theme::Theme;
+pub use theme::ThemeProvider;
+pub use theme::ThemeTokens;
+pub use transition::update_transitions;
+pub use transition::Transition;
+pub use transition::TransitionPhase;
+pub use transition_view::update_transition_views;
+pub use transition_view::TransitionState;
+pub use transition_view::TransitionView;
+pub use trigger::Trigger;
 pub use view::*;
+pub use view_group::ViewGroup;
+pub use view_group::ViewGroupBuilder;
+pub use virtual_list::VirtualList;
+pub use world_registry::WorldRegistry;
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+pub(crate) type LifecycleFn = Box<dyn FnOnce(Entity, &mut World) + Send + Sync>;
+
+/// Component which holds the callbacks registered by [`Cx::on_mount`](crate::Cx::on_mount)
+/// that have not run yet.
+#[derive(Component, Default)]
+pub(crate) struct MountCallbacks(pub(crate) Vec<LifecycleFn>);
+
+/// Component which holds the callbacks registered by
+/// [`Cx::on_unmount`](crate::Cx::on_unmount).
+#[derive(Component, Default)]
+pub(crate) struct UnmountCallbacks(pub(crate) Vec<LifecycleFn>);
+
+/// Hook slot value which records that a lifecycle callback has been registered, so that it
+/// isn't registered again when the presenter reacts.
+#[derive(Clone, Copy)]
+pub(crate) struct LifecycleHook;
+
+/// Marker component for view entities whose mount callbacks have run.
+#[derive(Component)]
+pub(crate) struct Mounted;
+
+/// System which runs the mount callbacks of views which were built since the previous update.
+pub fn run_mount_callbacks(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<MountCallbacks>>();
+    let entities = query.iter(world).collect::<Vec<Entity>>();
+    for entity in entities {
+        let Some(callbacks) = world.entity_mut(entity).take::<MountCallbacks>() else {
+            continue;
+        };
+        world.entity_mut(entity).insert(Mounted);
+        for callback in callbacks.0 {
+            callback(entity, world);
+        }
+    }
+}
+
+/// Run the unmount callbacks for a view entity, if its mount callbacks have run.
+pub(crate) fn run_unmount_callbacks(entity: Entity, world: &mut World) {
+    let Some(mut entt) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if entt.take::<Mounted>().is_none() {
+        return;
+    }
+    if let Some(callbacks) = entt.take::<UnmountCallbacks>() {
+        for callback in callbacks.0 {
+            callback(entity, world);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{cx::Cx, DespawnScopes, TrackingScope};
+
+    use super::*;
+
+    fn counter() -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        (count.clone(), count)
+    }
+
+    #[test]
+    fn test_mount_unmount() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let (mounts, mount_count) = counter();
+        let (unmounts, unmount_count) = counter();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        cx.on_mount(move |e, _| {
+            assert_eq!(e, entity);
+            mounts.fetch_add(1, Ordering::Relaxed);
+        });
+        cx.on_unmount(move |_, _| {
+            unmounts.fetch_add(1, Ordering::Relaxed);
+        });
+        world.entity_mut(entity).insert(scope);
+
+        // Mount callbacks only run once.
+        assert_eq!(mount_count.load(Ordering::Relaxed), 0);
+        run_mount_callbacks(&mut world);
+        run_mount_callbacks(&mut world);
+        assert_eq!(mount_count.load(Ordering::Relaxed), 1);
+        assert_eq!(unmount_count.load(Ordering::Relaxed), 0);
+
+        world.despawn_owned_recursive(entity);
+        assert_eq!(unmount_count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_register_once() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let (mounts, mount_count) = counter();
+        let (unmounts, unmount_count) = counter();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // Simulate the presenter running twice.
+        for _ in 0..2 {
+            let mounts = mounts.clone();
+            let unmounts = unmounts.clone();
+            let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+            cx.on_mount(move |_, _| {
+                mounts.fetch_add(1, Ordering::Relaxed);
+            });
+            cx.on_unmount(move |_, _| {
+                unmounts.fetch_add(1, Ordering::Relaxed);
+            });
+            run_mount_callbacks(&mut world);
+        }
+        world.entity_mut(entity).insert(scope);
+        assert_eq!(mount_count.load(Ordering::Relaxed), 1);
+        assert_eq!(world.get::<UnmountCallbacks>(entity).unwrap().0.len(), 1);
+
+        world.despawn_owned_recursive(entity);
+        assert_eq!(unmount_count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_raze_before_mount() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let (mounts, mount_count) = counter();
+        let (unmounts, unmount_count) = counter();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        cx.on_mount(move |_, _| {
+            mounts.fetch_add(1, Ordering::Relaxed);
+        });
+        cx.on_unmount(move |_, _| {
+            unmounts.fetch_add(1, Ordering::Relaxed);
+        });
+        world.entity_mut(entity).insert(scope);
+
+        world.despawn_owned_recursive(entity);
+        run_mount_callbacks(&mut world);
+        assert_eq!(mount_count.load(Ordering::Relaxed), 0);
+        assert_eq!(unmount_count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::{prelude::*, utils::HashMap};
+
+/// Resource which holds the translated strings for each locale, along with the current
+/// locale. Presenters look up strings with
+/// [`use_localized`](crate::ReactiveContext::use_localized), and react when the locale or the
+/// translations change.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct LocalizationResource {
+    /// The current locale, such as `"en"`.
+    pub locale: String,
+
+    /// Map from locale, to map from key to translated string.
+    pub translations: HashMap<String, HashMap<String, String>>,
+}
+
+impl LocalizationResource {
+    /// Construct a new `LocalizationResource` with the given current locale and no
+    /// translations.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            translations: HashMap::default(),
+        }
+    }
+
+    /// Add a translation of `key` to the given locale.
+    pub fn with_translation(
+        mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.translations
+            .entry(locale.into())
+            .or_default()
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Look up the translation of `key` in the current locale.
+    pub fn translate(&self, key: &str) -> Option<&str> {
+        self.translations
+            .get(&self.locale)?
+            .get(key)
+            .map(String::as_str)
+    }
+}
+
+/// Replace each `{name}` placeholder in `template` with the corresponding argument.
+pub(crate) fn format_localized(template: &str, args: &[(&str, &str)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, ReactiveContext, TrackingScope};
+
+    use super::*;
+
+    fn strings() -> LocalizationResource {
+        LocalizationResource::new("en")
+            .with_translation("en", "greeting", "Hello, {name}!")
+            .with_translation("fr", "greeting", "Bonjour, {name} !")
+            .with_translation("en", "quit", "Quit")
+            .with_translation("fr", "quit", "Quitter")
+    }
+
+    #[test]
+    fn test_localized() {
+        let mut world = World::default();
+        world.insert_resource(strings());
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.use_localized("quit"), "Quit");
+        assert_eq!(cx.use_localized("missing"), "missing");
+        assert_eq!(
+            cx.use_localized_format("greeting", &[("name", "Ada")]),
+            "Hello, Ada!"
+        );
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing the locale causes readers to react.
+        world.resource_mut::<LocalizationResource>().locale = "fr".to_string();
+        assert!(scope.dependencies_changed(&world));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.use_localized("quit"), "Quitter");
+        assert_eq!(
+            cx.use_localized_format("greeting", &[("name", "Ada")]),
+            "Bonjour, Ada !"
+        );
+    }
+}
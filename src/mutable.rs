@@ -1,12 +1,20 @@
-use crate::accessor::{CloneGetter, CloneSetter, Getter, Setter, SignalKind};
+use crate::{
+    accessor::{CloneGetter, CloneSetter, Getter, Setter, SignalKind},
+    diagnostics::ReactorFrameCounts,
+    mutable_map::MutableMapValue,
+    scope::{ReactionCycleGuard, TrackedProjection},
+    Reaction, ReactionHandle, ReactiveContext, ReactorPaused, TrackingScope,
+};
 use bevy::prelude::*;
-use std::{any::Any, sync::atomic::AtomicBool};
+use std::{any::Any, sync::Arc, time::Duration};
 
-/// Contains a mutable reactive value.
+type ValidatorFn = Box<dyn Fn(&(dyn Any + Send + Sync)) -> bool + Send + Sync>;
+type MirrorFn = fn(&mut World, Entity);
+
+/// Contains a mutable reactive value. Readers are notified of changes via the component's
+/// change ticks.
 #[derive(Component)]
 pub(crate) struct MutableValue {
-    // TODO: Currently unused. Use this to allow multiple changes per frame.
-    pub(crate) changed: AtomicBool,
     pub(crate) value: Box<dyn Any + Send + Sync + 'static>,
 }
 
@@ -16,12 +24,101 @@ pub(crate) struct MutableValue {
 #[derive(Component)]
 pub(crate) struct MutableValueNext(pub(crate) Box<dyn Any + Send + Sync + 'static>);
 
+/// Delays committing writes to a mutable until no writes have occurred for a given interval.
+#[derive(Component)]
+pub(crate) struct MutableDebounce {
+    pub(crate) interval: Duration,
+    pub(crate) last_write: Duration,
+}
+
+/// Marks a mutable which can only be written by the reactor itself, such as the validity
+/// flag of a validated mutable.
+#[derive(Component)]
+pub(crate) struct ReadOnlyMutable;
+
+/// Validation function for a mutable, which is run whenever a new value is committed. The
+/// result is stored in the `validity` mutable.
+#[derive(Component)]
+pub(crate) struct MutableValidator {
+    pub(crate) validator: ValidatorFn,
+    pub(crate) validity: Entity,
+}
+
 /// Contains a reference to a reactive mutable variable.
 pub struct Mutable<T> {
     pub(crate) id: Entity,
     pub(crate) marker: std::marker::PhantomData<T>,
 }
 
+impl<T> Mutable<T> {
+    /// Immediately commit any pending write to this [`Mutable`], ignoring any debounce interval.
+    pub fn flush(&self, world: &mut World) {
+        flush_mutable(world, self.id);
+    }
+
+    /// Returns true if this [`Mutable`] has a pending write which has not yet been committed.
+    /// This does not add the mutable to any tracking scope.
+    pub fn is_dirty(&self, world: &World) -> bool {
+        world.get::<MutableValueNext>(self.id).is_some()
+    }
+}
+
+impl<T> Mutable<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Returns the committed value of this [`Mutable`], ignoring any pending write. Unlike
+    /// reading via a reactive context, this does not add the mutable to any tracking scope.
+    pub fn peek<'w>(&self, world: &'w World) -> &'w T {
+        world
+            .get::<MutableValue>(self.id)
+            .unwrap()
+            .value
+            .downcast_ref::<T>()
+            .unwrap()
+    }
+
+    /// Returns both the committed value and the pending value of this [`Mutable`], for
+    /// debugging and inspection. This does not add the mutable to any tracking scope.
+    pub fn snapshot<'w>(&self, world: &'w World) -> MutableSnapshot<'w, T> {
+        MutableSnapshot {
+            value: self.peek(world),
+            pending: world
+                .get::<MutableValueNext>(self.id)
+                .map(|next| next.0.downcast_ref::<T>().unwrap()),
+        }
+    }
+
+    /// Returns a read-only projection of this [`Mutable`]. Readers of the projection are
+    /// subscribed to the source mutable, and react whenever it changes.
+    pub fn map<U>(&self, lens: impl Fn(&T) -> U + Send + Sync + 'static) -> MappedMutable<T, U> {
+        MappedMutable {
+            source: self.id,
+            lens: Arc::new(lens),
+            track: |tracking, mapped, _| tracking.add_mutable(mapped.source),
+        }
+    }
+
+    /// Like [`map`](Mutable::map), except that readers of the projection only react when the
+    /// projected value changes, rather than whenever the source mutable changes.
+    pub fn map_eq<U>(&self, lens: impl Fn(&T) -> U + Send + Sync + 'static) -> MappedMutable<T, U>
+    where
+        U: PartialEq + Clone + Send + Sync + 'static,
+    {
+        MappedMutable {
+            source: self.id,
+            lens: Arc::new(lens),
+            track: |tracking, mapped, value| {
+                tracking.add_projection(TrackedProjection {
+                    source: mapped.source,
+                    lens: mapped.lens.clone(),
+                    value: value.clone(),
+                })
+            },
+        }
+    }
+}
+
 impl<T> Mutable<T>
 where
     T: PartialEq + Copy + Send + Sync + 'static,
@@ -62,42 +159,408 @@ where
     }
 }
 
-/// Trait that allows writing the value to a signal, using Clone semantics.
+impl<T> Mutable<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Call `callback` with the new value whenever this [`Mutable`] changes. This lets
+    /// non-reactive code, such as animation or audio systems, respond to reactive state
+    /// without a presenter. The callback runs within [`run_reactions`], after the write has
+    /// been committed; it is not called for the current value.
+    ///
+    /// [`run_reactions`]: crate::run_reactions
+    pub fn watch<F: Fn(T) + Send + Sync + 'static>(
+        &self,
+        world: &mut World,
+        callback: F,
+    ) -> WatchHandle {
+        let mut reaction = WatchReaction {
+            mutable: self.id,
+            callback,
+            initialized: false,
+            marker: std::marker::PhantomData,
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let entity = world.spawn_empty().id();
+        reaction.react(entity, world, &mut scope);
+        world
+            .entity_mut(entity)
+            .insert((ReactionHandle::new(reaction), scope));
+        WatchHandle { entity }
+    }
+}
+
+/// The state of a [`Mutable`] at a point in time, returned by [`Mutable::snapshot`].
+#[derive(Debug)]
+pub struct MutableSnapshot<'w, T> {
+    /// The committed value, which is what readers currently see.
+    pub value: &'w T,
+    /// The value which will be committed on the next update, if any.
+    pub pending: Option<&'w T>,
+}
+
+impl<T> Mutable<T>
+where
+    T: Resource + Clone + PartialEq,
+{
+    /// Create a [`Mutable`] which mirrors the resource `T`, initialized from its current value.
+    /// Whenever the resource changes, the new value is written to the mutable by
+    /// [`sync_resource_mutables`], which runs just before [`commit_mutables`], so the mutable
+    /// reflects the change on the same update. The mutable is not owned by any presenter, and
+    /// mirroring stops when it is despawned; use
+    /// [`create_mutable_from_resource`](crate::ReactiveContextMut::create_mutable_from_resource)
+    /// to tie it to a presenter instead.
+    pub fn from_resource(world: &mut World) -> Mutable<T> {
+        let value = world.resource::<T>().clone();
+        let id = world
+            .spawn((
+                MutableValue {
+                    value: Box::new(value),
+                },
+                ResourceMirror(mirror_resource::<T>),
+            ))
+            .id();
+        Mutable {
+            id,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Write the committed value of this [`Mutable`] to the resource `T` whenever it changes.
+    /// Together with [`from_resource`](Mutable::from_resource), this keeps the mutable and the
+    /// resource in sync in both directions; since mutable writes are committed on the next
+    /// update, a change on either side settles rather than looping.
+    pub fn to_resource(&self, world: &mut World) -> WatchHandle {
+        let mut reaction = MutableToResource::<T> {
+            mutable: self.id,
+            marker: std::marker::PhantomData,
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let entity = world.spawn_empty().id();
+        reaction.react(entity, world, &mut scope);
+        world
+            .entity_mut(entity)
+            .insert((ReactionHandle::new(reaction), scope));
+        WatchHandle { entity }
+    }
+}
+
+/// Component on a mutable created by [`Mutable::from_resource`], holding the function which
+/// copies the resource into the mutable.
+#[derive(Component)]
+pub(crate) struct ResourceMirror(MirrorFn);
+
+/// Write the value of the resource `T` to the mutable `mutable`, if the resource has changed
+/// since the last update.
+fn mirror_resource<T: Resource + Clone + PartialEq>(world: &mut World, mutable: Entity) {
+    if !world.is_resource_changed::<T>() {
+        return;
+    }
+    let Some(value) = world.get_resource::<T>().cloned() else {
+        return;
+    };
+    let entt = world.entity(mutable);
+    // Compare against the pending value, if there is one, since that is what will be
+    // committed.
+    let current = match entt.get::<MutableValueNext>() {
+        Some(next) => next.0.downcast_ref::<T>(),
+        None => entt
+            .get::<MutableValue>()
+            .and_then(|current| current.value.downcast_ref::<T>()),
+    };
+    if current != Some(&value) {
+        world
+            .entity_mut(mutable)
+            .insert(MutableValueNext(Box::new(value)));
+        after_write(world, mutable);
+    }
+}
+
+/// System which copies changed resources into the mutables created by
+/// [`Mutable::from_resource`]. Runs before [`commit_mutables`].
+pub fn sync_resource_mutables(world: &mut World) {
+    if world.contains_resource::<ReactorPaused>() {
+        return;
+    }
+    let mirrors: Vec<(Entity, MirrorFn)> = world
+        .query::<(Entity, &ResourceMirror)>()
+        .iter(world)
+        .map(|(entity, mirror)| (entity, mirror.0))
+        .collect();
+    for (entity, mirror) in mirrors {
+        mirror(world, entity);
+    }
+}
+
+/// Reaction which writes the value of a mutable to a resource.
+struct MutableToResource<T> {
+    mutable: Entity,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Resource + Clone + PartialEq> Reaction for MutableToResource<T> {
+    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.add_mutable(self.mutable);
+        let Some(value) = world
+            .get::<MutableValue>(self.mutable)
+            .and_then(|value| value.value.downcast_ref::<T>())
+            .cloned()
+        else {
+            return;
+        };
+        if *world.resource::<T>() != value {
+            *world.resource_mut::<T>() = value;
+        }
+    }
+}
+
+/// Reaction which passes the value of a mutable to a callback.
+struct WatchReaction<T, F: Fn(T)> {
+    mutable: Entity,
+    callback: F,
+    initialized: bool,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Clone + Send + Sync + 'static, F: Fn(T)> Reaction for WatchReaction<T, F> {
+    fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.add_mutable(self.mutable);
+        let Some(value) = world.get::<MutableValue>(self.mutable) else {
+            return;
+        };
+        let value = value.value.downcast_ref::<T>().unwrap().clone();
+        if self.initialized {
+            (self.callback)(value);
+        }
+        self.initialized = true;
+    }
+}
+
+/// Handle to an observer created by [`Mutable::watch`] or [`Mutable::to_resource`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WatchHandle {
+    entity: Entity,
+}
+
+impl WatchHandle {
+    /// Stop watching the mutable. The callback will not be called again.
+    pub fn stop(self, world: &mut World) {
+        if let Some(entt) = world.get_entity_mut(self.entity) {
+            entt.despawn();
+        }
+    }
+}
+
+/// A reactive mutable variable which holds a value of a type that is neither `Copy` nor
+/// `Clone`. The value is stored in an `Arc<RwLock<T>>`, which readers can hold on to; writing
+/// a new value replaces the `Arc`, so readers which still hold the old `Arc` are unaffected.
+/// Created by [`ReactiveContextMut::create_mutable_arc`](crate::ReactiveContextMut::create_mutable_arc).
+pub struct MutableArc<T> {
+    pub(crate) id: Entity,
+    pub(crate) marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for MutableArc<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MutableArc<T> {}
+
+impl<T> MutableArc<T> {
+    /// The entity which holds the value.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+/// A derived, read-only projection of a [`Mutable`], created by [`Mutable::map`] or
+/// [`Mutable::map_eq`]. Reading the projection subscribes to the source mutable; no
+/// intermediate entity is created.
+pub struct MappedMutable<T, U> {
+    pub(crate) source: Entity,
+    pub(crate) lens: Arc<dyn Fn(&T) -> U + Send + Sync>,
+    /// Adds the dependency for a read to the tracking scope.
+    track: fn(&mut TrackingScope, &MappedMutable<T, U>, &U),
+}
+
+impl<T: Send + Sync + 'static, U> MappedMutable<T, U> {
+    /// Read the projected value. Calling this function adds the source mutable to the
+    /// current tracking scope.
+    pub fn get<'p, R: ReactiveContext<'p>>(&self, rc: &R) -> U {
+        let value = (self.lens)(
+            rc.world()
+                .get::<MutableValue>(self.source)
+                .unwrap()
+                .value
+                .downcast_ref::<T>()
+                .unwrap(),
+        );
+        (self.track)(&mut rc.tracking(), self, &value);
+        value
+    }
+}
+
+impl<T, U> Clone for MappedMutable<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source,
+            lens: self.lens.clone(),
+            track: self.track,
+        }
+    }
+}
+
+// Trait that allows writing the value to a signal, using Clone semantics.
 // pub struct WriteSignalClone<T: Clone> {
 //     state: Entity,
 //     marker: std::marker::PhantomData<T>,
 // }
 
-/// Trait that allows access to a mutable reference to the signal.
+// Trait that allows access to a mutable reference to the signal.
 // trait WriteSignalRef<T> {
 //     fn write_ref<F: FnMut(&mut T)>(&mut self, f: F);
 // }
 
-pub(crate) fn commit_mutables(world: &mut World) {
-    for (mut sig_val, mut sig_next) in world
-        .query::<(&mut MutableValue, &mut MutableValueNext)>()
+/// Resource which tracks the writes made within [`ReactiveContextMut::batch`].
+///
+/// [`ReactiveContextMut::batch`]: crate::ReactiveContextMut::batch
+#[derive(Resource, Default)]
+pub(crate) struct MutableBatch {
+    /// Nesting depth of the current batch; zero if not batching.
+    pub(crate) depth: usize,
+    /// Mutables written during the current batch.
+    pub(crate) written: Vec<Entity>,
+}
+
+/// Immediately commit the pending write to a mutable, if any, ignoring any debounce interval.
+pub(crate) fn flush_mutable(world: &mut World, mutable: Entity) {
+    commit_pending(world, Some(&[mutable]), true);
+}
+
+/// Panics if the mutable is read-only. Called before each write to a mutable.
+pub(crate) fn check_writable(world: &World, mutable: Entity) {
+    if world
+        .get_entity(mutable)
+        .map(|entt| entt.contains::<ReadOnlyMutable>())
+        .unwrap_or(false)
+    {
+        panic!("Attempt to write to a read-only mutable");
+    }
+}
+
+/// Bookkeeping performed after each write to a mutable.
+pub(crate) fn after_write(world: &mut World, mutable: Entity) {
+    touch_debounce(world, mutable);
+    if let Some(mut guard) = world.get_resource_mut::<ReactionCycleGuard>() {
+        if guard.executing {
+            guard.written.push(mutable);
+        }
+    }
+    if let Some(mut batch) = world.get_resource_mut::<MutableBatch>() {
+        if batch.depth > 0 {
+            batch.written.push(mutable);
+        }
+    }
+}
+
+/// Record the time of a write to a mutable, if the mutable is debounced.
+fn touch_debounce(world: &mut World, mutable: Entity) {
+    let now = world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default();
+    if let Some(mut debounce) = world.get_mut::<MutableDebounce>(mutable) {
+        debounce.last_write = now;
+    }
+}
+
+/// System which commits the pending writes to mutables, making them visible to readers.
+pub fn commit_mutables(world: &mut World) {
+    if world.contains_resource::<ReactorPaused>() {
+        return;
+    }
+    commit_pending(world, None, false);
+
+    // Apply pending writes to reactive maps.
+    let tick = world.change_tick();
+    for mut map in world.query::<&mut MutableMapValue>().iter_mut(world) {
+        if map.0.has_pending() {
+            map.0.commit(tick);
+        }
+    }
+}
+
+/// Commit the pending writes to the given mutables, or to all mutables if `only` is `None`.
+/// Debounced mutables which are still being written are skipped, unless `ignore_debounce` is
+/// set.
+pub(crate) fn commit_pending(world: &mut World, only: Option<&[Entity]>, ignore_debounce: bool) {
+    let now = world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default();
+    let mut mutables: Vec<Entity> = Vec::new();
+    for (entity, mut sig_val, mut sig_next, debounce) in world
+        .query::<(
+            Entity,
+            &mut MutableValue,
+            &mut MutableValueNext,
+            Option<&MutableDebounce>,
+        )>()
         .iter_mut(world)
     {
+        if only.is_some_and(|only| !only.contains(&entity)) {
+            continue;
+        }
+
+        // Debounced mutables wait until writes have stopped.
+        if let Some(debounce) = debounce {
+            if !ignore_debounce && now.saturating_sub(debounce.last_write) < debounce.interval {
+                continue;
+            }
+        }
+
         // Transfer mutable data from next to current.
         std::mem::swap(&mut sig_val.value, &mut sig_next.0);
-        // sig_val
-        //     .changed
-        //     .store(true, std::sync::atomic::Ordering::Relaxed);
+        mutables.push(entity);
     }
 
-    // Remove all the MutableNext components.
-    let mutables: Vec<Entity> = world
-        .query_filtered::<Entity, With<MutableValueNext>>()
-        .iter(world)
-        .collect();
+    if let Some(mut counts) = world.get_resource_mut::<ReactorFrameCounts>() {
+        counts.commits += mutables.len();
+    }
+
+    // Remove the MutableNext components that were committed.
     mutables.iter().for_each(|mutable| {
         world.entity_mut(*mutable).remove::<MutableValueNext>();
     });
+
+    // Validate the committed values.
+    for mutable in mutables {
+        let entt = world.entity(mutable);
+        let (Some(validator), Some(value)) =
+            (entt.get::<MutableValidator>(), entt.get::<MutableValue>())
+        else {
+            continue;
+        };
+        let valid = (validator.validator)(value.value.as_ref());
+        let validity = validator.validity;
+        if let Some(mut current) = world.get_mut::<MutableValue>(validity) {
+            if current.value.downcast_ref::<bool>() != Some(&valid) {
+                current.value = Box::new(valid);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{cx::Cx, ReactiveContextMut, TrackingScope};
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        cx::Cx, run_reactions, testing::TestWorld, DespawnScopes, Reaction, ReactionHandle,
+        ReactiveContextMut, TrackingScope,
+    };
 
     use super::*;
 
@@ -105,7 +568,7 @@ mod tests {
     fn test_mutable_copy() {
         let mut world = World::default();
         let mut scope = TrackingScope::new(world.change_tick());
-        let mut cx = Cx::new(&(), &mut world, &mut scope);
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
 
         let (reader, mut writer) = cx.create_mutable::<i32>(0).signal();
         let (reader2, mut _writer2) = cx.create_mutable::<i32>(0).signal();
@@ -125,7 +588,7 @@ mod tests {
         commit_mutables(&mut world);
 
         // Signals should have changed
-        let cx = Cx::new(&(), &mut world, &mut scope);
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
         assert_eq!(reader.get(&cx), 1);
         assert_eq!(reader2.get(&cx), 0);
     }
@@ -134,7 +597,7 @@ mod tests {
     fn test_mutable_clone() {
         let mut world = World::default();
         let mut scope = TrackingScope::new(world.change_tick());
-        let mut cx = Cx::new(&(), &mut world, &mut scope);
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
 
         let (reader, mut writer) = cx.create_mutable("Hello".to_string()).signal_clone();
         let (reader2, mut _writer2) = cx.create_mutable::<i32>(0).signal_clone();
@@ -154,8 +617,349 @@ mod tests {
         commit_mutables(&mut world);
 
         // Signals should have changed
-        let cx = Cx::new(&(), &mut world, &mut scope);
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
         assert_eq!(reader.get(&cx), "Goodbye".to_string());
         assert_eq!(reader2.get(&cx), 0);
     }
+
+    struct PointReaction {
+        x: Entity,
+        y: Entity,
+        seen: Arc<Mutex<Vec<(i32, i32)>>>,
+    }
+
+    impl Reaction for PointReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let cx = Cx::new(&(), world, Entity::PLACEHOLDER, tracking);
+            let point = (cx.read_mutable(self.x), cx.read_mutable(self.y));
+            self.seen.lock().unwrap().push(point);
+        }
+    }
+
+    #[test]
+    fn test_stable_mutable() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        let count = cx.create_stable_mutable("count", 0);
+        cx.write_mutable(count.id, 5);
+        commit_mutables(&mut world);
+
+        // When the presenter runs again, the same mutable is returned.
+        let mut next = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut next);
+        let again = cx.create_stable_mutable("count", 0);
+        assert_eq!(again.id, count.id);
+        assert_eq!(cx.read_mutable::<i32>(again.id), 5);
+        let other = cx.create_stable_mutable("other", 0);
+        assert_ne!(other.id, count.id);
+
+        // Reusing a key for a value of another type creates a separate mutable.
+        let text = cx.create_stable_mutable("count", "five".to_string());
+        assert_ne!(text.id, count.id);
+        assert_eq!(cx.read_mutable_clone::<String>(text.id), "five");
+        scope.take_deps(&mut next);
+
+        // Razing the presenter despawns the mutables.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert!(world.get_entity(count.id).is_none());
+        assert!(world.get_entity(other.id).is_none());
+        assert!(world.get_entity(text.id).is_none());
+    }
+
+    fn is_positive(value: &i32) -> bool {
+        *value > 0
+    }
+
+    #[test]
+    fn test_validated_mutable() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let (value, validity) = cx.create_mutable_validated(1, is_positive);
+        assert!(cx.read_mutable::<bool>(validity.id));
+
+        // A valid write.
+        cx.write_mutable(value.id, 2);
+        commit_mutables(&mut world);
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert!(cx.read_mutable::<bool>(validity.id));
+        world.increment_change_tick();
+
+        // An invalid write is stored, but the readers of the validity react.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(value.id, -1);
+        commit_mutables(&mut world);
+        assert!(scope.dependencies_changed(&world));
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<i32>(value.id), -1);
+        assert!(!cx.read_mutable::<bool>(validity.id));
+    }
+
+    #[test]
+    #[should_panic(expected = "read-only")]
+    fn test_validity_read_only() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let (_, validity) = cx.create_mutable_validated(1, is_positive);
+        cx.write_mutable(validity.id, false);
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let x = cx.create_mutable::<i32>(0);
+        let y = cx.create_mutable::<i32>(0);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut reaction = PointReaction {
+            x: x.id,
+            y: y.id,
+            seen: seen.clone(),
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let reaction_id = world.spawn_empty().id();
+        reaction.react(reaction_id, &mut world, &mut scope);
+        world
+            .entity_mut(reaction_id)
+            .insert((ReactionHandle::new(reaction), scope));
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.batch(|cx| {
+            cx.write_mutable(x.id, 1);
+            cx.batch(|cx| cx.write_mutable(y.id, 2));
+            // Writes are not visible until the outermost batch ends.
+            assert_eq!(cx.read_mutable::<i32>(y.id), 0);
+        });
+        assert_eq!(cx.read_mutable::<i32>(x.id), 1);
+        assert_eq!(cx.read_mutable::<i32>(y.id), 2);
+
+        // The reaction runs once, and sees both values.
+        run_reactions(&mut world);
+        assert_eq!(*seen.lock().unwrap(), vec![(0, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn test_batch_validated_and_debounced() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let (value, validity) = cx.create_mutable_validated(1, is_positive);
+        let debounced = cx.create_mutable_debounced(0, Duration::from_millis(100));
+
+        cx.batch(|cx| {
+            cx.write_mutable(value.id, -1);
+            cx.write_mutable(debounced.id, 5);
+        });
+        // The validator runs when the batch ends.
+        assert_eq!(cx.read_mutable::<i32>(value.id), -1);
+        assert!(!cx.read_mutable::<bool>(validity.id));
+        // The debounced write is still pending.
+        assert_eq!(cx.read_mutable::<i32>(debounced.id), 0);
+    }
+
+    #[test]
+    fn test_batch_panic() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let x = cx.create_mutable::<i32>(0);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            cx.batch(|cx| {
+                cx.write_mutable(x.id, 1);
+                panic!("oops");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.resource::<MutableBatch>().depth, 0);
+
+        // Later writes are no longer held back by the batch.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(x.id, 2);
+        assert!(world.resource::<MutableBatch>().written.is_empty());
+        commit_mutables(&mut world);
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<i32>(x.id), 2);
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mutable = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).create_mutable(0);
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+        let seen = first.clone();
+        let handle = mutable.watch(&mut world, move |value: i32| {
+            seen.lock().unwrap().push(value)
+        });
+        let seen = second.clone();
+        mutable.watch(&mut world, move |value: i32| {
+            seen.lock().unwrap().push(value)
+        });
+        world.increment_change_tick();
+
+        // Both watchers see the change.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(mutable.id, 1);
+        commit_mutables(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(*first.lock().unwrap(), vec![1]);
+        assert_eq!(*second.lock().unwrap(), vec![1]);
+        world.increment_change_tick();
+
+        // Writing the same value is not a change.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(mutable.id, 1);
+        commit_mutables(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(*first.lock().unwrap(), vec![1]);
+        world.increment_change_tick();
+
+        // A stopped watcher is not called.
+        handle.stop(&mut world);
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(mutable.id, 2);
+        commit_mutables(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(*first.lock().unwrap(), vec![1]);
+        assert_eq!(*second.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_dirty_and_peek() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mutable = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).create_mutable(0);
+        assert!(!mutable.is_dirty(&world));
+
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).write_mutable(mutable.id, 1);
+        assert!(mutable.is_dirty(&world));
+        assert_eq!(*mutable.peek(&world), 0);
+        let snapshot = mutable.snapshot(&world);
+        assert_eq!(*snapshot.value, 0);
+        assert_eq!(snapshot.pending, Some(&1));
+
+        commit_mutables(&mut world);
+        assert!(!mutable.is_dirty(&world));
+        assert_eq!(*mutable.peek(&world), 1);
+        assert_eq!(mutable.snapshot(&world).pending, None);
+    }
+
+    #[test]
+    fn test_mutable_arc() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let mutable = cx.create_mutable_arc(String::from("first"));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        let first = cx.read_mutable_arc(&mutable);
+        let other_reader = cx.read_mutable_arc(&mutable);
+        assert!(Arc::ptr_eq(&first, &other_reader));
+        world.increment_change_tick();
+
+        // Writing an equal value with value comparison does not trigger a reaction.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable_arc_eq(&mutable, String::from("first"));
+        commit_mutables(&mut world);
+        assert!(!scope.dependencies_changed(&world));
+
+        // Writing a new value always replaces the Arc, even if the value is equal.
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable_arc(&mutable, String::from("first"));
+        commit_mutables(&mut world);
+        assert!(scope.dependencies_changed(&world));
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable_arc(&mutable, String::from("second"));
+        commit_mutables(&mut world);
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let second = cx.read_mutable_arc(&mutable);
+        assert_eq!(*second.read().unwrap(), "second");
+
+        // Readers still holding the old Arc see the old value.
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(*first.read().unwrap(), "first");
+        assert_eq!(*other_reader.read().unwrap(), "first");
+    }
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Volume(u32);
+
+    #[test]
+    fn test_from_resource() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Volume(1));
+        let mutable = Mutable::<Volume>::from_resource(world.world_mut());
+        assert_eq!(*mutable.peek(world.world()), Volume(1));
+
+        // The change is committed on the next update.
+        world.world_mut().resource_mut::<Volume>().0 = 2;
+        world.tick();
+        assert!(!mutable.is_dirty(world.world()));
+        assert_eq!(*mutable.peek(world.world()), Volume(2));
+    }
+
+    #[test]
+    fn test_create_mutable_from_resource() {
+        let mut world = World::default();
+        world.insert_resource(Volume(1));
+        let presenter = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mutable = Cx::new(&(), &mut world, presenter, &mut scope)
+            .create_mutable_from_resource::<Volume>();
+        assert_eq!(*mutable.peek(&world), Volume(1));
+
+        // Razing the presenter despawns the mirror.
+        world.entity_mut(presenter).insert(scope);
+        world.despawn_owned_recursive(presenter);
+        assert!(world.get_entity(mutable.id).is_none());
+    }
+
+    #[test]
+    fn test_to_resource() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Volume(1));
+        let mutable = world.create_mutable(Volume(1));
+        mutable.to_resource(world.world_mut());
+
+        world.set_mutable(&mutable, Volume(3));
+        world.tick();
+        assert_eq!(*world.world().resource::<Volume>(), Volume(3));
+    }
+
+    #[test]
+    fn test_resource_sync() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Volume(1));
+        let mutable = Mutable::<Volume>::from_resource(world.world_mut());
+        mutable.to_resource(world.world_mut());
+
+        // Changes from either side settle, rather than bouncing back and forth.
+        world.world_mut().resource_mut::<Volume>().0 = 2;
+        world.tick();
+        assert_eq!(*mutable.peek(world.world()), Volume(2));
+        world.set_mutable(&mutable, Volume(4));
+        world.tick();
+        assert_eq!(*world.world().resource::<Volume>(), Volume(4));
+        for _ in 0..3 {
+            world.tick();
+            assert!(!mutable.is_dirty(world.world()));
+            assert_eq!(*world.world().resource::<Volume>(), Volume(4));
+        }
+        assert!(world
+            .world()
+            .get_resource::<ReactionCycleGuard>()
+            .is_none_or(|guard| guard.cycles.is_empty()));
+    }
 }
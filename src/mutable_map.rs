@@ -0,0 +1,189 @@
+use std::{any::Any, hash::Hash, marker::PhantomData};
+
+use bevy::{ecs::component::Tick, prelude::*, utils::HashMap};
+
+use crate::scope::AnyMapKey;
+
+/// Type-erased interface to a [`MutableMapInner`], used to commit pending writes.
+pub(crate) trait AnyMutableMap: Send + Sync {
+    /// Apply the pending writes, recording `tick` as the change tick of each written key.
+    fn commit(&mut self, tick: Tick);
+
+    /// Returns true if there are writes waiting to be committed.
+    fn has_pending(&self) -> bool;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// The contents of a reactive map, along with the writes which will be applied on the next
+/// update.
+pub(crate) struct MutableMapInner<K, V> {
+    values: HashMap<K, V>,
+    /// Pending writes; `None` means the key is removed.
+    pending: Vec<(K, Option<V>)>,
+    /// The tick at which each key was most recently written.
+    changed: HashMap<K, Tick>,
+}
+
+impl<K, V> AnyMutableMap for MutableMapInner<K, V>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn commit(&mut self, tick: Tick) {
+        for (key, value) in self.pending.drain(..) {
+            match value {
+                Some(value) => self.values.insert(key.clone(), value),
+                None => self.values.remove(&key),
+            };
+            self.changed.insert(key, tick);
+        }
+    }
+
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Component which contains a reactive map.
+#[derive(Component)]
+pub(crate) struct MutableMapValue(pub(crate) Box<dyn AnyMutableMap>);
+
+impl MutableMapValue {
+    pub(crate) fn new<K, V>() -> Self
+    where
+        K: Eq + Hash + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        Self(Box::new(MutableMapInner::<K, V> {
+            values: HashMap::default(),
+            pending: Vec::new(),
+            changed: HashMap::default(),
+        }))
+    }
+
+    pub(crate) fn inner<K: 'static, V: 'static>(&self) -> &MutableMapInner<K, V> {
+        self.0.as_any().downcast_ref().unwrap()
+    }
+
+    pub(crate) fn inner_mut<K: 'static, V: 'static>(&mut self) -> &mut MutableMapInner<K, V> {
+        self.0.as_any_mut().downcast_mut().unwrap()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> MutableMapInner<K, V> {
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        self.values.get(key).cloned()
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub(crate) fn write(&mut self, key: K, value: Option<V>) {
+        self.pending.push((key, value));
+    }
+}
+
+/// Contains a reference to a reactive map. Created via
+/// [`ReactiveContextMut::create_mutable_map`](crate::ReactiveContextMut::create_mutable_map).
+///
+/// Readers subscribe to individual keys, so that a change to one key does not cause readers
+/// of other keys to react. Like [`Mutable`](crate::Mutable), writes are not visible until the
+/// next update.
+pub struct MutableMap<K, V> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> Clone for MutableMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for MutableMap<K, V> {}
+
+/// Tracks a single key of a reactive map.
+pub(crate) struct TrackedMapKey<K, V> {
+    pub(crate) map: Entity,
+    pub(crate) key: K,
+    pub(crate) marker: PhantomData<fn() -> V>,
+}
+
+impl<K, V> AnyMapKey for TrackedMapKey<K, V>
+where
+    K: Eq + Hash + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        world
+            .get::<MutableMapValue>(self.map)
+            .and_then(|map| map.inner::<K, V>().changed.get(&self.key).copied())
+            .map(|changed| changed.is_newer_than(tick, world.read_change_tick()))
+            .unwrap_or(false)
+    }
+
+    fn source(&self) -> Entity {
+        self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{commit_mutables, cx::Cx, ReactiveContext, ReactiveContextMut, TrackingScope};
+
+    use super::*;
+
+    #[test]
+    fn test_mutable_map() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let mut map = cx.create_mutable_map::<&'static str, u32>();
+        cx.map_insert(&mut map, "A", 1);
+        cx.map_insert(&mut map, "B", 2);
+
+        // Writes are not visible until committed.
+        assert_eq!(cx.map_get(&map, &"A"), None);
+        commit_mutables(&mut world);
+
+        let mut reader = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut reader);
+        assert_eq!(cx.map_get(&map, &"A"), Some(1));
+        let mut has_key = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut has_key);
+        assert!(!cx.map_contains_key(&map, &"C"));
+        world.increment_change_tick();
+
+        // Modifying a different key.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).map_insert(&mut map, "B", 3);
+        commit_mutables(&mut world);
+        assert!(!reader.dependencies_changed(&world));
+        assert!(!has_key.dependencies_changed(&world));
+
+        // Adding a key.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).map_insert(&mut map, "C", 4);
+        commit_mutables(&mut world);
+        assert!(has_key.dependencies_changed(&world));
+        assert!(!reader.dependencies_changed(&world));
+
+        // Removing a key.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).map_remove(&mut map, "A");
+        commit_mutables(&mut world);
+        assert!(reader.dependencies_changed(&world));
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.map_get(&map, &"A"), None);
+        assert_eq!(cx.map_get(&map, &"B"), Some(3));
+    }
+}
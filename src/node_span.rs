@@ -2,10 +2,14 @@ use bevy::prelude::*;
 
 /// Hierarchical data structure representing an ordered sequence of entities. This is the
 /// rendered output of a `View`. This gets flattened before attaching to the parent UiNode.
-#[derive(Debug, Clone)]
+///
+/// Each view entity holds a copy of its view's `NodeSpan` as a component, which is updated
+/// whenever the view's display nodes change.
+#[derive(Component, Debug, Clone, Default)]
 pub enum NodeSpan {
     /// Means that nothing was rendered. This can represent either an initial state
     /// before the first render, or a conditional render operation.
+    #[default]
     Empty,
 
     /// Template rendered a single node
@@ -25,6 +29,34 @@ impl NodeSpan {
         }
     }
 
+    /// Returns the number of actual entities contained in this span. Same as
+    /// [`count`](NodeSpan::count).
+    pub fn len(&self) -> usize {
+        self.count()
+    }
+
+    /// Returns true if this span contains no entities.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns true if this span contains the given entity.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.iter().any(|e| e == entity)
+    }
+
+    /// Returns an iterator over the entities in this span, in order.
+    pub fn iter(&self) -> NodeSpanIter<'_> {
+        NodeSpanIter { stack: vec![self] }
+    }
+
+    /// Returns the entities in this span as a flat vector.
+    pub fn flat_collect(&self) -> Vec<Entity> {
+        let mut out = Vec::with_capacity(self.count());
+        self.flatten(&mut out);
+        out
+    }
+
     /// Flattens the list of entities into a vector.
     pub fn flatten(&self, out: &mut Vec<Entity>) {
         match self {
@@ -34,6 +66,47 @@ impl NodeSpan {
         }
     }
 
+    /// Combine several spans into one. Returns `Empty` if there are no spans, or the span
+    /// itself if there is only one; otherwise returns a `Fragment` containing all of the spans.
+    pub fn merge(spans: impl IntoIterator<Item = NodeSpan>) -> NodeSpan {
+        let mut spans: Vec<NodeSpan> = spans.into_iter().collect();
+        match spans.len() {
+            0 => Self::Empty,
+            1 => spans.pop().unwrap(),
+            _ => Self::Fragment(spans.into_boxed_slice()),
+        }
+    }
+
+    /// Combine two spans into one. Unlike [`merge`](NodeSpan::merge), the contents of
+    /// fragments are spliced into the result rather than nested, and empty spans are dropped.
+    pub fn concat(a: NodeSpan, b: NodeSpan) -> NodeSpan {
+        Self::merge([a, b].into_iter().flat_map(|span| match span {
+            Self::Empty => Vec::new(),
+            Self::Node(_) => vec![span],
+            Self::Fragment(nodes) => nodes.into_vec(),
+        }))
+    }
+
+    /// Returns a copy of this span which only contains the entities that match `predicate`.
+    pub fn filter_entities(&self, predicate: impl Fn(Entity) -> bool) -> NodeSpan {
+        self.filter_with(&predicate)
+    }
+
+    fn filter_with(&self, predicate: &dyn Fn(Entity) -> bool) -> NodeSpan {
+        match self {
+            Self::Empty => Self::Empty,
+            Self::Node(entity) if predicate(*entity) => Self::Node(*entity),
+            Self::Node(_) => Self::Empty,
+            Self::Fragment(nodes) => Self::Fragment(
+                nodes
+                    .iter()
+                    .map(|node| node.filter_with(predicate))
+                    .filter(|node| *node != Self::Empty)
+                    .collect(),
+            ),
+        }
+    }
+
     // Despawn all entities held.
     // pub(crate) fn despawn(&self, world: &mut World) {
     //     match self {
@@ -61,8 +134,112 @@ impl PartialEq for NodeSpan {
     }
 }
 
-impl Default for NodeSpan {
-    fn default() -> Self {
-        Self::Empty
+impl<'a> IntoIterator for &'a NodeSpan {
+    type Item = Entity;
+    type IntoIter = NodeSpanIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Depth-first iterator over the entities in a [`NodeSpan`].
+pub struct NodeSpanIter<'a> {
+    stack: Vec<&'a NodeSpan>,
+}
+
+impl<'a> Iterator for NodeSpanIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        while let Some(span) = self.stack.pop() {
+            match span {
+                NodeSpan::Empty => {}
+                NodeSpan::Node(entity) => return Some(*entity),
+                NodeSpan::Fragment(nodes) => self.stack.extend(nodes.iter().rev()),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let span = NodeSpan::Empty;
+        assert_eq!(span.len(), 0);
+        assert!(span.is_empty());
+        assert!(span.flat_collect().is_empty());
+    }
+
+    #[test]
+    fn test_node() {
+        let mut world = World::default();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let span = NodeSpan::Node(a);
+        assert_eq!(span.len(), 1);
+        assert!(!span.is_empty());
+        assert_eq!(span.flat_collect(), vec![a]);
+        assert!(span.contains(a));
+        assert!(!span.contains(b));
+    }
+
+    #[test]
+    fn test_fragment() {
+        let mut world = World::default();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        let span = NodeSpan::Fragment(Box::new([]));
+        assert_eq!(span.len(), 0);
+        assert!(span.is_empty());
+
+        let span = NodeSpan::Fragment(Box::new([
+            NodeSpan::Node(a),
+            NodeSpan::Fragment(Box::new([NodeSpan::Node(b), NodeSpan::Node(c)])),
+        ]));
+        assert_eq!(span.len(), 3);
+        assert!(!span.is_empty());
+        assert_eq!(span.flat_collect(), vec![a, b, c]);
+        assert_eq!((&span).into_iter().collect::<Vec<_>>(), vec![a, b, c]);
+        assert!(span.contains(c));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut world = World::default();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        assert_eq!(NodeSpan::merge([]), NodeSpan::Empty);
+        assert_eq!(NodeSpan::merge([NodeSpan::Node(a)]), NodeSpan::Node(a));
+        assert_eq!(
+            NodeSpan::merge([NodeSpan::Node(a), NodeSpan::Node(b)]),
+            NodeSpan::Fragment(Box::new([NodeSpan::Node(a), NodeSpan::Node(b)]))
+        );
+
+        let ab = NodeSpan::Fragment(Box::new([NodeSpan::Node(a), NodeSpan::Node(b)]));
+        let c_frag = NodeSpan::Fragment(Box::new([NodeSpan::Node(c)]));
+        assert_eq!(
+            NodeSpan::concat(ab.clone(), c_frag),
+            NodeSpan::Fragment(Box::new([
+                NodeSpan::Node(a),
+                NodeSpan::Node(b),
+                NodeSpan::Node(c)
+            ]))
+        );
+        assert_eq!(
+            NodeSpan::concat(NodeSpan::Empty, NodeSpan::Node(c)),
+            NodeSpan::Node(c)
+        );
+
+        let filtered = ab.filter_entities(|e| e != a);
+        assert_eq!(filtered, NodeSpan::Fragment(Box::new([NodeSpan::Node(b)])));
     }
 }
@@ -1,23 +1,400 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::schedule::{ScheduleLabel, SystemConfigs},
+    prelude::*,
+};
 
 use crate::{
-    attach_child_views, build_added_view_roots, mutable::commit_mutables, scope::run_reactions,
+    attach_child_views, attach_exiting_views, build_added_view_roots, build_startup_views,
+    commit_mutables, diagnostics::add_frame_diagnostics, dispatch_deferred_events,
+    propagate_display_node_changed, run_mount_callbacks, run_reactions, scroll_on_mouse_wheel,
+    stop_orphaned_bundle_reactions, sync_resource_mutables, update_animated_views,
+    update_transition_views, update_transitions,
 };
 
+type ScheduleFn = Box<dyn Fn(&mut App, SystemConfigs) + Send + Sync>;
+type ConfigFn = Box<dyn Fn(SystemConfigs) -> SystemConfigs + Send + Sync>;
+
 /// Plugin that adds the reactive UI system to the app.
-pub struct ReactorPlugin;
+///
+/// By default, the reactive systems run in the `Update` schedule. The builder methods can be
+/// used to change the schedule, and to order the systems relative to other system sets. View
+/// roots spawned by `Startup` systems are built in `PostStartup`, so that they are displayed
+/// on the first frame regardless of the schedule.
+///
+/// The core reactive systems, such as [`commit_mutables`] and [`run_reactions`], only need a
+/// `World`; resources such as `Time` are used if present. This means the plugin works with
+/// `MinimalPlugins`, or with no other plugins at all. For headless apps,
+/// [`ReactorPlugin::minimal`] also leaves out the systems which handle user input.
+pub struct ReactorPlugin {
+    /// Adds the systems to the configured schedule.
+    schedule: ScheduleFn,
+
+    /// Ordering constraints applied to the systems.
+    config: Vec<ConfigFn>,
+
+    /// Whether to check dependencies and run reactions in parallel.
+    parallelism: bool,
+
+    /// Whether to report reaction counts to Bevy's diagnostics.
+    diagnostics: bool,
+
+    /// Whether to leave out the systems which handle user input.
+    minimal: bool,
+
+    /// Whether to log the view hierarchy when it changes.
+    #[cfg(feature = "debug")]
+    debug_view_tree: bool,
+}
+
+/// Settings for the reactive systems, added by [`ReactorPlugin`].
+#[derive(Resource, Default)]
+pub struct ReactorSettings {
+    /// If true, the dependencies of all reactions are checked in parallel, and reactions which
+    /// declare disjoint [write sets](crate::Reaction::write_set) are run in parallel waves.
+    /// Other reactions are always run sequentially, since they require exclusive access to the
+    /// world.
+    pub parallelism: bool,
+}
+
+/// Marker resource which suspends the reactive systems, added by [`ReactorPlugin::pause`].
+/// While paused, mutables can still be written, but the writes are not committed, reactions
+/// do not run, and new view roots are not built.
+#[derive(Resource, Default)]
+pub struct ReactorPaused;
+
+impl ReactorPlugin {
+    /// Construct a new `ReactorPlugin`.
+    pub fn new() -> Self {
+        Self {
+            schedule: Box::new(|app, systems| {
+                app.add_systems(Update, systems);
+            }),
+            config: Vec::new(),
+            parallelism: false,
+            diagnostics: false,
+            minimal: false,
+            #[cfg(feature = "debug")]
+            debug_view_tree: false,
+        }
+    }
+
+    /// Construct a `ReactorPlugin` for headless apps, such as servers and tests which use
+    /// `MinimalPlugins`. This leaves out the systems which depend on input or windowing, such
+    /// as scrolling [`ScrollView`](crate::ScrollView) with the mouse wheel.
+    pub fn minimal() -> Self {
+        Self {
+            minimal: true,
+            ..Self::new()
+        }
+    }
+
+    /// Run the reactive systems in the given schedule instead of `Update`.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel + Clone) -> Self {
+        self.schedule = Box::new(move |app, systems| {
+            app.add_systems(schedule.clone(), systems);
+        });
+        self
+    }
+
+    /// Enable or disable parallel reactions. When enabled, reaction dependencies are checked in
+    /// parallel, and consecutive reactions whose write sets don't overlap are computed
+    /// concurrently, with their writes applied in order. This is worthwhile when there are a
+    /// large number of reactions.
+    pub fn with_parallelism(mut self, enabled: bool) -> Self {
+        self.parallelism = enabled;
+        self
+    }
+
+    /// Report the number of reactions, view rebuilds and mutable commits in each frame to
+    /// Bevy's diagnostics, as `reactor.reaction_count`, `reactor.rebuild_count` and
+    /// `reactor.mutable_commit_count`.
+    pub fn with_diagnostics(mut self) -> Self {
+        self.diagnostics = true;
+        self
+    }
+
+    /// Log the hierarchy of view entities whenever views are spawned, using
+    /// [`debug_view_tree`](crate::debug_view_tree). Requires the `debug` feature.
+    #[cfg(feature = "debug")]
+    pub fn with_debug_view_tree(mut self) -> Self {
+        self.debug_view_tree = true;
+        self
+    }
+
+    /// Suspend all reactions, for example during a cutscene or loading screen. Pausing an
+    /// already paused reactor has no effect.
+    pub fn pause(world: &mut World) {
+        if !world.contains_resource::<ReactorPaused>() {
+            world.insert_resource(ReactorPaused);
+        }
+    }
+
+    /// Resume reactions after [`pause`](ReactorPlugin::pause). Writes made while paused are
+    /// committed together on the next update, and views added while paused are built.
+    pub fn resume(world: &mut World) {
+        world.remove_resource::<ReactorPaused>();
+    }
+
+    /// Returns true if the reactor is paused.
+    pub fn is_paused(world: &World) -> bool {
+        world.contains_resource::<ReactorPaused>()
+    }
+
+    /// Run the reactive systems before the given system set.
+    pub fn before(mut self, set: impl SystemSet + Clone) -> Self {
+        self.config
+            .push(Box::new(move |systems| systems.before(set.clone())));
+        self
+    }
+
+    /// Run the reactive systems after the given system set.
+    pub fn after(mut self, set: impl SystemSet + Clone) -> Self {
+        self.config
+            .push(Box::new(move |systems| systems.after(set.clone())));
+        self
+    }
+
+    /// Add the reactive systems to the given system set.
+    pub fn in_set(mut self, set: impl SystemSet + Clone) -> Self {
+        self.config
+            .push(Box::new(move |systems| systems.in_set(set.clone())));
+        self
+    }
+}
+
+impl Default for ReactorPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reactive systems, chained in the order in which they run each frame. This is shared by
+/// [`ReactorPlugin`] and [`TestWorld`](crate::testing::TestWorld).
+pub(crate) fn reactor_systems() -> SystemConfigs {
+    (
+        update_transitions,
+        update_animated_views,
+        update_transition_views,
+        sync_resource_mutables,
+        commit_mutables,
+        stop_orphaned_bundle_reactions,
+        build_added_view_roots,
+        run_reactions,
+        dispatch_deferred_events,
+        propagate_display_node_changed,
+        attach_child_views,
+        attach_exiting_views,
+        run_mount_callbacks,
+    )
+        .chain()
+}
 
 impl Plugin for ReactorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                commit_mutables,
-                build_added_view_roots,
-                run_reactions,
-                attach_child_views,
+        let mut systems = reactor_systems();
+        #[cfg(feature = "debug")]
+        if self.debug_view_tree {
+            systems = (
+                systems,
+                crate::debug_view_tree.run_if(crate::view::view_tree_changed),
             )
-                .chain(),
+                .chain();
+        }
+        for config in self.config.iter() {
+            systems = config(systems);
+        }
+        (self.schedule)(app, systems);
+        if !self.minimal {
+            // Wheel input is written to the scroll offsets before they are committed.
+            let mut input = scroll_on_mouse_wheel.before(commit_mutables);
+            for config in self.config.iter() {
+                input = config(input);
+            }
+            (self.schedule)(app, input);
+        }
+        app.add_systems(PostStartup, build_startup_views);
+        app.insert_resource(ReactorSettings {
+            parallelism: self.parallelism,
+        });
+        if self.diagnostics {
+            add_frame_diagnostics(app);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cx::Cx, node_span::NodeSpan, text_computed, ReactiveContext, ReactiveContextMut,
+        TrackingScope, ViewRoot,
+    };
+
+    use super::*;
+
+    fn text(world: &World, root: Entity) -> Option<String> {
+        let view = world.get::<ViewRoot>(root).unwrap().view.clone();
+        let view = view.lock().unwrap();
+        let NodeSpan::Node(node) = view.nodes(world) else {
+            return None;
+        };
+        Some(world.get::<Text>(node).unwrap().sections[0].value.clone())
+    }
+
+    fn counter_root(app: &mut App, counter: Entity) -> Entity {
+        app.world
+            .spawn(ViewRoot::new(text_computed(move |cx| {
+                cx.read_mutable::<u32>(counter).to_string()
+            })))
+            .id()
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut app = App::new();
+        app.add_plugins(ReactorPlugin::new());
+        let mut owner = TrackingScope::new(app.world.change_tick());
+        let counter =
+            Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner).create_mutable(0u32);
+        let root = counter_root(&mut app, counter.id);
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "0");
+
+        // Writes are not committed while paused.
+        ReactorPlugin::pause(&mut app.world);
+        ReactorPlugin::pause(&mut app.world);
+        assert!(ReactorPlugin::is_paused(&app.world));
+        let mut cx = Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(counter.id, 1u32);
+        let late = counter_root(&mut app, counter.id);
+        app.update();
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "0");
+        let cx = Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<u32>(counter.id), 0);
+
+        // New views are built after resuming, and both views see the committed write.
+        ReactorPlugin::resume(&mut app.world);
+        assert!(!ReactorPlugin::is_paused(&app.world));
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "1");
+        assert_eq!(text(&app.world, late).unwrap(), "1");
+    }
+
+    #[test]
+    fn test_minimal_plugins() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, ReactorPlugin::minimal()));
+        let mut owner = TrackingScope::new(app.world.change_tick());
+        let counter =
+            Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner).create_mutable(0u32);
+        let root = counter_root(&mut app, counter.id);
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "0");
+
+        let mut cx = Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(counter.id, 1u32);
+        app.update();
+        let cx = Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<u32>(counter.id), 1);
+        assert_eq!(text(&app.world, root).unwrap(), "1");
+    }
+
+    #[derive(Resource)]
+    struct Greeting(&'static str);
+
+    #[derive(Resource)]
+    struct StartupRoot(Entity);
+
+    /// Text displayed by the startup root when `Update` runs, for each frame.
+    #[derive(Resource, Default)]
+    struct SeenInUpdate(Vec<Option<String>>);
+
+    #[test]
+    fn test_startup_views() {
+        let mut app = App::new();
+        app.add_plugins(ReactorPlugin::new().in_schedule(PostUpdate))
+            .init_resource::<SeenInUpdate>()
+            .add_systems(Startup, |mut commands: Commands| {
+                // The resource read by the view is inserted by a startup system.
+                commands.insert_resource(Greeting("Hello"));
+                let root = commands
+                    .spawn(ViewRoot::new(text_computed(|cx| {
+                        cx.use_resource::<Greeting>().0.to_string()
+                    })))
+                    .id();
+                commands.insert_resource(StartupRoot(root));
+            })
+            .add_systems(Update, |world: &mut World| {
+                let root = world.resource::<StartupRoot>().0;
+                let seen = text(world, root);
+                world.resource_mut::<SeenInUpdate>().0.push(seen);
+            });
+
+        // The view is displayed before the reactive systems run for the first time.
+        app.update();
+        let root = app.world.resource::<StartupRoot>().0;
+        assert_eq!(
+            app.world.resource::<SeenInUpdate>().0,
+            vec![Some("Hello".to_string())]
         );
+
+        // The view is not built again.
+        let node = app.world.get::<NodeSpan>(root).unwrap().clone();
+        app.update();
+        assert_eq!(app.world.get::<NodeSpan>(root), Some(&node));
+        let mut texts = app.world.query::<&Text>();
+        assert_eq!(texts.iter(&app.world).count(), 1);
+    }
+
+    #[derive(Resource)]
+    struct Counter(Entity);
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct GameSet;
+
+    /// System which increments the counter each frame.
+    fn increment_counter(world: &mut World) {
+        let counter = world.resource::<Counter>().0;
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, Entity::PLACEHOLDER, &mut owner);
+        let value = cx.read_mutable::<u32>(counter);
+        cx.write_mutable(counter, value + 1);
+    }
+
+    fn counter_app(plugin: ReactorPlugin) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(plugin)
+            .add_systems(Update, increment_counter.in_set(GameSet));
+        let mut owner = TrackingScope::new(app.world.change_tick());
+        let counter =
+            Cx::new(&(), &mut app.world, Entity::PLACEHOLDER, &mut owner).create_mutable(0u32);
+        app.insert_resource(Counter(counter.id));
+        let root = counter_root(&mut app, counter.id);
+        (app, root)
+    }
+
+    #[test]
+    fn test_in_schedule() {
+        // Reactions in `PostUpdate` see the writes made during `Update` in the same frame.
+        let (mut app, root) = counter_app(ReactorPlugin::new().in_schedule(PostUpdate));
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "1");
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "2");
+    }
+
+    #[test]
+    fn test_before_set() {
+        // When the reactor runs before the writer, writes are committed on the next frame.
+        let (mut app, root) = counter_app(ReactorPlugin::new().before(GameSet));
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "0");
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "1");
+
+        // And when it runs after the writer, in the same frame.
+        let (mut app, root) = counter_app(ReactorPlugin::new().after(GameSet));
+        app.update();
+        assert_eq!(text(&app.world, root).unwrap(), "1");
     }
 }
@@ -0,0 +1,206 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, DespawnScopes, IntoView, Rcx, TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// A view which renders its child's display nodes as children of some other entity, rather
+/// than as part of its logical parent. This is useful for things like tooltips, menus and
+/// dialogs. The portal itself has no display nodes.
+pub struct Portal {
+    /// Function which computes the target entity.
+    target_fn: Box<dyn Fn(&Rcx) -> Entity + Send + Sync>,
+
+    /// The entity which currently holds the portal content.
+    target: Option<Entity>,
+
+    /// The portal content.
+    view: ViewRef,
+
+    /// View entity for the portal content.
+    inner: Option<Entity>,
+
+    /// Display nodes which are currently attached to the target.
+    attached: Vec<Entity>,
+}
+
+impl Portal {
+    /// Construct a new `Portal` which renders `view` as a child of `target`.
+    pub fn new(target: Entity, view: impl IntoView) -> Self {
+        Self::new_dynamic(move |_| target, view)
+    }
+
+    /// Construct a new `Portal` whose target entity is computed reactively.
+    pub fn new_dynamic(
+        target_fn: impl Fn(&Rcx) -> Entity + Send + Sync + 'static,
+        view: impl IntoView,
+    ) -> Self {
+        Self {
+            target_fn: Box::new(target_fn),
+            target: None,
+            view: view.into_view(),
+            inner: None,
+            attached: Vec::new(),
+        }
+    }
+
+    fn target_entity<'w>(&self, world: &'w mut World) -> Option<EntityWorldMut<'w>> {
+        match self.target {
+            Some(target) => world.get_entity_mut(target),
+            None => None,
+        }
+    }
+
+    /// Replace the display nodes attached to the target with the current output of the view.
+    fn attach(&mut self, world: &mut World) {
+        let nodes = self.view.lock().unwrap().nodes(world).flat_collect();
+        if let Some(mut target) = self.target_entity(world) {
+            target.remove_children(&self.attached);
+            target.push_children(&nodes);
+        }
+        self.attached = nodes;
+    }
+
+    /// Remove the attached display nodes from the target.
+    fn detach(&mut self, world: &mut World) {
+        if let Some(mut target) = self.target_entity(world) {
+            target.remove_children(&self.attached);
+        }
+        self.attached.clear();
+    }
+}
+
+impl View for Portal {
+    fn nodes(&self, _world: &World) -> NodeSpan {
+        NodeSpan::Empty
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.inner.is_none());
+        self.inner = Some(ViewHandle::spawn(&self.view, view_entity, world));
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let target = (self.target_fn)(&re);
+        if self.target != Some(target) {
+            self.detach(world);
+            self.target = Some(target);
+            self.attach(world);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.inner.is_some());
+        self.detach(world);
+        let inner = self.inner.take().unwrap();
+        self.view.lock().unwrap().raze(inner, world);
+        // Views without a tracking scope don't despawn their own view entity.
+        if let Some(entt) = world.get_entity_mut(inner) {
+            entt.despawn_recursive();
+        }
+        self.target = None;
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        // Changes to the content are handled here, rather than propagated to the parent.
+        self.attach(world);
+        true
+    }
+}
+
+impl IntoView for Portal {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, Element, ForIndex, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Items(Vec<u32>);
+
+    fn child_count(world: &TestWorld, entity: Entity) -> usize {
+        world
+            .world()
+            .get::<Children>(entity)
+            .map_or(0, |children| children.len())
+    }
+
+    #[test]
+    fn test_portal() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Items(vec![1]));
+        let target = world.world_mut().spawn(NodeBundle::default()).id();
+        let root = world.spawn_view(Element::<NodeBundle>::new().children(Portal::new(
+            target,
+            ForIndex::new(
+                |re| re.use_resource::<Items>().0.clone().into_iter(),
+                |item, _| item.to_string(),
+            ),
+        )));
+        world.tick();
+
+        // The content is attached to the target, and not to the logical parent.
+        let element = world.get_node_entities(root)[0];
+        assert_eq!(child_count(&world, element), 0);
+        assert_eq!(child_count(&world, target), 1);
+
+        // Changes to the content are attached to the target.
+        world.world_mut().resource_mut::<Items>().0.push(2);
+        world.tick();
+        assert_eq!(child_count(&world, element), 0);
+        assert_eq!(child_count(&world, target), 2);
+    }
+
+    #[test]
+    fn test_portal_dynamic() {
+        let mut world = TestWorld::new();
+        let first = world.world_mut().spawn(NodeBundle::default()).id();
+        let second = world.world_mut().spawn(NodeBundle::default()).id();
+        let use_second = world.create_mutable(false);
+        let id = use_second.id;
+        world.spawn_view(Portal::new_dynamic(
+            move |re| match re.read_mutable::<bool>(id) {
+                false => first,
+                true => second,
+            },
+            "Content",
+        ));
+        world.tick();
+        assert_eq!(child_count(&world, first), 1);
+        assert_eq!(child_count(&world, second), 0);
+
+        // Changing the target moves the content.
+        world.set_mutable(&use_second, true);
+        world.tick();
+        assert_eq!(child_count(&world, first), 0);
+        assert_eq!(child_count(&world, second), 1);
+    }
+
+    #[test]
+    fn test_portal_raze() {
+        let mut world = TestWorld::new();
+        let target = world.world_mut().spawn(NodeBundle::default()).id();
+        let entities = world.world().entities().len();
+        let root = world.spawn_view(Portal::new(target, ("A", "B")));
+        world.tick();
+        assert_eq!(child_count(&world, target), 2);
+
+        // Razing detaches and despawns the content, and the inner view entity.
+        let view = world.world().get::<ViewHandle>(root).unwrap().view.clone();
+        view.lock().unwrap().raze(root, world.world_mut());
+        assert_eq!(child_count(&world, target), 0);
+        assert_eq!(world.world().entities().len(), entities);
+    }
+}
@@ -1,11 +1,129 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
 
-use bevy::ecs::{entity::Entity, world::World};
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    hierarchy::{BuildWorldChildren, DespawnRecursiveExt},
+};
 
 use crate::{
-    node_span::NodeSpan, Cx, DespawnScopes, IntoView, TrackingScope, View, ViewHandle, ViewRef,
+    hooks::drop_unused_assets,
+    node_span::NodeSpan,
+    suspense::{find_boundary, set_resumed},
+    view::child_nodes,
+    Cx, DespawnScopes, DisplayNodeChanged, IntoView, TrackingScope, View, ViewHandle, ViewRef,
 };
 
+/// A reusable presenter, whose properties are stored in the implementing type. Deriving
+/// [`Presenter`](derive@crate::Presenter) makes the type usable as a view.
+pub trait Presenter: Send + Sync + Sized + 'static {
+    /// Produce the view for this presenter. This is called when the view is built, and again
+    /// whenever the dependencies of the presenter change.
+    fn present(cx: &mut Cx<Self>) -> impl IntoView;
+}
+
+/// View which drives a [`Presenter`]. The output of the presenter is rebuilt each time the
+/// presenter runs, unless it is the same as before: either the same [`ViewRef`], or a value
+/// such as a string which produces an identical view. In that case the output's entities are
+/// kept as they are.
+pub struct PresenterView<P: Presenter> {
+    props: P,
+    /// The built output, its view entity, and its output key.
+    inner: Option<(ViewRef, Entity, Option<u64>)>,
+}
+
+impl<P: Presenter> PresenterView<P> {
+    /// Construct a new `PresenterView` from the presenter's properties.
+    pub fn new(props: P) -> Self {
+        Self { props, inner: None }
+    }
+}
+
+impl<P: Presenter> View for PresenterView<P> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.inner {
+            Some((ref view, entity, _)) => child_nodes(view, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.inner.is_none());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.react(view_entity, world, &mut tracking)
+        }));
+        // The scope is stored even if the presenter panicked, so that an error boundary can
+        // release what the presenter created, and watch the data it read before panicking.
+        world.entity_mut(view_entity).insert(tracking);
+        if let Err(payload) = result {
+            resume_unwind(payload);
+        }
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        // The presenter stays suspended only if it suspends again during this run.
+        clear_suspense(view_entity, world);
+        let pass = tracking.id();
+        let mut cx = Cx::new(&self.props, world, view_entity, tracking);
+        let output = P::present(&mut cx);
+        let key = output.output_key();
+        if let Some((_, _, prev_key)) = self.inner {
+            if key.is_some() && key == prev_key {
+                drop(output);
+                drop_unused_assets(world, view_entity, pass);
+                // The kept output may refer to entities created by the run which built it.
+                tracking.retain_previous_run();
+                return;
+            }
+        }
+        let view = output.into_view();
+        drop_unused_assets(world, view_entity, pass);
+        if let Some((prev, entity, _)) = self.inner.take() {
+            if Arc::ptr_eq(&prev, &view) {
+                self.inner = Some((prev, entity, key));
+                tracking.retain_previous_run();
+                return;
+            }
+            raze_output(&prev, entity, world);
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.inner = Some((view, entity, key));
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity, _)) = self.inner.take() {
+            raze_output(&view, entity, world);
+        }
+        clear_suspense(view_entity, world);
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn debug_name(&self) -> String {
+        let name = std::any::type_name::<P>();
+        let base = name.split('<').next().unwrap_or(name);
+        base.rsplit("::").next().unwrap_or(base).to_string()
+    }
+}
+
+/// Raze the output of a presenter, and despawn its view entity, which views without a
+/// tracking scope leave behind.
+fn raze_output(view: &ViewRef, entity: Entity, world: &mut World) {
+    view.lock().unwrap().raze(entity, world);
+    if let Some(entt) = world.get_entity_mut(entity) {
+        entt.despawn_recursive();
+    }
+}
+
+impl<P: Presenter> IntoView for PresenterView<P> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
 /// A trait that allows methods to be added to presenter function references.
 pub trait PresenterFn<F: 'static>: Sized + Send + Sync + Copy + 'static {
     /// The type of properties expected by this presenter.
@@ -67,19 +185,35 @@ impl<F: 'static, P: PresenterFn<F>> Bind<F, P> {
 }
 
 impl<F: 'static, P: PresenterFn<F>> View for Bind<F, P> {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, _world: &World) -> NodeSpan {
         self.nodes.clone()
     }
 
-    fn build(&mut self, _view_entity: Entity, world: &mut World) {
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.inner.is_none());
+        clear_suspense(view_entity, world);
         let mut tracking = TrackingScope::new(world.change_tick());
-        let mut cx = Cx::new(&self.props, world, &mut tracking);
+        let mut cx = Cx::new(&self.props, world, view_entity, &mut tracking);
         let mut view = self.presenter.call(&mut cx);
-        let inner = world.spawn(tracking).id();
+        let inner = world.spawn_empty().set_parent(view_entity).id();
         view.build(inner, world);
-        self.nodes = view.nodes();
+        self.nodes = view.nodes(world);
         world.entity_mut(inner).insert(ViewHandle::new(view));
+        // The presenter's scope gets an entity of its own, which owns the inner view entity, and
+        // which is in turn owned by the view entity, so that both are despawned on raze without
+        // disturbing any scope already on the view entity.
+        tracking.add_owned(inner);
+        let scope = world.spawn(tracking).id();
+        let tick = world.change_tick();
+        let mut entt = world.entity_mut(view_entity);
+        match entt.get_mut::<TrackingScope>() {
+            Some(mut view_scope) => view_scope.add_owned(scope),
+            None => {
+                let mut view_scope = TrackingScope::new(tick);
+                view_scope.add_owned(scope);
+                entt.insert(view_scope);
+            }
+        }
         self.inner = Some(inner);
     }
 
@@ -92,18 +226,173 @@ impl<F: 'static, P: PresenterFn<F>> View for Bind<F, P> {
             inner.lock().unwrap().raze(entt.id(), world);
         };
         self.inner = None;
+        clear_suspense(view_entity, world);
         world.despawn_owned_recursive(view_entity);
     }
 }
 
+/// A presenter which is rebuilt or razed is no longer suspended.
+fn clear_suspense(view_entity: Entity, world: &mut World) {
+    if let Some(boundary) = find_boundary(world, view_entity) {
+        set_resumed(world, boundary, view_entity);
+    }
+}
+
 impl<F: 'static, P: PresenterFn<F>> IntoView for Bind<F, P> {
     fn into_view(self) -> ViewRef {
         Arc::new(Mutex::new(self))
     }
 }
 
-// impl<Props: 'static, P: PresenterFn<Props = Props>> IntoView for P {
-//     fn into_view(self) -> ViewRef {
-//         todo!()
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{run_reactions, CallbackHandle, ReactiveContext, ReactiveContextMut, TextStatic};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Punctuation(&'static str);
+
+    #[derive(crate::Presenter)]
+    struct Greeting {
+        name: &'static str,
+    }
+
+    impl Presenter for Greeting {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let punctuation = cx.use_resource::<Punctuation>().0;
+            format!("Hello, {}{}", cx.props.name, punctuation)
+        }
+    }
+
+    /// Presenter which creates a mutable each time it runs. It has its own constructor, so the
+    /// derived one is left out.
+    #[derive(crate::Presenter)]
+    #[presenter(no_new)]
+    struct Allocating {
+        source: Entity,
+    }
+
+    impl Allocating {
+        fn new(source: Entity) -> Self {
+            Self { source }
+        }
+    }
+
+    impl Presenter for Allocating {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let value = cx.read_mutable::<i32>(cx.props.source);
+            cx.create_mutable(value);
+            format!("{}", value)
+        }
+    }
+
+    #[test]
+    fn test_rerun_releases_previous_run() {
+        let mut world = crate::testing::TestWorld::new();
+        let source = world.create_mutable(0);
+        world.spawn_view(Allocating::new(source.id));
+        world.set_mutable(&source, 1);
+        world.tick();
+        let entities = world.world().entities().len();
+
+        // Mutables created by earlier runs don't pile up.
+        for value in 2..5 {
+            world.set_mutable(&source, value);
+            world.tick();
+            assert_eq!(world.world().entities().len(), entities);
+        }
+    }
+
+    fn text(world: &World, view: &ViewRef) -> String {
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(world) else {
+            panic!("Expected a single node");
+        };
+        world.get::<Text>(node).unwrap().sections[0].value.clone()
+    }
+
+    #[test]
+    fn test_derive_presenter() {
+        let mut world = World::default();
+        world.insert_resource(Punctuation("!"));
+        let parent = world.spawn_empty().id();
+        let view = Greeting::new(Greeting { name: "World" });
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(text(&world, &view), "Hello, World!");
+
+        // The presenter runs again when its dependencies change.
+        world.clear_trackers();
+        world.resource_mut::<Punctuation>().0 = "?";
+        run_reactions(&mut world);
+        assert_eq!(text(&world, &view), "Hello, World?");
+
+        // The output is kept when it is unchanged.
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(&world) else {
+            panic!("Expected a single node");
+        };
+        let children = world.get::<Children>(entity).unwrap().to_vec();
+        world.entity_mut(entity).remove::<DisplayNodeChanged>();
+        world.clear_trackers();
+        world.resource_mut::<Punctuation>().0 = "?";
+        run_reactions(&mut world);
+        assert_eq!(text(&world, &view), "Hello, World?");
+        assert!(world.get_entity(node).is_some());
+        assert_eq!(world.get::<Children>(entity).unwrap().to_vec(), children);
+        assert!(!world.entity(entity).contains::<DisplayNodeChanged>());
+
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[derive(Component)]
+    struct Marker;
+
+    fn marked(cx: &mut Cx) -> TextStatic {
+        let entity = cx.entity();
+        cx.world_mut().entity_mut(entity).insert(Marker);
+        TextStatic::new("Marked".to_string())
+    }
+
+    #[test]
+    fn test_entity() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = marked.bind(()).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert!(world.entity(entity).contains::<Marker>());
+
+        // The marker is despawned along with the view entity.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(entity).is_none());
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 0);
+    }
+
+    #[derive(Resource)]
+    struct Registered(CallbackHandle);
+
+    fn with_callback(cx: &mut Cx) -> TextStatic {
+        let callback = cx.use_callback(|| {});
+        cx.world_mut().insert_resource(Registered(callback));
+        TextStatic::new("Callback".to_string())
+    }
+
+    #[test]
+    fn test_bind_scope() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let entities = world.entities().len();
+        let view = with_callback.bind(()).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let callback = world.resource::<Registered>().0;
+
+        // The presenter's scope lives on an entity of its own.
+        assert_eq!(world.query::<&TrackingScope>().iter(&world).count(), 2);
+
+        // Razing despawns the scope and inner entities, and unregisters the callback.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.run_system(callback.id).is_err());
+        assert_eq!(world.entities().len(), entities);
+    }
+}
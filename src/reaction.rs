@@ -1,6 +1,10 @@
 use std::sync::{Arc, Mutex};
 
-use bevy::ecs::{component::Component, entity::Entity, world::World};
+use bevy::ecs::{
+    component::{Component, Tick},
+    entity::Entity,
+    world::World,
+};
 
 use crate::scope::TrackingScope;
 
@@ -22,8 +26,38 @@ pub trait Reaction {
 
     /// Release any resources used by the reaction.
     fn cleanup(&mut self, _owner: Entity, _world: &mut World) {}
+
+    /// Returns the entities which the reaction writes to, if the reaction can be split into a
+    /// read-only [`compute`](Reaction::compute) step and a deferred write. When parallelism is
+    /// enabled with [`ReactorPlugin::with_parallelism`](crate::ReactorPlugin::with_parallelism),
+    /// reactions whose write sets don't overlap are computed concurrently. The default, `None`,
+    /// means that the reaction is always run on its own, via [`react`](Reaction::react).
+    fn write_set(&self) -> Option<Vec<Entity>> {
+        None
+    }
+
+    /// The read-only step of a reaction which has a [`write_set`](Reaction::write_set). The
+    /// returned write is applied once the other reactions in the same wave have been computed,
+    /// and must only modify the entities in the write set. Since this may run on another
+    /// thread, it must not read non-send resources.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics, since it is never called for reactions without a
+    /// write set.
+    fn compute(
+        &mut self,
+        _owner: Entity,
+        _world: &World,
+        _tracking: &mut TrackingScope,
+    ) -> ReactionWrite {
+        panic!("Reaction::compute called on a reaction without a write set");
+    }
 }
 
+/// A deferred write returned by [`Reaction::compute`].
+pub type ReactionWrite = Box<dyn FnOnce(&mut World) + Send>;
+
 /// A reference to a reaction.
 pub type ReactionRef = Arc<Mutex<dyn Reaction + Sync + Send + 'static>>;
 
@@ -37,4 +71,218 @@ impl ReactionHandle {
     pub fn new(view: impl Reaction + Sync + Send + 'static) -> Self {
         Self(Arc::new(Mutex::new(view)))
     }
+
+    /// Spawn an entity for a reaction, and run the reaction once so that its dependencies are
+    /// tracked. Returns a [`ReactionId`] which can be used to control the reaction.
+    pub fn spawn(
+        mut reaction: impl Reaction + Sync + Send + 'static,
+        world: &mut World,
+    ) -> ReactionId {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let entity = world.spawn_empty().id();
+        reaction.react(entity, world, &mut scope);
+        let tick = world.change_tick();
+        world.entity_mut(entity).insert((
+            ReactionHandle::new(reaction),
+            scope,
+            ReactionRunTick(tick),
+        ));
+        ReactionId(entity)
+    }
+}
+
+/// Returns the tracking scope of a reaction, panicking with a descriptive message if it is
+/// missing.
+fn tracking_scope(reaction: Entity, world: &World) -> &TrackingScope {
+    world
+        .get_entity(reaction)
+        .unwrap_or_else(|| panic!("Reaction {:?} has been despawned", reaction))
+        .get::<TrackingScope>()
+        .unwrap_or_else(|| panic!("Reaction {:?} has no TrackingScope", reaction))
+}
+
+/// Identifies the entity of a reaction, and allows the reaction to be controlled from systems
+/// which run outside of the reactor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReactionId(pub Entity);
+
+impl ReactionId {
+    /// Returns the entity which holds the reaction and its [`TrackingScope`].
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+
+    /// Returns the total number of dependencies of the reaction, as of the last time it ran.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reaction has been despawned, or has no [`TrackingScope`].
+    pub fn dependency_count(&self, world: &World) -> usize {
+        tracking_scope(self.0, world).dependency_count()
+    }
+
+    /// Returns the change tick at which the reaction last ran, as recorded in its
+    /// [`ReactionRunTick`] component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reaction has been despawned, or has no [`ReactionRunTick`].
+    pub fn last_run_tick(&self, world: &World) -> Tick {
+        world
+            .get_entity(self.0)
+            .unwrap_or_else(|| panic!("Reaction {:?} has been despawned", self.0))
+            .get::<ReactionRunTick>()
+            .unwrap_or_else(|| panic!("Reaction {:?} has no ReactionRunTick", self.0))
+            .0
+    }
+
+    /// Force the reaction to run during the next reaction pass, even if none of its
+    /// dependencies have changed.
+    pub fn trigger(&self, world: &mut World) {
+        world.entity_mut(self.0).insert(ReactionTriggered);
+    }
+
+    /// If the reaction is throttled, and has a change which is waiting for the throttle
+    /// interval to elapse, run it during the next reaction pass.
+    pub fn flush(&self, world: &mut World) {
+        if tracking_scope(self.0, world).throttle_waiting(world) {
+            world.entity_mut(self.0).insert(ReactionTriggered);
+        }
+    }
+
+    /// Pause the reaction. Paused reactions are not run, even if their dependencies change.
+    pub fn pause(&self, world: &mut World) {
+        world.entity_mut(self.0).insert(ReactionPaused);
+    }
+
+    /// Resume a paused reaction. Since changes to dependencies may have been missed while
+    /// paused, the reaction is run during the next reaction pass.
+    pub fn resume(&self, world: &mut World) {
+        world
+            .entity_mut(self.0)
+            .remove::<ReactionPaused>()
+            .insert(ReactionTriggered);
+    }
+
+    /// Returns true if the reaction is paused.
+    pub fn is_paused(&self, world: &World) -> bool {
+        world.get::<ReactionPaused>(self.0).is_some()
+    }
+}
+
+/// Component which records the change tick at which a reaction last ran. It is inserted by
+/// [`ReactionHandle::spawn`], and updated each time the reaction runs.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ReactionRunTick(pub Tick);
+
+/// Marker component which prevents a reaction from running.
+#[derive(Component)]
+pub struct ReactionPaused;
+
+/// Marker component which causes a reaction to run regardless of whether its dependencies
+/// have changed.
+#[derive(Component)]
+pub struct ReactionTriggered;
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::Resource;
+
+    use crate::{run_reactions, testing::TestWorld, Rcx, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Speed(u32);
+
+    struct ReadTwo {
+        mutable: Entity,
+    }
+
+    impl Reaction for ReadTwo {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let re = Rcx::new(world, tracking);
+            re.read_mutable::<u32>(self.mutable);
+            re.use_resource::<Speed>();
+        }
+    }
+
+    fn spawn_reaction(tw: &mut TestWorld) -> ReactionId {
+        let mutable = tw.create_mutable(0u32);
+        let world = tw.world_mut();
+        world.insert_resource(Speed(1));
+        ReactionHandle::spawn(
+            ReadTwo {
+                mutable: mutable.id,
+            },
+            world,
+        )
+    }
+
+    #[test]
+    fn test_introspection() {
+        let mut tw = TestWorld::new();
+        let reaction = spawn_reaction(&mut tw);
+        let world = tw.world_mut();
+        assert!(world.get::<ReactionHandle>(reaction.entity()).is_some());
+        assert_eq!(reaction.dependency_count(world), 2);
+
+        let before = reaction.last_run_tick(world);
+        world.increment_change_tick();
+        world.resource_mut::<Speed>().0 = 2;
+        run_reactions(world);
+        let after = reaction.last_run_tick(world);
+        assert!(after.get() > before.get());
+        assert_eq!(reaction.dependency_count(world), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "has been despawned")]
+    fn test_despawned() {
+        let mut tw = TestWorld::new();
+        let reaction = spawn_reaction(&mut tw);
+        let world = tw.world_mut();
+        world.despawn(reaction.entity());
+        reaction.dependency_count(world);
+    }
+
+    struct CountRuns(Arc<Mutex<usize>>);
+
+    impl Reaction for CountRuns {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            Rcx::new(world, tracking).use_resource::<Speed>();
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    #[test]
+    fn test_trigger_pause_resume() {
+        let mut world = World::default();
+        world.insert_resource(Speed(1));
+        let runs = Arc::new(Mutex::new(0));
+        let reaction = ReactionHandle::spawn(CountRuns(runs.clone()), &mut world);
+        assert_eq!(*runs.lock().unwrap(), 1);
+
+        // A triggered reaction runs even though nothing changed, and only once.
+        world.clear_trackers();
+        reaction.trigger(&mut world);
+        run_reactions(&mut world);
+        world.clear_trackers();
+        run_reactions(&mut world);
+        assert_eq!(*runs.lock().unwrap(), 2);
+
+        // A paused reaction does not run when its dependencies change.
+        reaction.pause(&mut world);
+        assert!(reaction.is_paused(&world));
+        world.clear_trackers();
+        world.resource_mut::<Speed>().0 = 2;
+        run_reactions(&mut world);
+        assert_eq!(*runs.lock().unwrap(), 2);
+
+        // Resuming runs the reaction on the next pass.
+        reaction.resume(&mut world);
+        assert!(!reaction.is_paused(&world));
+        run_reactions(&mut world);
+        assert_eq!(*runs.lock().unwrap(), 3);
+    }
 }
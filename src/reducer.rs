@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{
+    mutable::{after_write, MutableValue, MutableValueNext},
+    Mutable, ReactiveContext,
+};
+
+type ApplyFn<A> = Arc<dyn Fn(&mut World, A) + Send + Sync>;
+
+/// A read handle for state managed by a reducer, created via
+/// [`Cx::use_reducer`](crate::Cx::use_reducer).
+pub struct ReducerState<S> {
+    pub(crate) mutable: Mutable<S>,
+}
+
+impl<S: Clone + Send + Sync + 'static> ReducerState<S> {
+    /// Read the current state. Calling this function adds the state to the current tracking
+    /// scope.
+    pub fn get<'p, R: ReactiveContext<'p>>(&self, rc: &R) -> S {
+        rc.read_mutable_clone(self.mutable.id)
+    }
+}
+
+/// A handle which dispatches actions to a reducer. Dispatch handles are cheap to clone, and
+/// can be captured in callbacks.
+pub struct Dispatch<A> {
+    pub(crate) apply: ApplyFn<A>,
+}
+
+impl<A> Dispatch<A> {
+    /// Apply an action to the reducer state. Like writes to a [`Mutable`], the new state is
+    /// visible on the next update.
+    pub fn dispatch(&self, world: &mut World, action: A) {
+        (self.apply)(world, action)
+    }
+}
+
+impl<A> Clone for Dispatch<A> {
+    fn clone(&self) -> Self {
+        Self {
+            apply: self.apply.clone(),
+        }
+    }
+}
+
+/// Compute the next state of a reducer, and write it to the mutable which holds the state.
+pub(crate) fn apply_reducer<S: PartialEq + Clone + Send + Sync + 'static, A>(
+    world: &mut World,
+    mutable: Entity,
+    reducer: fn(S, A) -> S,
+    action: A,
+) {
+    let mut entt = world.entity_mut(mutable);
+    // Actions are applied on top of any pending state, so that several actions can be
+    // dispatched in the same frame.
+    let (current, pending) = match entt.get::<MutableValueNext>() {
+        Some(next) => (next.0.downcast_ref::<S>().unwrap().clone(), true),
+        None => (
+            entt.get::<MutableValue>()
+                .unwrap()
+                .value
+                .downcast_ref::<S>()
+                .unwrap()
+                .clone(),
+            false,
+        ),
+    };
+    let next = reducer(current.clone(), action);
+    if pending || next != current {
+        entt.insert(MutableValueNext(Box::new(next)));
+    }
+    after_write(world, mutable);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, Cx, IntoView, Presenter, ReactiveContextMut};
+
+    use super::*;
+
+    enum Action {
+        Increment,
+        Decrement,
+        Reset,
+    }
+
+    fn counter(state: i32, action: Action) -> i32 {
+        match action {
+            Action::Increment => state + 1,
+            Action::Decrement => state - 1,
+            Action::Reset => 0,
+        }
+    }
+
+    /// The dispatch handle from the first run of the presenter, and the number of runs.
+    #[derive(Resource, Default)]
+    struct Counts {
+        dispatch: Option<Dispatch<Action>>,
+        runs: u32,
+    }
+
+    #[derive(crate::Presenter)]
+    struct Counter;
+
+    impl Presenter for Counter {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let (state, dispatch) = cx.use_reducer(5, counter);
+            let mut counts = cx.world_mut().resource_mut::<Counts>();
+            counts.runs += 1;
+            counts.dispatch.get_or_insert(dispatch);
+            format!("{}", state.get(cx))
+        }
+    }
+
+    #[test]
+    fn test_reducer() {
+        let mut world = TestWorld::new();
+        world.world_mut().init_resource::<Counts>();
+        let view = world.spawn_view(Counter);
+        world.tick();
+        let text = |world: &TestWorld| {
+            let node = world.get_node_entities(view)[0];
+            world.world().get::<Text>(node).unwrap().sections[0]
+                .value
+                .clone()
+        };
+        assert_eq!(text(&world), "5");
+        assert_eq!(world.world().resource::<Counts>().runs, 1);
+
+        // Both actions are applied, and the presenter reacts once to the new state.
+        let dispatch = world.world().resource::<Counts>().dispatch.clone().unwrap();
+        dispatch.dispatch(world.world_mut(), Action::Increment);
+        dispatch.dispatch(world.world_mut(), Action::Increment);
+        world.tick();
+        assert_eq!(text(&world), "7");
+        assert_eq!(world.world().resource::<Counts>().runs, 2);
+
+        // The state survives the re-run, and the handle from the first run still works.
+        dispatch.dispatch(world.world_mut(), Action::Decrement);
+        world.tick();
+        assert_eq!(text(&world), "6");
+        dispatch.dispatch(world.world_mut(), Action::Reset);
+        world.tick();
+        assert_eq!(text(&world), "0");
+        assert_eq!(world.world().resource::<Counts>().runs, 4);
+    }
+}
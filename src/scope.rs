@@ -1,102 +1,549 @@
-use std::{marker::PhantomData, sync::atomic::Ordering};
+use std::{
+    collections::BTreeSet,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use bevy::{
-    ecs::component::{ComponentId, Tick},
+    asset::{LoadState, UntypedAssetId},
+    core::FrameCount,
+    ecs::{
+        component::{ComponentId, Tick},
+        query::{QueryState, ReadOnlyWorldQuery},
+        world::WorldId,
+    },
     prelude::*,
+    tasks::{ComputeTaskPool, ParallelSlice},
     utils::{HashMap, HashSet},
 };
 
-use crate::{mutable::MutableValue, reaction::ReactionHandle, ViewHandle};
+use crate::{
+    diagnostics::{reaction_label, ReactorDiagnostics, ReactorFrameCounts},
+    error_boundary::forward_panic,
+    lifecycle::run_unmount_callbacks,
+    mutable::{MutableValue, MutableValueNext},
+    reaction::{
+        ReactionHandle, ReactionPaused, ReactionRef, ReactionRunTick, ReactionTriggered,
+        ReactionWrite,
+    },
+    DisplayNodeChanged, ReactorPaused, ReactorSettings, ViewBuildFailed, ViewHandle, ViewRoot,
+    WorldRegistry,
+};
+
+/// A function which releases a resource held by a [`TrackingScope`].
+type CleanupFn = Box<dyn FnOnce(&mut World) + Send + Sync + 'static>;
 
 /// A component that tracks the dependencies of a reactive task.
 #[derive(Component)]
 pub struct TrackingScope {
+    /// Unique id of this scope. Each run of a reaction has a new scope, and therefore a new id.
+    id: u64,
+
     /// List of scopes that are owned by this scope.
     owned: Vec<Entity>,
 
+    /// Entities owned by the most recent run of the reaction, which are released when a later
+    /// run replaces them. Unlike `owned`, these are not kept until the scope is despawned.
+    run_owned: Vec<Entity>,
+
+    /// Entities registered once by hooks, which are kept until the scope is despawned even if
+    /// they were added by a later run.
+    hook_owned: Vec<Entity>,
+
+    /// Cleanup functions registered once by hooks, which are run when the scope is despawned.
+    hook_cleanups: Vec<CleanupFn>,
+
+    /// Whether the run which produced this scope kept the output of the previous run, so that
+    /// the previous run's entities must be kept rather than this run's.
+    retain_previous_run: bool,
+
     /// The set of mutables that this scope is subscribed to.
     mutable_deps: HashSet<Entity>,
 
     /// Set of components that we are currently subscribed to.
     component_deps: HashSet<(Entity, ComponentId)>,
 
+    /// Set of components which were absent, and which we want to know if they are added.
+    added_component_deps: HashSet<(Entity, ComponentId)>,
+
+    /// Set of components which were present, and which we want to know if they are removed.
+    removed_component_deps: HashSet<(Entity, ComponentId)>,
+
     /// Set of resources that we are currently subscribed to.
     resource_deps: HashMap<ComponentId, Box<dyn AnyResource>>,
 
+    /// List of queries whose set of matching entities we are subscribed to.
+    query_deps: Vec<Box<dyn AnyQuery>>,
+
+    /// List of mutable projections whose values we are subscribed to.
+    projection_deps: Vec<Box<dyn AnyProjection>>,
+
+    /// List of keys in reactive maps that we are subscribed to.
+    map_key_deps: Vec<Box<dyn AnyMapKey>>,
+
+    /// List of frame intervals that we are subscribed to, along with the frame count at the
+    /// time of subscription.
+    frame_deps: Vec<(u64, u64)>,
+
+    /// List of resources in secondary worlds that we are subscribed to.
+    secondary_resource_deps: Vec<TrackedSecondaryResource>,
+
+    /// List of assets whose load state we are subscribed to, along with the load state at the
+    /// time of subscription.
+    load_state_deps: Vec<(UntypedAssetId, LoadState)>,
+
+    /// Scopes of values memoized by [`Cx::use_computed`](crate::Cx::use_computed), whose
+    /// dependencies are also dependencies of this scope.
+    nested_deps: Vec<Arc<TrackingScope>>,
+
     /// Engine tick used for determining if components have changed. This represents the
     /// time of the previous reaction.
     tick: Tick,
-    // contexts
-    // debug_name
-    // cleanups
+
+    /// Minimum interval between reactions, used to throttle high-frequency updates.
+    throttle: Option<Duration>,
+
+    /// Elapsed time at the previous reaction, used for throttling.
+    last_run: Option<Duration>,
+
+    /// Whether a reaction was suppressed by the throttle, and is still waiting to be run.
+    throttle_pending: AtomicBool,
 }
 
 impl TrackingScope {
+    /// Construct a new, empty `TrackingScope` for a reaction which runs at the given tick.
     pub fn new(tick: Tick) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
         Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             owned: Vec::new(),
+            run_owned: Vec::new(),
+            hook_owned: Vec::new(),
+            hook_cleanups: Vec::new(),
+            retain_previous_run: false,
             mutable_deps: HashSet::default(),
             component_deps: HashSet::default(),
+            added_component_deps: HashSet::default(),
+            removed_component_deps: HashSet::default(),
             resource_deps: HashMap::default(),
+            query_deps: Vec::new(),
+            projection_deps: Vec::new(),
+            map_key_deps: Vec::new(),
+            frame_deps: Vec::new(),
+            secondary_resource_deps: Vec::new(),
+            load_state_deps: Vec::new(),
+            nested_deps: Vec::new(),
             tick,
+            throttle: None,
+            last_run: None,
+            throttle_pending: AtomicBool::new(false),
         }
     }
 
+    /// Returns the unique id of this scope.
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the change tick at which the reaction last ran. While a view or reaction is
+    /// running, the scope stored on its entity is still the previous one, so views can use
+    /// this to find out how stale their last update is.
+    ///
+    /// ```
+    /// # use bevy::{ecs::component::Tick, prelude::*};
+    /// # use bevy_reactor::*;
+    /// # use std::sync::{Arc, Mutex};
+    /// /// Records the tick of the previous run each time it reacts.
+    /// struct Staleness(Arc<Mutex<Vec<Option<Tick>>>>);
+    ///
+    /// impl Reaction for Staleness {
+    ///     fn react(&mut self, owner: Entity, world: &mut World, _tracking: &mut TrackingScope) {
+    ///         let previous = world.get::<TrackingScope>(owner).map(|scope| scope.tick());
+    ///         self.0.lock().unwrap().push(previous);
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::default();
+    /// let ticks = Arc::new(Mutex::new(Vec::new()));
+    /// let reaction = ReactionHandle::spawn(Staleness(ticks.clone()), &mut world);
+    /// let first_run = reaction.last_run_tick(&world);
+    /// world.increment_change_tick();
+    /// reaction.trigger(&mut world);
+    /// run_reactions(&mut world);
+    ///
+    /// // The first run has no previous scope, and the second sees the tick of the first.
+    /// assert_eq!(*ticks.lock().unwrap(), vec![None, Some(first_run)]);
+    /// ```
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
     pub(crate) fn add_owned(&mut self, owned: Entity) {
         self.owned.push(owned);
     }
 
+    /// Add an entity which is created once by a hook and reused by later runs, so it is kept
+    /// until the scope is despawned rather than released by the next run.
+    pub(crate) fn add_hook_owned(&mut self, owned: Entity) {
+        self.hook_owned.push(owned);
+    }
+
+    /// Add a cleanup function for a resource which is created once by a hook. Like
+    /// [`add_hook_owned`](Self::add_hook_owned), it is run when the scope is despawned.
+    pub(crate) fn add_hook_cleanup(
+        &mut self,
+        cleanup: impl FnOnce(&mut World) + Send + Sync + 'static,
+    ) {
+        self.hook_cleanups.push(Box::new(cleanup));
+    }
+
+    /// Move the entities of `other`, a scope used to start something which is created once by
+    /// a hook, into this scope as if they had been added by the hook.
+    pub(crate) fn take_hook_owned(&mut self, other: &mut Self) {
+        self.hook_owned.append(&mut other.owned);
+    }
+
+    /// Mark this run as having kept the output of the previous run, for example a presenter
+    /// whose output is unchanged. The entities of the previous run are then kept,
+    /// and those of this run are released instead.
+    pub(crate) fn retain_previous_run(&mut self) {
+        self.retain_previous_run = true;
+    }
+
+    /// Set the minimum interval between reactions for this scope.
+    pub(crate) fn set_throttle(&mut self, interval: Duration) {
+        self.throttle = Some(interval);
+    }
+
+    /// Returns true if the throttle interval has not yet elapsed since the previous reaction.
+    fn is_throttled(&self, now: Option<Duration>) -> bool {
+        match (self.throttle, self.last_run, now) {
+            (Some(interval), Some(last_run), Some(now)) => now.saturating_sub(last_run) < interval,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this scope is throttled, and has a change which is waiting to be reacted
+    /// to.
+    pub(crate) fn throttle_waiting(&self, world: &World) -> bool {
+        self.throttle.is_some()
+            && (self.throttle_pending.load(Ordering::Relaxed) || self.dependencies_changed(world))
+    }
+
     pub(crate) fn add_mutable(&mut self, mutable: Entity) {
         self.mutable_deps.insert(mutable);
     }
 
+    pub(crate) fn add_component(&mut self, entity: Entity, component: ComponentId) {
+        self.component_deps.insert((entity, component));
+    }
+
+    /// Subscribe to the addition or removal of a component, depending on whether it is
+    /// currently present.
+    pub(crate) fn add_component_presence(
+        &mut self,
+        entity: Entity,
+        component: ComponentId,
+        present: bool,
+    ) {
+        if present {
+            self.removed_component_deps.insert((entity, component));
+        } else {
+            self.added_component_deps.insert((entity, component));
+        }
+    }
+
+    pub(crate) fn add_query<F: ReadOnlyWorldQuery + 'static>(
+        &mut self,
+        query: QueryState<Entity, F>,
+        entities: Vec<Entity>,
+    ) {
+        self.query_deps.push(Box::new(TrackedQuery {
+            query: Mutex::new(query),
+            entities,
+        }));
+    }
+
+    pub(crate) fn add_world_query<Q: ReadOnlyWorldQuery + 'static>(
+        &mut self,
+        query: QueryState<Entity, Q>,
+        entities: Vec<Entity>,
+        components: Vec<ComponentId>,
+    ) {
+        self.query_deps.push(Box::new(TrackedWorldQuery {
+            query: Mutex::new(query),
+            entities,
+            components,
+        }));
+    }
+
+    /// Subscribe to a projection of a mutable, which only reacts when the projected value
+    /// changes.
+    pub(crate) fn add_projection<T: Send + Sync + 'static, U: PartialEq + Send + Sync + 'static>(
+        &mut self,
+        projection: TrackedProjection<T, U>,
+    ) {
+        self.projection_deps.push(Box::new(projection));
+    }
+
+    /// Subscribe to a single key of a reactive map.
+    pub(crate) fn add_map_key(&mut self, key: impl AnyMapKey + 'static) {
+        self.map_key_deps.push(Box::new(key));
+    }
+
+    /// Subscribe to the frame count, reacting once every `interval` frames.
+    pub(crate) fn add_frame_interval(&mut self, interval: u64, frame: u64) {
+        self.frame_deps.push((interval.max(1), frame));
+    }
+
     pub(crate) fn add_resource<T: Resource>(&mut self, resource_id: ComponentId) {
         self.resource_deps
             .entry(resource_id)
             .or_insert_with(|| Box::new(TrackedResource::<T>::new()));
     }
 
+    /// Subscribe to a non-send resource.
+    pub(crate) fn add_non_send_resource(&mut self, resource_id: ComponentId) {
+        self.resource_deps
+            .entry(resource_id)
+            .or_insert_with(|| Box::new(TrackedNonSendResource { id: resource_id }));
+    }
+
+    /// Subscribe to a resource in a secondary world.
+    pub(crate) fn add_secondary_resource(&mut self, world: &World, resource_id: ComponentId) {
+        let world_id = world.id();
+        if self
+            .secondary_resource_deps
+            .iter()
+            .any(|dep| dep.world == world_id && dep.resource == resource_id)
+        {
+            return;
+        }
+        // Secondary worlds have their own change ticks, so record the tick of the secondary
+        // world, advancing it so that later changes are distinguishable from earlier ones.
+        self.secondary_resource_deps.push(TrackedSecondaryResource {
+            world: world_id,
+            resource: resource_id,
+            tick: world.increment_change_tick(),
+        });
+    }
+
+    /// Add the dependencies of a nested scope, so that this scope reacts when they change.
+    pub(crate) fn add_nested(&mut self, scope: Arc<TrackingScope>) {
+        self.nested_deps.push(scope);
+    }
+
+    /// Subscribe to the load state of an asset. The scope reacts when the [`AssetServer`]
+    /// reports a different state, including when the load fails, which does not otherwise
+    /// change `Assets<A>`.
+    pub(crate) fn add_load_state(&mut self, id: UntypedAssetId, state: LoadState) {
+        if !self.load_state_deps.iter().any(|(dep, _)| *dep == id) {
+            self.load_state_deps.push((id, state));
+        }
+    }
+
+    /// Returns the ids of the resources that this scope depends on.
+    pub fn debug_resources(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.resource_deps.keys().copied()
+    }
+
+    /// Returns the entities of the mutables that this scope depends on.
+    pub fn debug_mutables(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.mutable_deps.iter().copied()
+    }
+
+    /// Returns the entity components that this scope depends on.
+    pub fn debug_components(&self) -> impl Iterator<Item = (Entity, ComponentId)> + '_ {
+        self.component_deps.iter().copied()
+    }
+
+    /// Returns the total number of dependencies of this scope, of all kinds.
+    pub fn dependency_count(&self) -> usize {
+        self.mutable_deps.len()
+            + self.component_deps.len()
+            + self.added_component_deps.len()
+            + self.removed_component_deps.len()
+            + self.resource_deps.len()
+            + self.query_deps.len()
+            + self.projection_deps.len()
+            + self.map_key_deps.len()
+            + self.frame_deps.len()
+            + self.secondary_resource_deps.len()
+            + self.load_state_deps.len()
+            + self
+                .nested_deps
+                .iter()
+                .map(|scope| scope.dependency_count())
+                .sum::<usize>()
+    }
+
+    /// Adds the entities which this scope reads from to `entities`. Returns false if the scope
+    /// may read from any entity, which is the case when it has query dependencies.
+    pub(crate) fn read_entities(&self, entities: &mut HashSet<Entity>) -> bool {
+        entities.extend(self.mutable_deps.iter().copied());
+        entities.extend(
+            self.component_deps
+                .iter()
+                .chain(self.added_component_deps.iter())
+                .chain(self.removed_component_deps.iter())
+                .map(|(e, _)| *e),
+        );
+        entities.extend(self.projection_deps.iter().map(|dep| dep.source()));
+        entities.extend(self.map_key_deps.iter().map(|dep| dep.source()));
+        self.query_deps.is_empty()
+            && self
+                .nested_deps
+                .iter()
+                .all(|scope| scope.read_entities(entities))
+    }
+
     /// Returns true if any of the dependencies of this scope have been updated since
     /// the previous reaction.
-    fn dependencies_changed(&self, world: &World) -> bool {
+    pub(crate) fn dependencies_changed(&self, world: &World) -> bool {
         self.mutable_deps.iter().any(|m| {
             world
-                .entity(*m)
-                .get::<MutableValue>()
-                .map(|m| m.changed.load(Ordering::Relaxed))
+                .get_entity(*m)
+                .and_then(|entt| entt.get_change_ticks::<MutableValue>())
+                .map(|ticks| ticks.is_changed(self.tick, world.read_change_tick()))
+                .unwrap_or(false)
+        }) || self.component_deps.iter().any(|(e, c)| {
+            world
+                .get_entity(*e)
+                .and_then(|entt| entt.get_change_ticks_by_id(*c))
+                .map(|ticks| ticks.is_changed(self.tick, world.read_change_tick()))
                 .unwrap_or(false)
-        }) || self.resource_deps.iter().any(|(_, c)| c.is_changed(world))
+        }) || self.added_component_deps.iter().any(|(e, c)| {
+            world
+                .get_entity(*e)
+                .map(|entt| entt.contains_id(*c))
+                .unwrap_or(false)
+        }) || self.removed_component_deps.iter().any(|(e, c)| {
+            world
+                .get_entity(*e)
+                .map(|entt| !entt.contains_id(*c))
+                .unwrap_or(true)
+        }) || self
+            .resource_deps
+            .iter()
+            .any(|(_, c)| c.is_changed(world, self.tick))
+            || self
+                .query_deps
+                .iter()
+                .any(|q| q.is_changed(world, self.tick))
+            || self
+                .projection_deps
+                .iter()
+                .any(|p| p.is_changed(world, self.tick))
+            || self
+                .map_key_deps
+                .iter()
+                .any(|k| k.is_changed(world, self.tick))
+            || self.frame_deps.iter().any(|(interval, frame)| {
+                let current = world
+                    .get_resource::<FrameCount>()
+                    .map(|count| count.0 as u64)
+                    .unwrap_or(0);
+                current / interval != frame / interval
+            })
+            || self
+                .secondary_resource_deps
+                .iter()
+                .any(|dep| dep.is_changed(world))
+            || self.load_state_deps.iter().any(|(id, state)| {
+                world
+                    .get_resource::<AssetServer>()
+                    .map(|server| server.get_load_state(*id).unwrap_or(LoadState::NotLoaded))
+                    .is_some_and(|current| current != *state)
+            })
+            || self
+                .nested_deps
+                .iter()
+                .any(|scope| scope.dependencies_changed(world))
     }
 
     /// Take the dependencies from another scope. Typically the other scope is a temporary
-    /// scope that is used to compute the next set of dependencies.
+    /// scope that is used to compute the next set of dependencies. The dependencies of this
+    /// scope are replaced rather than merged, so that a reaction which reads different data
+    /// depending on some condition only reacts to the data it read most recently.
+    ///
+    /// Entities which were added to `other` replace those of the previous run, which are moved
+    /// to `other` so that the caller can release them with
+    /// [`release_scope`](DespawnScopes::release_scope). If `other` retained the previous run,
+    /// its own entities are left in it to be released instead. Those added when
+    /// the scope was first built, or registered by hooks, are kept until the scope is despawned.
     pub(crate) fn take_deps(&mut self, other: &mut Self) {
+        self.hook_owned.append(&mut other.hook_owned);
+        self.hook_cleanups.append(&mut other.hook_cleanups);
+        if !other.retain_previous_run {
+            std::mem::swap(&mut self.run_owned, &mut other.owned);
+        }
+        self.take_subscriptions(other);
+        self.throttle = other.throttle;
+    }
+
+    /// Replace the dependencies of this scope with those of another scope, leaving the
+    /// entities and cleanups owned by either scope where they are.
+    pub(crate) fn take_subscriptions(&mut self, other: &mut Self) {
         self.mutable_deps = std::mem::take(&mut other.mutable_deps);
         self.component_deps = std::mem::take(&mut other.component_deps);
+        self.added_component_deps = std::mem::take(&mut other.added_component_deps);
+        self.removed_component_deps = std::mem::take(&mut other.removed_component_deps);
         self.resource_deps = std::mem::take(&mut other.resource_deps);
+        self.query_deps = std::mem::take(&mut other.query_deps);
+        self.projection_deps = std::mem::take(&mut other.projection_deps);
+        self.map_key_deps = std::mem::take(&mut other.map_key_deps);
+        self.frame_deps = std::mem::take(&mut other.frame_deps);
+        self.secondary_resource_deps = std::mem::take(&mut other.secondary_resource_deps);
+        self.load_state_deps = std::mem::take(&mut other.load_state_deps);
+        self.nested_deps = std::mem::take(&mut other.nested_deps);
     }
 }
 
 pub(crate) trait DespawnScopes {
     fn despawn_owned_recursive(&mut self, scope_entity: Entity);
+
+    /// Release the entities and cleanups held by a scope which is not stored on an entity.
+    fn release_scope(&mut self, scope: TrackingScope);
 }
 
 impl DespawnScopes for World {
     fn despawn_owned_recursive(&mut self, scope_entity: Entity) {
-        let mut entt = self.entity_mut(scope_entity);
-        let Some(mut scope) = entt.get_mut::<TrackingScope>() else {
+        run_unmount_callbacks(scope_entity, self);
+        // The entity may already have been despawned, e.g. a bundle producer's reaction whose
+        // target was despawned.
+        let Some(mut entt) = self.get_entity_mut(scope_entity) else {
+            return;
+        };
+        let Some(scope) = entt.take::<TrackingScope>() else {
             return;
         };
-        let owned_list = std::mem::take(&mut scope.owned);
         entt.despawn();
-        for owned in owned_list {
-            self.despawn_owned_recursive(owned);
+        self.release_scope(scope);
+    }
+
+    fn release_scope(&mut self, mut scope: TrackingScope) {
+        for cleanup in scope.hook_cleanups.drain(..) {
+            cleanup(self);
+        }
+        let owned_list = scope.owned.drain(..).chain(scope.run_owned.drain(..));
+        for owned in owned_list.chain(scope.hook_owned.drain(..)) {
+            // Owned entities without a scope, such as mutables, are simply despawned.
+            match self.get_entity_mut(owned) {
+                Some(entt) if !entt.contains::<TrackingScope>() => entt.despawn(),
+                _ => self.despawn_owned_recursive(owned),
+            }
         }
     }
 }
 
 pub trait AnyResource: Send + Sync {
-    fn is_changed(&self, world: &World) -> bool;
+    /// Returns true if the resource was changed after the `since` tick.
+    fn is_changed(&self, world: &World, since: Tick) -> bool;
 }
 
 #[derive(PartialEq, Eq)]
@@ -116,43 +563,1894 @@ impl<T> AnyResource for TrackedResource<T>
 where
     T: Resource,
 {
+    fn is_changed(&self, world: &World, since: Tick) -> bool {
+        // Like component dependencies, this is compared with the tick of the scope rather than
+        // the last run of the system, so that writes made by other reactions are seen.
+        world
+            .components()
+            .resource_id::<T>()
+            .and_then(|id| world.storages().resources.get(id))
+            .and_then(|data| data.get_ticks())
+            .map(|ticks| ticks.is_changed(since, world.read_change_tick()))
+            .unwrap_or(false)
+    }
+}
+
+/// Tracks a non-send resource. Unlike [`TrackedResource`], this is not generic over the
+/// resource type, since the type is not required to be `Send` or `Sync`.
+pub(crate) struct TrackedNonSendResource {
+    id: ComponentId,
+}
+
+impl AnyResource for TrackedNonSendResource {
+    fn is_changed(&self, world: &World, since: Tick) -> bool {
+        // Reading the change ticks does not access the resource itself, so this is safe to
+        // do from any thread.
+        world
+            .storages()
+            .non_send_resources
+            .get(self.id)
+            .and_then(|data| data.get_ticks())
+            .map(|ticks| ticks.is_changed(since, world.read_change_tick()))
+            .unwrap_or(false)
+    }
+}
+
+/// Tracks a resource in a secondary world held by the [`WorldRegistry`], along with the
+/// change tick of that world when the resource was read.
+struct TrackedSecondaryResource {
+    world: WorldId,
+    resource: ComponentId,
+    tick: Tick,
+}
+
+impl TrackedSecondaryResource {
+    /// Returns true if the resource has changed since it was read. A resource, or world,
+    /// which has been removed counts as changed.
     fn is_changed(&self, world: &World) -> bool {
-        world.is_resource_changed::<T>()
+        let Some(secondary) = world
+            .get_resource::<WorldRegistry>()
+            .and_then(|registry| registry.get(self.world))
+        else {
+            return true;
+        };
+        secondary
+            .storages()
+            .resources
+            .get(self.resource)
+            .and_then(|data| data.get_ticks())
+            .map(|ticks| ticks.is_changed(self.tick, secondary.read_change_tick()))
+            .unwrap_or(true)
+    }
+}
+
+pub trait AnyQuery: Send + Sync {
+    fn is_changed(&self, world: &World, tick: Tick) -> bool;
+}
+
+/// Tracks the set of entities matched by a query, so that we can detect when entities
+/// start or stop matching.
+pub struct TrackedQuery<F: ReadOnlyWorldQuery + 'static> {
+    query: Mutex<QueryState<Entity, F>>,
+    entities: Vec<Entity>,
+}
+
+impl<F: ReadOnlyWorldQuery + 'static> AnyQuery for TrackedQuery<F> {
+    fn is_changed(&self, world: &World, _tick: Tick) -> bool {
+        let mut query = self.query.lock().unwrap();
+        query.update_archetypes(world);
+        !query.iter_manual(world).eq(self.entities.iter().copied())
+    }
+}
+
+/// Tracks both the set of entities matched by a query, and the components read by the query,
+/// as a single dependency. Unlike [`TrackedQuery`], this doesn't subscribe to each matching
+/// entity separately, so that large queries stay cheap to track.
+pub struct TrackedWorldQuery<Q: ReadOnlyWorldQuery + 'static> {
+    query: Mutex<QueryState<Entity, Q>>,
+    entities: Vec<Entity>,
+    components: Vec<ComponentId>,
+}
+
+impl<Q: ReadOnlyWorldQuery + 'static> AnyQuery for TrackedWorldQuery<Q> {
+    fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        let mut query = self.query.lock().unwrap();
+        query.update_archetypes(world);
+        if !query.iter_manual(world).eq(self.entities.iter().copied()) {
+            return true;
+        }
+        self.entities.iter().any(|entity| {
+            let entt = world.entity(*entity);
+            self.components.iter().any(|component| {
+                entt.get_change_ticks_by_id(*component)
+                    .is_some_and(|ticks| ticks.is_changed(tick, world.read_change_tick()))
+            })
+        })
+    }
+}
+
+pub trait AnyProjection: Send + Sync {
+    fn is_changed(&self, world: &World, tick: Tick) -> bool;
+
+    /// The mutable which is projected.
+    fn source(&self) -> Entity;
+}
+
+/// Tracks the projected value of a mutable, so that we can ignore changes to the mutable
+/// which don't affect the projection.
+pub struct TrackedProjection<T, U> {
+    pub(crate) source: Entity,
+    pub(crate) lens: Arc<dyn Fn(&T) -> U + Send + Sync>,
+    pub(crate) value: U,
+}
+
+impl<T: Send + Sync + 'static, U: PartialEq + Send + Sync + 'static> AnyProjection
+    for TrackedProjection<T, U>
+{
+    fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        let Some(entt) = world.get_entity(self.source) else {
+            return false;
+        };
+        let source_changed = entt
+            .get_change_ticks::<MutableValue>()
+            .map(|ticks| ticks.is_changed(tick, world.read_change_tick()))
+            .unwrap_or(false);
+        source_changed
+            && entt
+                .get::<MutableValue>()
+                .and_then(|mutable| mutable.value.downcast_ref::<T>())
+                .map(|value| (self.lens)(value) != self.value)
+                .unwrap_or(false)
+    }
+
+    fn source(&self) -> Entity {
+        self.source
+    }
+}
+
+pub trait AnyMapKey: Send + Sync {
+    fn is_changed(&self, world: &World, tick: Tick) -> bool;
+
+    /// The reactive map which holds the key.
+    fn source(&self) -> Entity;
+}
+
+/// Returns the number of ancestors of the given entity.
+pub(crate) fn hierarchy_depth(world: &World, entity: Entity) -> usize {
+    let mut depth = 0;
+    let mut e = entity;
+    while let Some(parent) = world.get::<Parent>(e) {
+        depth += 1;
+        e = parent.get();
+    }
+    depth
+}
+
+/// Resource used to detect reactions which re-trigger themselves by writing to mutables that
+/// they depend on, either directly or via other reactions.
+#[derive(Resource, Default)]
+pub(crate) struct ReactionCycleGuard {
+    /// Whether a reaction is currently executing. Reactions are run one at a time, so there is
+    /// at most one.
+    pub(crate) executing: bool,
+
+    /// Mutables written by the currently executing reaction.
+    pub(crate) written: Vec<Entity>,
+
+    /// Mutables written by each reaction, the last time it ran.
+    writes: HashMap<Entity, HashSet<Entity>>,
+
+    /// Cycles which have been detected, as lists of reaction entities.
+    pub(crate) cycles: Vec<Vec<Entity>>,
+}
+
+/// Returns a path of reactions leading from `reaction` back to itself, if there is one. There
+/// is an edge from reaction A to reaction B if A wrote to a mutable that B depends on.
+///
+/// Every reaction on such a path writes to a mutable, so only the reactions which wrote to
+/// mutables the last time they ran need to be visited, rather than every tracking scope.
+fn find_cycle(world: &World, reaction: Entity) -> Option<Vec<Entity>> {
+    let guard = world.resource::<ReactionCycleGuard>();
+    let mut readers: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for writer in guard.writes.keys() {
+        let Some(scope) = world.get::<TrackingScope>(*writer) else {
+            continue;
+        };
+        for mutable in scope.mutable_deps.iter() {
+            readers.entry(*mutable).or_default().push(*writer);
+        }
+    }
+    let mut visited: HashSet<Entity> = HashSet::default();
+    let mut stack: Vec<Vec<Entity>> = vec![vec![reaction]];
+    while let Some(path) = stack.pop() {
+        let current = *path.last().unwrap();
+        let Some(writes) = guard.writes.get(&current) else {
+            continue;
+        };
+        for reader in writes.iter().filter_map(|m| readers.get(m)).flatten() {
+            if *reader == reaction {
+                return Some(path);
+            }
+            if visited.insert(*reader) {
+                let mut next = path.clone();
+                next.push(*reader);
+                stack.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Record the mutables written by a reaction, and break any reaction cycle which this
+/// creates by discarding the writes.
+fn check_reaction_cycle(world: &mut World, reaction: Entity, written: Vec<Entity>) {
+    let mut guard = world.resource_mut::<ReactionCycleGuard>();
+    if written.is_empty() {
+        guard.writes.remove(&reaction);
+        return;
+    }
+    guard
+        .writes
+        .insert(reaction, written.iter().copied().collect());
+    let Some(cycle) = find_cycle(world, reaction) else {
+        return;
+    };
+    error!(
+        "Reaction cycle detected: {:?} write to mutables which they depend on",
+        cycle
+    );
+    for mutable in written {
+        if let Some(mut entt) = world.get_entity_mut(mutable) {
+            entt.remove::<MutableValueNext>();
+        }
+    }
+    let mut guard = world.resource_mut::<ReactionCycleGuard>();
+    guard.writes.remove(&reaction);
+    guard.cycles.push(cycle);
+}
+
+/// Forget the writes of reactions which have been despawned since the previous update.
+fn prune_cycle_guard(world: &mut World) {
+    let Some(guard) = world.get_resource::<ReactionCycleGuard>() else {
+        return;
+    };
+    let despawned: Vec<Entity> = guard
+        .writes
+        .keys()
+        .copied()
+        .filter(|writer| world.get_entity(*writer).is_none())
+        .collect();
+    if despawned.is_empty() {
+        return;
+    }
+    let mut guard = world.resource_mut::<ReactionCycleGuard>();
+    for writer in despawned {
+        guard.writes.remove(&writer);
     }
 }
 
+/// Component on a mutable entity which records the reaction that computes its value, such as
+/// a selector. Used to run writers before readers when both are stale. Only immediate writers
+/// are recorded: a reaction which merely calls `write_mutable` is not, since it may not write
+/// on its next run. Because the writer is the mutable entity itself, the record goes away when
+/// the writer is despawned.
+#[derive(Component)]
+pub(crate) struct WrittenBy(pub(crate) Entity);
+
+/// Sort the stale reactions so that a reaction which writes to a mutable runs before the
+/// stale reactions which read that mutable. Otherwise, the hierarchy order of `changed` is
+/// preserved, and a writer never runs ahead of its own ancestors. Dependency cycles are broken
+/// by running the earliest remaining reaction.
+fn sort_reactions(world: &World, changed: Vec<(usize, Entity)>) -> Vec<(usize, Entity)> {
+    let index: HashMap<Entity, usize> = changed
+        .iter()
+        .enumerate()
+        .map(|(i, (_, entity))| (*entity, i))
+        .collect();
+    let mut readers: Vec<Vec<usize>> = vec![Vec::new(); changed.len()];
+    let mut in_degree: Vec<usize> = vec![0; changed.len()];
+    for (i, (_, entity)) in changed.iter().enumerate() {
+        let Some(scope) = world.get::<TrackingScope>(*entity) else {
+            continue;
+        };
+        for mutable in scope.mutable_deps.iter() {
+            let Some(WrittenBy(writer)) = world.get::<WrittenBy>(*mutable) else {
+                continue;
+            };
+            match index.get(writer) {
+                Some(&w) if w != i && !is_ancestor(world, *entity, *writer) => {
+                    readers[w].push(i);
+                    in_degree[i] += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Kahn's algorithm, always picking the earliest ready reaction so that parents still run
+    // before their children where possible.
+    let mut ready: BTreeSet<usize> = (0..changed.len()).filter(|i| in_degree[*i] == 0).collect();
+    let mut done = vec![false; changed.len()];
+    let mut sorted = Vec::with_capacity(changed.len());
+    while sorted.len() < changed.len() {
+        let next = match ready.pop_first() {
+            Some(next) => next,
+            None => {
+                let next = (0..changed.len()).find(|i| !done[*i]).unwrap();
+                warn!(
+                    "Reaction dependency cycle detected at {:?}, running in hierarchy order",
+                    changed[next].1
+                );
+                next
+            }
+        };
+        done[next] = true;
+        sorted.push(changed[next]);
+        for reader in readers[next].iter() {
+            if !done[*reader] {
+                in_degree[*reader] -= 1;
+                if in_degree[*reader] == 0 {
+                    ready.insert(*reader);
+                }
+            }
+        }
+    }
+    sorted
+}
+
+/// Returns true if `ancestor` is a parent, grandparent, etc. of `entity`.
+fn is_ancestor(world: &World, ancestor: Entity, entity: Entity) -> bool {
+    let mut current = entity;
+    while let Some(parent) = world.get::<Parent>(current) {
+        if parent.get() == ancestor {
+            return true;
+        }
+        current = parent.get();
+    }
+    false
+}
+
 /// Run reactions whose dependencies have changed.
 pub fn run_reactions(world: &mut World) {
+    if world.contains_resource::<ReactorPaused>() {
+        return;
+    }
     let mut scopes = world.query::<(Entity, &mut TrackingScope)>();
-    let mut changed = HashSet::<Entity>::default();
-    for (entity, scope) in scopes.iter(world) {
-        if scope.dependencies_changed(world) {
-            changed.insert(entity);
+    let now = world.get_resource::<Time>().map(|time| time.elapsed());
+    let parallel = world
+        .get_resource::<ReactorSettings>()
+        .map(|settings| settings.parallelism)
+        .unwrap_or(false);
+
+    // Returns the hierarchy depth of the scope if it needs to react.
+    let needs_reaction = |entity: Entity, scope: &TrackingScope| -> Option<usize> {
+        if world.get::<ReactionPaused>(entity).is_some()
+            || world.get::<ViewBuildFailed>(entity).is_some()
+        {
+            return None;
+        }
+        if world.get::<ReactionTriggered>(entity).is_none()
+            && !scope.throttle_pending.load(Ordering::Relaxed)
+            && !scope.dependencies_changed(world)
+        {
+            return None;
+        }
+        if world.get::<ReactionTriggered>(entity).is_none() && scope.is_throttled(now) {
+            // Remember the change so that the reaction can run once the interval elapses.
+            scope.throttle_pending.store(true, Ordering::Relaxed);
+            return None;
+        }
+        Some(hierarchy_depth(world, entity))
+    };
+
+    let mut changed: Vec<(usize, Entity)> = if parallel {
+        let changed = Mutex::new(Vec::new());
+        scopes.par_iter(world).for_each(|(entity, scope)| {
+            if let Some(depth) = needs_reaction(entity, scope) {
+                changed.lock().unwrap().push((depth, entity));
+            }
+        });
+        changed.into_inner().unwrap()
+    } else {
+        scopes
+            .iter(world)
+            .filter_map(|(entity, scope)| {
+                needs_reaction(entity, scope).map(|depth| (depth, entity))
+            })
+            .collect()
+    };
+
+    // Run parent reactions before child reactions, since the parent may raze the child.
+    changed.sort_by_key(|(depth, _)| *depth);
+    // Run reactions which write to mutables before the reactions which read them.
+    let changed = sort_reactions(world, changed);
+
+    prune_cycle_guard(world);
+    let profiling = world.contains_resource::<ReactorDiagnostics>();
+    for wave in reaction_waves(world, &changed, parallel) {
+        match wave.as_slice() {
+            [(scope_entity, _)] => run_reaction(world, &mut scopes, *scope_entity, now, profiling),
+            _ => run_wave(world, &mut scopes, &wave, now, profiling),
         }
     }
+}
+
+/// A reaction to run, along with the entities which it writes to.
+type WaveEntry = (Entity, Vec<Entity>);
+
+/// The result of computing a reaction in a wave: its index in the wave, its entity, its next
+/// scope, its deferred write, and the time it took.
+type ComputedReaction = (
+    usize,
+    Entity,
+    TrackingScope,
+    std::thread::Result<ReactionWrite>,
+    Option<Duration>,
+);
+
+/// Returns the entities read by the scope of a reaction during its previous run, or `None` if
+/// it may read from any entity.
+fn reaction_reads(world: &World, entity: Entity) -> Option<HashSet<Entity>> {
+    let mut reads = HashSet::default();
+    match world.get::<TrackingScope>(entity) {
+        Some(scope) if !scope.read_entities(&mut reads) => None,
+        _ => Some(reads),
+    }
+}
 
+/// The entities written and read by the reactions in a wave.
+#[derive(Default)]
+struct WaveAccess {
+    written: HashSet<Entity>,
+    read: HashSet<Entity>,
+    reads_any: bool,
+}
+
+impl WaveAccess {
+    /// Returns true if a reaction with the given writes and reads can't join the wave.
+    fn conflicts(&self, writes: &[Entity], reads: &Option<HashSet<Entity>>) -> bool {
+        writes
+            .iter()
+            .any(|e| self.reads_any || self.written.contains(e) || self.read.contains(e))
+            || match reads {
+                Some(reads) => reads.iter().any(|e| self.written.contains(e)),
+                None => !self.written.is_empty(),
+            }
+    }
+
+    fn add(&mut self, writes: &[Entity], reads: Option<HashSet<Entity>>) {
+        self.written.extend(writes.iter().copied());
+        match reads {
+            Some(reads) => self.read.extend(reads),
+            None => self.reads_any = true,
+        }
+    }
+}
+
+/// Group the reactions to run into waves, preserving their order. When `parallel` is true,
+/// consecutive reactions which have a [`write_set`](crate::Reaction::write_set) share a wave,
+/// as long as none of them writes to an entity which another reaction in the wave writes to,
+/// or read from during its previous run. Every other reaction has a wave of its own.
+fn reaction_waves(
+    world: &World,
+    changed: &[(usize, Entity)],
+    parallel: bool,
+) -> Vec<Vec<WaveEntry>> {
+    let mut waves: Vec<Vec<WaveEntry>> = Vec::new();
+    // The accesses of the last wave, if it can take more reactions.
+    let mut open: Option<WaveAccess> = None;
+    for (_, entity) in changed {
+        let write_set = world
+            .get::<ReactionHandle>(*entity)
+            .filter(|_| parallel)
+            .and_then(|handle| handle.0.lock().unwrap().write_set());
+        let Some(write_set) = write_set else {
+            waves.push(vec![(*entity, Vec::new())]);
+            open = None;
+            continue;
+        };
+        let reads = reaction_reads(world, *entity);
+        match (waves.last_mut(), open.as_mut()) {
+            (Some(wave), Some(access)) if !access.conflicts(&write_set, &reads) => {
+                access.add(&write_set, reads);
+                wave.push((*entity, write_set));
+            }
+            _ => {
+                let mut access = WaveAccess::default();
+                access.add(&write_set, reads);
+                open = Some(access);
+                waves.push(vec![(*entity, write_set)]);
+            }
+        }
+    }
+    waves
+}
+
+/// Run a single view or reaction.
+fn run_reaction(
+    world: &mut World,
+    scopes: &mut QueryState<(Entity, &mut TrackingScope)>,
+    scope_entity: Entity,
+    now: Option<Duration>,
+    profiling: bool,
+) {
+    // Each reaction runs at a tick of its own, so that the components and resources it
+    // writes are newer than the scopes of the reactions which ran before it, but not newer
+    // than its own scope.
+    world.increment_change_tick();
     let tick = world.change_tick();
-    for scope_entity in changed.iter() {
-        let mut next_scope = TrackingScope::new(tick);
-        let mut entt = world.entity_mut(*scope_entity);
-        if let Some(view_handle) = entt.get_mut::<ViewHandle>() {
-            let inner = view_handle.view.clone();
-            inner
-                .lock()
-                .unwrap()
-                .react(*scope_entity, world, &mut next_scope);
-        } else if let Some(reaction) = entt.get_mut::<ReactionHandle>() {
-            let inner = reaction.0.clone();
-            inner
-                .lock()
+    let mut next_scope = TrackingScope::new(tick);
+    let started = profiling.then(Instant::now);
+    // Skip reactions which were despawned by an earlier reaction.
+    if world.get_entity(scope_entity).is_none() {
+        return;
+    }
+    // Record the mutables written by the reaction, to detect reaction cycles.
+    let mut guard = world.get_resource_or_insert_with(ReactionCycleGuard::default);
+    guard.executing = true;
+    guard.written.clear();
+    let mut entt = world.entity_mut(scope_entity);
+    // Panics are caught so that they can be forwarded to an error boundary. The lock is
+    // acquired outside of the unwind boundary so that the mutex isn't poisoned.
+    // View roots react like the views held by a `ViewHandle`.
+    let view_ref = match entt.get::<ViewHandle>() {
+        Some(handle) => Some(handle.view.clone()),
+        None => entt.get::<ViewRoot>().map(|root| root.view.clone()),
+    };
+    let is_view = view_ref.is_some();
+    // A view counts as rebuilt when its reaction replaces its output nodes.
+    let nodes_changed = entt.contains::<DisplayNodeChanged>();
+    // Scopes which hold neither a view nor a reaction, such as the scope entity of a
+    // `Bind`, only have their dependencies updated.
+    let result = if let Some(inner) = view_ref {
+        let mut view = inner.lock().unwrap();
+        Some(catch_unwind(AssertUnwindSafe(|| {
+            view.react(scope_entity, world, &mut next_scope)
+        })))
+    } else if let Some(reaction) = entt.get_mut::<ReactionHandle>() {
+        let inner = reaction.0.clone();
+        entt.insert(ReactionRunTick(tick));
+        let mut reaction = inner.lock().unwrap();
+        Some(catch_unwind(AssertUnwindSafe(|| {
+            reaction.react(scope_entity, world, &mut next_scope)
+        })))
+    } else {
+        None
+    };
+    if result.is_some() {
+        if let Some(mut counts) = world.get_resource_mut::<ReactorFrameCounts>() {
+            counts.reactions += 1;
+        }
+    }
+    if is_view
+        && !nodes_changed
+        && world
+            .get_entity(scope_entity)
+            .is_some_and(|entt| entt.contains::<DisplayNodeChanged>())
+    {
+        if let Some(mut counts) = world.get_resource_mut::<ReactorFrameCounts>() {
+            counts.rebuilds += 1;
+        }
+    }
+    if let Some(started) = started {
+        let elapsed = started.elapsed();
+        let label = reaction_label(world, scope_entity);
+        world
+            .resource_mut::<ReactorDiagnostics>()
+            .record(scope_entity, label, elapsed);
+    }
+    if let Some(Err(payload)) = result {
+        forward_panic(scope_entity, world, payload);
+    }
+    finish_reaction(world, scopes, scope_entity, next_scope, tick, now);
+}
+
+/// Run a wave of reactions which have write sets. The reactions are computed concurrently
+/// against the same state of the world, and their writes are then applied in order. Since
+/// they all run at the same tick, a reaction which has started reading an entity written by
+/// another reaction in the wave is triggered to run again on the next pass.
+fn run_wave(
+    world: &mut World,
+    scopes: &mut QueryState<(Entity, &mut TrackingScope)>,
+    wave: &[WaveEntry],
+    now: Option<Duration>,
+    profiling: bool,
+) {
+    world.increment_change_tick();
+    let tick = world.change_tick();
+    let reactions: Vec<(usize, Entity, ReactionRef)> = wave
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (entity, _))| {
+            let handle = world.get::<ReactionHandle>(*entity)?;
+            Some((index, *entity, handle.0.clone()))
+        })
+        .collect();
+
+    let mut computed: Vec<ComputedReaction> = {
+        let world: &World = world;
+        // The lock is acquired outside of the unwind boundary so that the mutex isn't poisoned.
+        let compute = move |(index, entity, reaction): &(usize, Entity, ReactionRef)| {
+            let mut next_scope = TrackingScope::new(tick);
+            let started = profiling.then(Instant::now);
+            let mut reaction = reaction.lock().unwrap();
+            let write = catch_unwind(AssertUnwindSafe(|| {
+                reaction.compute(*entity, world, &mut next_scope)
+            }));
+            let elapsed = started.map(|started| started.elapsed());
+            (*index, *entity, next_scope, write, elapsed)
+        };
+        match ComputeTaskPool::try_get() {
+            Some(pool) => reactions
+                .par_splat_map(pool, None, |chunk| {
+                    chunk.iter().map(compute).collect::<Vec<ComputedReaction>>()
+                })
+                .into_iter()
+                .flatten()
+                .collect(),
+            None => reactions.iter().map(compute).collect(),
+        }
+    };
+    computed.sort_by_key(|(index, ..)| *index);
+
+    for (_, scope_entity, next_scope, write, elapsed) in computed {
+        let Some(mut entt) = world.get_entity_mut(scope_entity) else {
+            world.release_scope(next_scope);
+            continue;
+        };
+        entt.insert(ReactionRunTick(tick));
+        if let Some(mut counts) = world.get_resource_mut::<ReactorFrameCounts>() {
+            counts.reactions += 1;
+        }
+        let mut guard = world.get_resource_or_insert_with(ReactionCycleGuard::default);
+        guard.executing = true;
+        guard.written.clear();
+        let result = write.and_then(|write| catch_unwind(AssertUnwindSafe(|| write(world))));
+        if let Some(elapsed) = elapsed {
+            let label = reaction_label(world, scope_entity);
+            world
+                .resource_mut::<ReactorDiagnostics>()
+                .record(scope_entity, label, elapsed);
+        }
+        if let Err(payload) = result {
+            forward_panic(scope_entity, world, payload);
+        }
+        finish_reaction(world, scopes, scope_entity, next_scope, tick, now);
+    }
+
+    // Re-run the reactions which have started reading an entity written by another reaction.
+    let writers: HashMap<Entity, Entity> = wave
+        .iter()
+        .flat_map(|(entity, writes)| writes.iter().map(|e| (*e, *entity)))
+        .collect();
+    for (entity, _) in wave {
+        if world.get_entity(*entity).is_none() {
+            continue;
+        }
+        let stale = match reaction_reads(world, *entity) {
+            Some(reads) => reads
+                .iter()
+                .any(|e| writers.get(e).is_some_and(|writer| writer != entity)),
+            None => writers.values().any(|writer| writer != entity),
+        };
+        if stale {
+            world.entity_mut(*entity).insert(ReactionTriggered);
+        }
+    }
+}
+
+/// Store the scope of a reaction which has run, release what its previous run owned, and
+/// check for reaction cycles.
+fn finish_reaction(
+    world: &mut World,
+    scopes: &mut QueryState<(Entity, &mut TrackingScope)>,
+    scope_entity: Entity,
+    mut next_scope: TrackingScope,
+    tick: Tick,
+    now: Option<Duration>,
+) {
+    if let Ok((_, mut scope)) = scopes.get_mut(world, scope_entity) {
+        // Swap the scopes so that the next scope becomes the current scope.
+        scope.take_deps(&mut next_scope);
+        scope.tick = tick;
+        scope.last_run = now;
+        scope.throttle_pending.store(false, Ordering::Relaxed);
+    }
+    // Release the entities which are no longer needed: those of the previous
+    // run, or those of this run if it kept the previous output or was despawned.
+    world.release_scope(next_scope);
+    let mut guard = world.resource_mut::<ReactionCycleGuard>();
+    guard.executing = false;
+    let written = std::mem::take(&mut guard.written);
+    check_reaction_cycle(world, scope_entity, written);
+    if let Some(mut entt) = world.get_entity_mut(scope_entity) {
+        entt.remove::<ReactionTriggered>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        commit_mutables, cx::Cx, hooks::HookSlots, Rcx, Reaction, ReactionWrite, ReactiveContext,
+        ReactiveContextMut, Selector,
+    };
+
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B(u32);
+
+    fn query_scope(world: &mut World) -> TrackingScope {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.use_query::<&A, ()>().len(), 1);
+        world.increment_change_tick();
+        scope
+    }
+
+    #[derive(Resource)]
+    struct Counter(u32);
+
+    #[test]
+    fn test_dependency_count() {
+        let mut world = World::default();
+        world.insert_resource(Counter(0));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        let mutable = cx.create_mutable(1u32);
+        assert_eq!(cx.read_mutable::<u32>(mutable.id), 1);
+        assert_eq!(cx.use_resource::<Counter>().0, 0);
+        assert_eq!(scope.dependency_count(), 2);
+        assert_eq!(scope.debug_mutables().collect::<Vec<_>>(), vec![mutable.id]);
+        assert_eq!(
+            scope.debug_resources().collect::<Vec<_>>(),
+            vec![world.components().resource_id::<Counter>().unwrap()]
+        );
+        assert_eq!(scope.debug_components().count(), 0);
+
+        // Dependencies from a later reaction replace the previous ones.
+        let mut next = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut next);
+        assert_eq!(cx.use_resource::<Counter>().0, 0);
+        scope.take_deps(&mut next);
+        assert_eq!(scope.dependency_count(), 1);
+        assert_eq!(next.dependency_count(), 0);
+    }
+
+    #[test]
+    fn test_query_deps() {
+        let mut world = World::default();
+        let e1 = world.spawn((A(1), B(1))).id();
+
+        // Mutating a component that is not part of the query has no effect.
+        let scope = query_scope(&mut world);
+        assert!(!scope.dependencies_changed(&world));
+        world.get_mut::<B>(e1).unwrap().0 = 2;
+        assert!(!scope.dependencies_changed(&world));
+
+        // Mutating a queried component.
+        world.get_mut::<A>(e1).unwrap().0 = 2;
+        assert!(scope.dependencies_changed(&world));
+
+        // Adding a matching entity.
+        let scope = query_scope(&mut world);
+        let e2 = world.spawn(A(3)).id();
+        assert!(scope.dependencies_changed(&world));
+
+        // Removing a matching entity.
+        world.despawn(e2);
+        let scope = query_scope(&mut world);
+        world.entity_mut(e1).remove::<A>();
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    fn world_query_scope(world: &mut World) -> (TrackingScope, u32) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, Entity::PLACEHOLDER, &mut scope);
+        let total = cx.use_world_query::<&A>().iter().map(|a| a.0).sum();
+        world.increment_change_tick();
+        (scope, total)
+    }
+
+    #[test]
+    fn test_world_query_deps() {
+        let mut world = World::default();
+        let e1 = world.spawn(A(1)).id();
+        let outside = world.spawn(B(1)).id();
+
+        // An entity outside of the query changes.
+        let (scope, total) = world_query_scope(&mut world);
+        assert_eq!(total, 1);
+        world.get_mut::<B>(outside).unwrap().0 = 2;
+        assert!(!scope.dependencies_changed(&world));
+
+        // An entity in the query changes value.
+        world.get_mut::<A>(e1).unwrap().0 = 2;
+        assert!(scope.dependencies_changed(&world));
+
+        // An entity is added to the query.
+        let (scope, total) = world_query_scope(&mut world);
+        assert_eq!(total, 2);
+        world.entity_mut(outside).insert(A(3));
+        assert!(scope.dependencies_changed(&world));
+
+        // An entity is removed from the query.
+        let (scope, total) = world_query_scope(&mut world);
+        assert_eq!(total, 5);
+        world.despawn(e1);
+        assert!(scope.dependencies_changed(&world));
+
+        // The query is tracked as a single dependency, however many entities match.
+        world.spawn_batch((0..100).map(A));
+        let (scope, _) = world_query_scope(&mut world);
+        assert_eq!(scope.dependency_count(), 1);
+        assert!(scope.debug_components().next().is_none());
+    }
+
+    fn frame_scope(world: &mut World) -> (TrackingScope, u64, bool) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), world, Entity::PLACEHOLDER, &mut scope);
+        let frame = cx.use_frame_count();
+        let fifth = cx.use_every_n_frames(5);
+        world.clear_trackers();
+        (scope, frame, fifth)
+    }
+
+    #[test]
+    fn test_frame_deps() {
+        let mut world = World::default();
+        world.init_resource::<FrameCount>();
+
+        let (scope, frame, fifth) = frame_scope(&mut world);
+        assert_eq!((frame, fifth), (0, true));
+        assert!(!scope.dependencies_changed(&world));
+        world.resource_mut::<FrameCount>().0 += 1;
+        assert!(scope.dependencies_changed(&world));
+
+        // Only react once every five frames.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert!(!cx.use_every_n_frames(5));
+        for frame in 2..5 {
+            world.resource_mut::<FrameCount>().0 = frame;
+            assert!(!scope.dependencies_changed(&world));
+        }
+        world.resource_mut::<FrameCount>().0 = 5;
+        assert!(scope.dependencies_changed(&world));
+        let (_, frame, fifth) = frame_scope(&mut world);
+        assert_eq!((frame, fifth), (5, true));
+    }
+
+    #[test]
+    fn test_time_untracked() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.use_elapsed_secs_untracked(), 0.5);
+        assert_eq!(cx.use_delta_secs_untracked(), 0.5);
+        assert_eq!(
+            cx.use_time_untracked().elapsed(),
+            Duration::from_millis(500)
+        );
+        assert_eq!(scope.dependency_count(), 0);
+
+        // Advancing the time does not cause a reaction.
+        world.clear_trackers();
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(100));
+        assert!(!scope.dependencies_changed(&world));
+    }
+
+    #[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    enum Screen {
+        #[default]
+        Title,
+        Game,
+    }
+
+    #[test]
+    fn test_bevy_state_deps() {
+        let mut world = World::default();
+        world.init_resource::<State<Screen>>();
+        world.init_resource::<NextState<Screen>>();
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(*cx.use_bevy_state::<Screen>(), Screen::Title);
+        cx.set_bevy_state(Screen::Game);
+        world.clear_trackers();
+
+        // Setting the state only schedules the transition.
+        assert_eq!(world.resource::<NextState<Screen>>().0, Some(Screen::Game));
+        assert!(!scope.dependencies_changed(&world));
+
+        // Applying the transition causes a reaction.
+        world.insert_resource(State::new(Screen::Game));
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[derive(Resource, Default)]
+    struct ClockRuns(u32);
+
+    #[derive(Resource)]
+    struct MountedAt(f32, f32);
+
+    struct Clock;
+
+    impl crate::Presenter for Clock {
+        fn present(cx: &mut Cx<Self>) -> impl crate::IntoView {
+            cx.world_mut().resource_mut::<ClockRuns>().0 += 1;
+            let built_at = cx.use_elapsed_secs_untracked();
+            cx.on_mount(move |_, world| {
+                let now = world.resource::<Time>().elapsed_seconds();
+                world.insert_resource(MountedAt(built_at, now));
+            });
+            "Clock"
+        }
+    }
+
+    #[test]
+    fn test_time_untracked_on_mount() {
+        let mut world = crate::testing::TestWorld::new();
+        world.world_mut().init_resource::<ClockRuns>();
+        world.world_mut().init_resource::<Time>();
+        world
+            .world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        world.spawn_view(crate::PresenterView::new(Clock));
+        world.tick();
+
+        // The mount callback sees the current time.
+        let mounted = world.world().resource::<MountedAt>();
+        assert_eq!((mounted.0, mounted.1), (0.5, 0.5));
+
+        // Advancing the time does not cause the presenter to run again.
+        for _ in 0..3 {
+            world
+                .world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            world.tick();
+        }
+        assert_eq!(world.world().resource::<ClockRuns>().0, 1);
+    }
+
+    #[test]
+    fn test_component_presence_deps() {
+        let mut world = World::default();
+        world.init_component::<A>();
+        let e1 = world.spawn(B(1)).id();
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert!(cx.use_component::<A>(e1).is_none());
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Adding the component
+        world.entity_mut(e1).insert(A(1));
+        assert!(scope.dependencies_changed(&world));
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.use_component::<A>(e1).unwrap().0, 1);
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Removing the component
+        world.entity_mut(e1).remove::<A>();
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_mutable_deps() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mutable =
+            Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).create_mutable(1u32);
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.read_mutable::<u32>(mutable.id), 1);
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // A write is not a change until it is committed.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).write_mutable(mutable.id, 2u32);
+        assert!(!scope.dependencies_changed(&world));
+        commit_mutables(&mut world);
+        assert!(scope.dependencies_changed(&world));
+
+        // A scope which has read the committed value is not stale.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(cx.read_mutable::<u32>(mutable.id), 2);
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // A despawned mutable is not a change.
+        world.despawn(mutable.id);
+        assert!(!scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_component_mut_deps() {
+        let mut world = World::default();
+        let e1 = world.spawn(Style::default()).id();
+        let spawned = world.change_tick();
+        world.increment_change_tick();
+
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        cx.use_component_mut::<Style>(e1).unwrap().width = Val::Px(10.);
+        let ticks = world.entity(e1).get_change_ticks::<Style>().unwrap();
+        assert!(ticks.is_changed(spawned, world.change_tick()));
+
+        // Our own write doesn't cause a reaction.
+        assert!(!scope.dependencies_changed(&world));
+
+        // Someone else writing to the component does.
+        world.increment_change_tick();
+        world.get_mut::<Style>(e1).unwrap().height = Val::Px(20.);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct Pair(u32, u32);
+
+    #[test]
+    fn test_mapped_mutable_deps() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let mutable = cx.create_mutable(Pair(1, 1));
+        let first = mutable.map(|pair: &Pair| pair.0);
+        let first_eq = mutable.map_eq(|pair: &Pair| pair.0);
+
+        // The lens is applied to the initial value.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut scope_eq = TrackingScope::new(world.change_tick());
+        assert_eq!(
+            first.get(&Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope)),
+            1
+        );
+        assert_eq!(
+            first_eq.get(&Cx::new(
+                &(),
+                &mut world,
+                Entity::PLACEHOLDER,
+                &mut scope_eq
+            )),
+            1
+        );
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+        assert!(!scope_eq.dependencies_changed(&world));
+
+        // Changing a field which is not part of the projection.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)
+            .write_mutable_clone(mutable.id, Pair(1, 2));
+        commit_mutables(&mut world);
+        assert!(scope.dependencies_changed(&world));
+        assert!(!scope_eq.dependencies_changed(&world));
+
+        // Changing the projected field.
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)
+            .write_mutable_clone(mutable.id, Pair(3, 2));
+        commit_mutables(&mut world);
+        assert!(scope_eq.dependencies_changed(&world));
+        let second = mutable.map(|pair: &Pair| pair.1);
+        assert_eq!(
+            second.get(&Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)),
+            2
+        );
+    }
+
+    /// Reaction which copies one mutable into another, plus one.
+    struct Increment {
+        from: Entity,
+        to: Entity,
+    }
+
+    impl Reaction for Increment {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let mut cx = Cx::new(&(), world, Entity::PLACEHOLDER, tracking);
+            let value = cx.read_mutable::<u32>(self.from);
+            cx.write_mutable(self.to, value + 1);
+        }
+    }
+
+    fn spawn_increment(world: &mut World, from: Entity, to: Entity) -> Entity {
+        let mut reaction = Increment { from, to };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let reaction_id = world.spawn_empty().id();
+        reaction.react(reaction_id, world, &mut scope);
+        world
+            .entity_mut(reaction_id)
+            .insert((ReactionHandle::new(reaction), scope));
+        reaction_id
+    }
+
+    fn update(world: &mut World) {
+        world.increment_change_tick();
+        run_reactions(world);
+        commit_mutables(world);
+    }
+
+    #[test]
+    fn test_direct_cycle() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let x = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).create_mutable(0u32);
+        let reaction = spawn_increment(&mut world, x.id, x.id);
+        commit_mutables(&mut world);
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(x.id, 10u32);
+        commit_mutables(&mut world);
+        update(&mut world);
+
+        // The reaction's write is discarded, so it doesn't run forever.
+        let guard = world.resource::<ReactionCycleGuard>();
+        assert_eq!(guard.cycles, vec![vec![reaction]]);
+        assert!(!guard.executing);
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<u32>(x.id), 10);
+    }
+
+    #[test]
+    fn test_indirect_cycle() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let x = cx.create_mutable(0u32);
+        let y = cx.create_mutable(0u32);
+        let a = spawn_increment(&mut world, x.id, y.id);
+        let b = spawn_increment(&mut world, y.id, x.id);
+        commit_mutables(&mut world);
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(x.id, 10u32);
+        commit_mutables(&mut world);
+
+        // A writes to y, which B depends on.
+        update(&mut world);
+        assert!(world.resource::<ReactionCycleGuard>().cycles.is_empty());
+
+        // B writes to x, which A depends on, closing the loop.
+        update(&mut world);
+        assert_eq!(
+            world.resource::<ReactionCycleGuard>().cycles,
+            vec![vec![b, a]]
+        );
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<u32>(x.id), 10);
+        assert_eq!(cx.read_mutable::<u32>(y.id), 11);
+    }
+
+    #[test]
+    fn test_cycle_guard_pruned() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let x = cx.create_mutable(0u32);
+        let y = cx.create_mutable(0u32);
+        let reaction = spawn_increment(&mut world, x.id, y.id);
+        commit_mutables(&mut world);
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(x.id, 10u32);
+        commit_mutables(&mut world);
+        update(&mut world);
+        let guard = world.resource::<ReactionCycleGuard>();
+        assert!(guard.writes.contains_key(&reaction));
+
+        // The writes of a despawned reaction are forgotten on the next update.
+        world.despawn(reaction);
+        update(&mut world);
+        assert!(world.resource::<ReactionCycleGuard>().writes.is_empty());
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let x = cx.create_mutable(0u32);
+        let y = cx.create_mutable(0u32);
+        let z = cx.create_mutable(0u32);
+        spawn_increment(&mut world, x.id, y.id);
+        spawn_increment(&mut world, y.id, z.id);
+        commit_mutables(&mut world);
+        world.increment_change_tick();
+
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        cx.write_mutable(x.id, 10u32);
+        commit_mutables(&mut world);
+        update(&mut world);
+        update(&mut world);
+        assert!(world.resource::<ReactionCycleGuard>().cycles.is_empty());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        assert_eq!(cx.read_mutable::<u32>(y.id), 11);
+        assert_eq!(cx.read_mutable::<u32>(z.id), 12);
+    }
+
+    /// A resource which is not `Send`, such as a native window handle.
+    struct Window {
+        title: std::rc::Rc<String>,
+    }
+
+    #[test]
+    fn test_non_send_resource_deps() {
+        let mut world = World::default();
+        world.insert_non_send_resource(Window {
+            title: std::rc::Rc::new("Main".to_string()),
+        });
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        assert_eq!(*cx.use_non_send_resource::<Window>().title, "Main");
+        assert_eq!(scope.dependency_count(), 1);
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(&world));
+
+        world.non_send_resource_mut::<Window>().title = std::rc::Rc::new("Other".to_string());
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_non_send_resource_wrong_thread() {
+        let mut world = World::default();
+        world.insert_non_send_resource(Window {
+            title: std::rc::Rc::new("Main".to_string()),
+        });
+        let result = std::thread::spawn(move || {
+            let mut scope = TrackingScope::new(world.change_tick());
+            let cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+            cx.use_non_send_resource::<Window>().title.len()
+        })
+        .join();
+        assert!(result.is_err());
+    }
+
+    #[derive(Asset, TypePath, Default, Debug, PartialEq)]
+    struct Level(u32);
+
+    #[test]
+    fn test_asset_or_default_deps() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Level>();
+        let world = &mut app.world;
+        let handle_provider = world.resource::<Assets<Level>>().get_handle_provider();
+        let first = handle_provider.reserve_handle().typed::<Level>();
+        let second = handle_provider.reserve_handle().typed::<Level>();
+        let entity = world.spawn_empty().id();
+
+        // While loading, each call site returns its own default.
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, entity, &mut scope);
+        assert_eq!(cx.use_asset_or_default(&first), &Level(0));
+        assert_eq!(cx.use_asset_or_default(&second), &Level(0));
+        world.clear_trackers();
+        assert!(!scope.dependencies_changed(world));
+
+        // Once one asset is loaded, readers react. Its default is released, but the default
+        // for the asset which is still loading is kept.
+        world
+            .resource_mut::<Assets<Level>>()
+            .insert(first.id(), Level(3));
+        assert!(scope.dependencies_changed(world));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), world, entity, &mut scope);
+        assert_eq!(cx.use_asset_or_default(&first), &Level(3));
+        assert_eq!(cx.use_asset_or_default(&second), &Level(0));
+        let slots = world.get::<HookSlots>(entity).unwrap();
+        assert!(slots.get_ref::<Level>(0).is_none());
+        assert!(slots.get_ref::<Level>(1).is_some());
+    }
+
+    #[test]
+    fn test_asset_or_default_unregistered() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert_eq!(
+            cx.use_asset_or_default(&Handle::<Level>::default()),
+            &Level(0)
+        );
+        assert_eq!(scope.dependency_count(), 0);
+    }
+
+    #[test]
+    fn test_view_visibility_deps() {
+        let mut world = World::default();
+        let entity = world.spawn(ViewVisibility::HIDDEN).id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert!(!cx.use_visible_in_camera());
+        assert_eq!(cx.use_aabb_in_camera(Entity::PLACEHOLDER), None);
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+
+        // The visibility systems mark the node as visible when it is within the frustum.
+        world.get_mut::<ViewVisibility>(entity).unwrap().set();
+        assert!(scope.dependencies_changed(&world));
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), &mut world, entity, &mut scope);
+        assert!(cx.use_visible_in_camera());
+    }
+
+    #[derive(Resource)]
+    struct Seen(bool);
+
+    struct Watcher;
+
+    impl crate::Presenter for Watcher {
+        fn present(cx: &mut Cx<Self>) -> impl crate::IntoView {
+            let visible = cx.use_visible_in_camera();
+            cx.world_mut().insert_resource(Seen(visible));
+            "Watched"
+        }
+    }
+
+    #[test]
+    fn test_output_visibility_deps() {
+        let mut world = crate::testing::TestWorld::new();
+        let view = world.spawn_view(crate::PresenterView::new(Watcher));
+        assert!(!world.world().resource::<Seen>().0);
+
+        // The presenter runs again once its output is built, and reads the output's text node.
+        world.tick();
+        let node = world.get_node_entities(view)[0];
+        assert!(world.world().get::<Text>(node).is_some());
+        assert!(!world.world().resource::<Seen>().0);
+
+        world
+            .world_mut()
+            .get_mut::<ViewVisibility>(node)
+            .unwrap()
+            .set();
+        world.tick();
+        assert!(world.world().resource::<Seen>().0);
+        assert_eq!(world.get_node_entities(view), vec![node]);
+    }
+
+    /// Reaction which reads one of two mutables, depending on a flag.
+    struct Branch {
+        flag: Entity,
+        a: Entity,
+        b: Entity,
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl Reaction for Branch {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let cx = Cx::new(&(), world, Entity::PLACEHOLDER, tracking);
+            if cx.read_mutable::<bool>(self.flag) {
+                cx.read_mutable::<u32>(self.a);
+            } else {
+                cx.read_mutable::<u32>(self.b);
+            }
+            self.runs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn write_and_react<T: Send + Sync + Copy + PartialEq + 'static>(
+        world: &mut World,
+        owner: &mut TrackingScope,
+        mutable: Entity,
+        value: T,
+    ) {
+        world.increment_change_tick();
+        let mut cx = Cx::new(&(), world, Entity::PLACEHOLDER, owner);
+        cx.write_mutable(mutable, value);
+        commit_mutables(world);
+        run_reactions(world);
+    }
+
+    #[test]
+    fn test_stale_deps_dropped() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner);
+        let flag = cx.create_mutable(true);
+        let a = cx.create_mutable(0u32);
+        let b = cx.create_mutable(0u32);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut reaction = Branch {
+            flag: flag.id,
+            a: a.id,
+            b: b.id,
+            runs: runs.clone(),
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let reaction_id = world.spawn_empty().id();
+        reaction.react(reaction_id, &mut world, &mut scope);
+        world
+            .entity_mut(reaction_id)
+            .insert((ReactionHandle::new(reaction), scope));
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        // Only the mutable in the current branch is a dependency.
+        write_and_react(&mut world, &mut owner, b.id, 1u32);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        write_and_react(&mut world, &mut owner, a.id, 1u32);
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+
+        // After flipping the flag, the old branch is no longer a dependency.
+        write_and_react(&mut world, &mut owner, flag.id, false);
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+        write_and_react(&mut world, &mut owner, a.id, 2u32);
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+        write_and_react(&mut world, &mut owner, b.id, 2u32);
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_despawn_missing_owned() {
+        let mut world = World::default();
+        let tick = world.change_tick();
+        let owned = world.spawn(TrackingScope::new(tick)).id();
+        let mut scope = TrackingScope::new(tick);
+        scope.add_owned(owned);
+        let owner = world.spawn(scope).id();
+
+        // An owned entity which has already been despawned is skipped.
+        world.despawn(owned);
+        world.despawn_owned_recursive(owner);
+        assert!(world.get_entity(owner).is_none());
+    }
+
+    #[test]
+    fn test_take_deps_releases_previous_run() {
+        let mut world = World::default();
+        let tick = world.change_tick();
+        let [built, second, third, fourth, hook] = [(); 5].map(|_| world.spawn_empty().id());
+        let mut scope = TrackingScope::new(tick);
+        scope.add_owned(built);
+
+        // Entities added by a run are kept until the next run.
+        let mut next = TrackingScope::new(tick);
+        next.add_owned(second);
+        scope.take_deps(&mut next);
+        world.release_scope(next);
+        assert!(world.get_entity(second).is_some());
+
+        // The next run releases them.
+        let mut next = TrackingScope::new(tick);
+        next.add_owned(third);
+        scope.take_deps(&mut next);
+        world.release_scope(next);
+        assert!(world.get_entity(second).is_none());
+
+        // A run which retains the previous run releases its own entities instead, but not
+        // those registered by hooks.
+        let mut next = TrackingScope::new(tick);
+        next.add_owned(fourth);
+        next.add_hook_owned(hook);
+        next.retain_previous_run();
+        scope.take_deps(&mut next);
+        world.release_scope(next);
+        assert!(world.get_entity(third).is_some());
+        assert!(world.get_entity(fourth).is_none());
+        assert!(world.get_entity(hook).is_some());
+
+        // Entities added when the scope was built are kept until it is despawned.
+        assert!(world.get_entity(built).is_some());
+        let owner = world.spawn(scope).id();
+        world.despawn_owned_recursive(owner);
+        assert!(world.get_entity(built).is_none());
+        assert!(world.get_entity(third).is_none());
+        assert!(world.get_entity(hook).is_none());
+    }
+
+    #[test]
+    fn test_despawn_owned_mutables() {
+        let mut world = World::default();
+        let count = world.entities().len();
+        let presenter = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, presenter, &mut scope);
+        cx.create_mutable(0u32);
+        cx.create_mutable("Hello".to_string());
+        world.entity_mut(presenter).insert(scope);
+
+        // Razing the presenter leaves no entities behind.
+        world.despawn_owned_recursive(presenter);
+        assert_eq!(world.entities().len(), count);
+    }
+
+    #[derive(Resource)]
+    struct Source(u32);
+
+    /// Reaction which records the source value along with the value of a selector derived
+    /// from it.
+    struct ReadBoth {
+        selector: Selector<u32>,
+        seen: Arc<Mutex<Vec<(u32, u32)>>>,
+    }
+
+    impl Reaction for ReadBoth {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let re = Rcx::new(world, tracking);
+            let source = re.use_resource::<Source>().0;
+            let selected = self.selector.get(&re);
+            self.seen.lock().unwrap().push((source, selected));
+        }
+    }
+
+    #[test]
+    fn test_writer_runs_before_reader() {
+        let mut world = World::default();
+        world.insert_resource(Source(1));
+        let mut owner = TrackingScope::new(world.change_tick());
+        let selector = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)
+            .create_selector(|re| re.use_resource::<Source>().0 * 10);
+        // Make the selector deeper than the reader, so that hierarchy order alone would run
+        // the reader first.
+        world.spawn_empty().add_child(selector.id);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut reaction = ReadBoth {
+            selector,
+            seen: seen.clone(),
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let reaction_id = world.spawn_empty().id();
+        reaction.react(reaction_id, &mut world, &mut scope);
+        world
+            .entity_mut(reaction_id)
+            .insert((ReactionHandle::new(reaction), scope));
+        world.increment_change_tick();
+
+        // Both reactions are stale, and the reader sees the updated selector value.
+        world.resource_mut::<Source>().0 = 2;
+        run_reactions(&mut world);
+        assert_eq!(*seen.lock().unwrap(), vec![(1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn test_sort_reactions_cycle() {
+        let mut world = World::default();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        let written_by_a = world.spawn(WrittenBy(a)).id();
+        let written_by_b = world.spawn(WrittenBy(b)).id();
+        let mut scope_a = TrackingScope::new(world.change_tick());
+        scope_a.add_mutable(written_by_b);
+        let mut scope_b = TrackingScope::new(world.change_tick());
+        scope_b.add_mutable(written_by_a);
+        world.entity_mut(a).insert(scope_a);
+        world.entity_mut(b).insert(scope_b);
+        let tick = world.change_tick();
+        world.entity_mut(c).insert(TrackingScope::new(tick));
+
+        // `c` has no writers, so runs first; the cycle between `a` and `b` is broken in order.
+        let sorted = sort_reactions(&world, vec![(0, a), (0, b), (0, c)]);
+        assert_eq!(sorted, vec![(0, c), (0, a), (0, b)]);
+    }
+
+    #[test]
+    fn test_sort_reactions_keeps_ancestors_first() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let writer = world.spawn_empty().id();
+        world.entity_mut(writer).insert(WrittenBy(writer));
+        world.entity_mut(parent).add_child(writer);
+        let mut scope = TrackingScope::new(world.change_tick());
+        scope.add_mutable(writer);
+        world.entity_mut(parent).insert(scope);
+        let tick = world.change_tick();
+        world.entity_mut(writer).insert(TrackingScope::new(tick));
+
+        // The parent reads the mutable written by its child, but still runs first.
+        let sorted = sort_reactions(&world, vec![(0, parent), (1, writer)]);
+        assert_eq!(sorted, vec![(0, parent), (1, writer)]);
+    }
+
+    static CHILD_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn test_parent_reacts_before_child() {
+        let mut world = crate::testing::TestWorld::new();
+        world
+            .world_mut()
+            .init_resource::<crate::diagnostics::ReactorFrameCounts>();
+        let show = world.create_mutable(true);
+        let id = show.id;
+        world.spawn_view(crate::Cond::new(
+            move |re| re.read_mutable::<bool>(id),
+            move || {
+                crate::TextComputed::new(move |re| {
+                    CHILD_RUNS.fetch_add(1, Ordering::Relaxed);
+                    format!("{}", re.read_mutable::<bool>(id))
+                })
+            },
+            || (),
+        ));
+        world.tick();
+        assert_eq!(CHILD_RUNS.load(Ordering::Relaxed), 1);
+        world
+            .world_mut()
+            .insert_resource(crate::diagnostics::ReactorFrameCounts::default());
+
+        // Both the condition and the child depend on the mutable, but the condition runs first
+        // and razes the child, so the child's stale reaction is skipped.
+        world.set_mutable(&show, false);
+        world.tick();
+        assert_eq!(CHILD_RUNS.load(Ordering::Relaxed), 1);
+        let counts = world
+            .world()
+            .resource::<crate::diagnostics::ReactorFrameCounts>();
+        assert_eq!(counts.reactions, 1);
+        assert_eq!(counts.rebuilds, 1);
+    }
+
+    #[test]
+    fn test_rebuild_count() {
+        let mut world = crate::testing::TestWorld::new();
+        world
+            .world_mut()
+            .init_resource::<crate::diagnostics::ReactorFrameCounts>();
+        let count = world.create_mutable(0);
+        let id = count.id;
+        world.spawn_view(crate::TextComputed::new(move |re| {
+            format!("{}", re.read_mutable::<i32>(id))
+        }));
+        world.tick();
+        world
+            .world_mut()
+            .insert_resource(crate::diagnostics::ReactorFrameCounts::default());
+
+        // Updating the text reacts, but keeps the same output node.
+        world.set_mutable(&count, 1);
+        world.tick();
+        let counts = world
+            .world()
+            .resource::<crate::diagnostics::ReactorFrameCounts>();
+        assert_eq!(counts.reactions, 1);
+        assert_eq!(counts.rebuilds, 0);
+    }
+
+    #[test]
+    fn test_scope_without_reaction_not_counted() {
+        let mut world = World::default();
+        world.init_resource::<crate::diagnostics::ReactorFrameCounts>();
+        world.insert_resource(Source(0));
+        let mut scope = TrackingScope::new(world.change_tick());
+        Rcx::new(&world, &mut scope).use_resource::<Source>();
+        world.spawn(scope);
+
+        // A bare scope, like the one spawned by `Bind`, has nothing to run.
+        world.clear_trackers();
+        world.resource_mut::<Source>().0 = 1;
+        run_reactions(&mut world);
+        let counts = world.resource::<crate::diagnostics::ReactorFrameCounts>();
+        assert_eq!(counts.reactions, 0);
+    }
+
+    #[derive(Resource)]
+    struct Position(u32);
+
+    struct ThrottledRead(Arc<AtomicUsize>);
+
+    impl Reaction for ThrottledRead {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let re = Rcx::new(world, tracking);
+            re.use_throttled_resource::<Position>(Duration::from_millis(100));
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Advance the time by 20ms, and move the position.
+    fn move_position(world: &mut World) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(20));
+        world.resource_mut::<Position>().0 += 1;
+        world.increment_change_tick();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        world.insert_resource(Position(0));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let reaction = ReactionHandle::spawn(ThrottledRead(runs.clone()), &mut world);
+
+        // Changing every 20ms reacts at most once per 100ms.
+        for _ in 0..10 {
+            move_position(&mut world);
+            run_reactions(&mut world);
+        }
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+        // Flushing runs the waiting reaction without waiting for the interval.
+        reaction.flush(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+
+        // Flushing does nothing when there are no changes.
+        reaction.flush(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mutable = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)
+            .create_mutable_debounced(0, Duration::from_millis(100));
+        let write = |world: &mut World, value: i32| {
+            let mut owner = TrackingScope::new(world.change_tick());
+            Cx::new(&(), world, Entity::PLACEHOLDER, &mut owner).write_mutable(mutable.id, value);
+        };
+        let advance = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            commit_mutables(world);
+        };
+
+        // Writes every 20ms are not committed until they stop for 100ms.
+        for value in 1..=5 {
+            write(&mut world, value);
+            advance(&mut world, 20);
+            assert_eq!(*mutable.peek(&world), 0);
+        }
+        advance(&mut world, 60);
+        assert_eq!(*mutable.peek(&world), 0);
+        advance(&mut world, 20);
+        assert_eq!(*mutable.peek(&world), 5);
+
+        // Flushing commits immediately.
+        write(&mut world, 6);
+        mutable.flush(&mut world);
+        assert_eq!(*mutable.peek(&world), 6);
+    }
+
+    #[test]
+    fn test_parallel_checks() {
+        bevy::tasks::ComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        for parallelism in [false, true] {
+            let mut world = World::default();
+            world.insert_resource(ReactorSettings { parallelism });
+            world.insert_resource(Source(0));
+            world.insert_resource(Position(0));
+            let sources = Arc::new(AtomicUsize::new(0));
+            let positions = Arc::new(AtomicUsize::new(0));
+            for _ in 0..50 {
+                ReactionHandle::spawn(ReadSource(sources.clone()), &mut world);
+                ReactionHandle::spawn(ReadPosition(positions.clone()), &mut world);
+            }
+
+            // Only the reactions whose dependencies changed are run.
+            world.clear_trackers();
+            world.resource_mut::<Source>().0 = 1;
+            run_reactions(&mut world);
+            assert_eq!(sources.load(Ordering::Relaxed), 100);
+            assert_eq!(positions.load(Ordering::Relaxed), 50);
+        }
+    }
+
+    /// Copies `A` from `source` into `B` on `target`, via a write set.
+    struct CopyA {
+        source: Entity,
+        target: Entity,
+    }
+
+    impl Reaction for CopyA {
+        fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let write = self.compute(owner, world, tracking);
+            write(world);
+        }
+
+        fn write_set(&self) -> Option<Vec<Entity>> {
+            Some(vec![self.target])
+        }
+
+        fn compute(
+            &mut self,
+            _owner: Entity,
+            world: &World,
+            tracking: &mut TrackingScope,
+        ) -> ReactionWrite {
+            let value = Rcx::new(world, tracking)
+                .use_component::<A>(self.source)
                 .unwrap()
-                .react(*scope_entity, world, &mut next_scope);
+                .0;
+            let target = self.target;
+            Box::new(move |world: &mut World| {
+                world.entity_mut(target).insert(B(value));
+            })
         }
-        if let Ok((_, mut scope)) = scopes.get_mut(world, *scope_entity) {
-            // Swap the scopes so that the next scope becomes the current scope.
-            // The old scopes will be dropped at the end of the loop block.
-            scope.take_deps(&mut next_scope);
-            scope.tick = tick;
+    }
+
+    #[test]
+    fn test_parallel_waves() {
+        bevy::tasks::ComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        for parallelism in [false, true] {
+            let mut world = World::default();
+            world.insert_resource(ReactorSettings { parallelism });
+            let sources = [world.spawn(A(1)).id(), world.spawn(A(2)).id()];
+            let targets = [world.spawn_empty().id(), world.spawn_empty().id()];
+            let reactions = [0, 1].map(|i| {
+                ReactionHandle::spawn(
+                    CopyA {
+                        source: sources[i],
+                        target: targets[i],
+                    },
+                    &mut world,
+                )
+            });
+
+            // Both reactions update their own entity in the same pass.
+            world.clear_trackers();
+            world.get_mut::<A>(sources[0]).unwrap().0 = 10;
+            world.get_mut::<A>(sources[1]).unwrap().0 = 20;
+            run_reactions(&mut world);
+            assert_eq!(world.get::<B>(targets[0]).unwrap().0, 10);
+            assert_eq!(world.get::<B>(targets[1]).unwrap().0, 20);
+            let ticks = reactions.map(|reaction| reaction.last_run_tick(&world));
+            // In parallel, the reactions run as one wave at the same tick.
+            assert_eq!(ticks[0] == ticks[1], parallelism);
+        }
+    }
+
+    #[test]
+    fn test_parallel_waves_conflict() {
+        bevy::tasks::ComputeTaskPool::get_or_init(bevy::tasks::TaskPool::default);
+        let mut world = World::default();
+        world.insert_resource(ReactorSettings { parallelism: true });
+        let source = world.spawn(A(1)).id();
+        let target = world.spawn_empty().id();
+        // Both reactions write the same entity, so they can't share a wave.
+        let reactions = [0, 1].map(|_| ReactionHandle::spawn(CopyA { source, target }, &mut world));
+
+        world.clear_trackers();
+        world.get_mut::<A>(source).unwrap().0 = 5;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<B>(target).unwrap().0, 5);
+        assert_ne!(
+            reactions[0].last_run_tick(&world),
+            reactions[1].last_run_tick(&world)
+        );
+    }
+
+    struct ReadSource(Arc<AtomicUsize>);
+
+    impl Reaction for ReadSource {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            Rcx::new(world, tracking).use_resource::<Source>();
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    struct ReadPosition(Arc<AtomicUsize>);
+
+    impl Reaction for ReadPosition {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            Rcx::new(world, tracking).use_resource::<Position>();
+            self.0.fetch_add(1, Ordering::Relaxed);
         }
     }
 }
@@ -0,0 +1,446 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    ecs::event::ManualEventReader,
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::{
+    mutable::{after_write, MutableValue, MutableValueNext},
+    node_span::NodeSpan,
+    DespawnScopes, IntoView, Mutable, Rcx, ReactiveContext, TrackingScope, View, ViewHandle,
+    ViewRef,
+};
+
+/// Height of a line, in logical pixels, when scrolling with a mouse wheel which reports
+/// scroll distances in lines.
+const LINE_HEIGHT: f32 = 20.;
+
+/// Width of the scrollbar track, in logical pixels.
+const SCROLLBAR_WIDTH: f32 = 8.;
+
+/// Component on the viewport node of a [`ScrollView`].
+#[derive(Component)]
+pub(crate) struct ScrollViewport {
+    /// The scroll offset mutable.
+    offset: Entity,
+    /// The content node, which is moved within the viewport.
+    content: Entity,
+}
+
+/// Marker component for the node which contains the content of a [`ScrollView`]. The node is
+/// offset within the viewport by the scroll position.
+#[derive(Component)]
+pub struct ScrollableContent;
+
+/// Marker component for the scrollbar track of a [`ScrollView`].
+#[derive(Component)]
+pub struct ScrollbarTrack;
+
+/// Marker component for the scrollbar thumb of a [`ScrollView`].
+#[derive(Component)]
+pub struct ScrollbarThumb;
+
+/// Component on the view entity of a [`ScrollView`] which holds the scroll offset mutable.
+#[derive(Component)]
+pub(crate) struct ScrollViewOffset(Entity);
+
+/// A viewport which clips its content, and scrolls it by a scroll offset. The offset is held
+/// in a [`Mutable<Vec2>`], which is updated when the mouse wheel is turned over the viewport,
+/// and can also be written directly to scroll programmatically.
+pub struct ScrollView {
+    content: ViewRef,
+    content_entity: Option<Entity>,
+    viewport: Option<Entity>,
+    inner: Option<Entity>,
+    thumb: Option<Entity>,
+    offset: Option<Entity>,
+    scrollbar: bool,
+}
+
+impl ScrollView {
+    /// Construct a new `ScrollView`.
+    pub fn new(content: impl IntoView) -> Self {
+        Self {
+            content: content.into_view(),
+            content_entity: None,
+            viewport: None,
+            inner: None,
+            thumb: None,
+            offset: None,
+            scrollbar: false,
+        }
+    }
+
+    /// Show a vertical scrollbar, whose thumb reflects the scroll position.
+    pub fn with_scrollbar(mut self, scrollbar: bool) -> Self {
+        self.scrollbar = scrollbar;
+        self
+    }
+
+    /// Returns the scroll offset of the `ScrollView` whose view entity is `view_entity`.
+    pub fn scroll_offset_of(view_entity: Entity, world: &World) -> Option<Mutable<Vec2>> {
+        world
+            .get::<ScrollViewOffset>(view_entity)
+            .map(|offset| Mutable {
+                id: offset.0,
+                marker: std::marker::PhantomData,
+            })
+    }
+
+    /// Attach the display nodes of the content to the content node.
+    fn attach_content(&self, world: &mut World) {
+        let nodes = self.content.lock().unwrap().nodes(world).flat_collect();
+        world
+            .entity_mut(self.inner.unwrap())
+            .replace_children(&nodes);
+    }
+}
+
+impl View for ScrollView {
+    fn nodes(&self, _world: &World) -> NodeSpan {
+        match self.viewport {
+            None => NodeSpan::Empty,
+            Some(node) => NodeSpan::Node(node),
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.viewport.is_none());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let offset = world
+            .spawn(MutableValue {
+                value: Box::new(Vec2::ZERO),
+            })
+            .id();
+        tracking.add_owned(offset);
+        self.offset = Some(offset);
+        world
+            .entity_mut(view_entity)
+            .insert(ScrollViewOffset(offset));
+
+        let inner = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        flex_direction: FlexDirection::Column,
+                        min_width: Val::Percent(100.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ScrollableContent,
+            ))
+            .id();
+        let viewport = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Interaction::None,
+                ScrollViewport {
+                    offset,
+                    content: inner,
+                },
+            ))
+            .add_child(inner)
+            .id();
+        if self.scrollbar {
+            let thumb = world
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.),
+                            height: Val::Percent(100.),
+                            ..default()
+                        },
+                        background_color: Color::rgba(1., 1., 1., 0.3).into(),
+                        ..default()
+                    },
+                    ScrollbarThumb,
+                ))
+                .id();
+            let track = world
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.),
+                            bottom: Val::Px(0.),
+                            right: Val::Px(0.),
+                            width: Val::Px(SCROLLBAR_WIDTH),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ScrollbarTrack,
+                ))
+                .add_child(thumb)
+                .id();
+            world.entity_mut(viewport).add_child(track);
+            self.thumb = Some(thumb);
+        }
+        self.viewport = Some(viewport);
+        self.inner = Some(inner);
+
+        self.content_entity = Some(ViewHandle::spawn(&self.content, view_entity, world));
+        self.attach_content(world);
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let offset = re.read_mutable::<Vec2>(self.offset.unwrap());
+        // The thumb is resized when the layout of the viewport or the content changes.
+        let sizes = self.thumb.map(|_| {
+            (
+                re.use_component::<Node>(self.viewport.unwrap())
+                    .map(Node::size),
+                re.use_component::<Node>(self.inner.unwrap())
+                    .map(Node::size),
+            )
+        });
+        if let Some(mut style) = world.get_mut::<Style>(self.inner.unwrap()) {
+            let (left, top) = (Val::Px(-offset.x), Val::Px(-offset.y));
+            if style.left != left || style.top != top {
+                style.left = left;
+                style.top = top;
+            }
+        }
+
+        // Size and position the thumb as a fraction of the content height.
+        let (Some(thumb), Some((viewport, content))) = (self.thumb, sizes) else {
+            return;
+        };
+        let (top, height) = match (viewport, content) {
+            (Some(viewport), Some(content)) if content.y > viewport.y => {
+                (offset.y / content.y * 100., viewport.y / content.y * 100.)
+            }
+            _ => (0., 100.),
+        };
+        if let Some(mut style) = world.get_mut::<Style>(thumb) {
+            style.top = Val::Percent(top);
+            style.height = Val::Percent(height);
+        }
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        self.attach_content(world);
+        true
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.content_entity.take() {
+            self.content.lock().unwrap().raze(entity, world);
+        }
+        if let Some(viewport) = self.viewport.take() {
+            world.entity_mut(viewport).remove_parent();
+            world.entity_mut(viewport).despawn_recursive();
+        }
+        self.inner = None;
+        self.thumb = None;
+        self.offset = None;
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl IntoView for ScrollView {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// System which scrolls hovered [`ScrollView`]s in response to the mouse wheel. The scroll
+/// offset is clamped so that the content cannot be scrolled out of the viewport.
+pub fn scroll_on_mouse_wheel(world: &mut World, mut reader: Local<ManualEventReader<MouseWheel>>) {
+    let Some(events) = world.get_resource::<Events<MouseWheel>>() else {
+        return;
+    };
+    let delta = reader
+        .read(events)
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => Vec2::new(event.x, event.y) * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => Vec2::new(event.x, event.y),
+        })
+        .sum::<Vec2>();
+    if delta == Vec2::ZERO {
+        return;
+    }
+    let mut viewports = world.query::<(&ScrollViewport, &Interaction, &Node)>();
+    let mut contents = world.query_filtered::<&Node, With<ScrollableContent>>();
+    let mut offsets = world.query::<(&MutableValue, Option<&MutableValueNext>)>();
+    let mut scrolled = Vec::new();
+    for (viewport, interaction, node) in viewports.iter(world) {
+        if *interaction != Interaction::Hovered {
+            continue;
+        }
+        let Ok((value, next)) = offsets.get(world, viewport.offset) else {
+            continue;
+        };
+        let current = next
+            .and_then(|next| next.0.downcast_ref::<Vec2>())
+            .or_else(|| value.value.downcast_ref::<Vec2>())
+            .copied()
+            .unwrap_or_default();
+        let max = contents
+            .get(world, viewport.content)
+            .map(|content| (content.size() - node.size()).max(Vec2::ZERO))
+            .unwrap_or_default();
+        // Turning the wheel away from the user moves the content up.
+        let offset = (current - delta).clamp(Vec2::ZERO, max);
+        if offset != current {
+            scrolled.push((viewport.offset, offset));
+        }
+    }
+    for (mutable, offset) in scrolled {
+        world
+            .entity_mut(mutable)
+            .insert(MutableValueNext(Box::new(offset)));
+        after_write(world, mutable);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{commit_mutables, cx::Cx, run_reactions, ReactiveContextMut};
+
+    use super::*;
+
+    fn content_style(world: &World, view: &ViewRef) -> Style {
+        let viewport = view.lock().unwrap().nodes(world).flat_collect()[0];
+        let inner = world.get::<ScrollViewport>(viewport).unwrap().content;
+        world.get::<Style>(inner).unwrap().clone()
+    }
+
+    #[test]
+    fn test_scroll_view() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = ScrollView::new(("First", "Second")).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(content_style(&world, &view).top, Val::Px(0.));
+        assert_eq!(world.query::<&ScrollbarThumb>().iter(&world).count(), 0);
+
+        // The content is a child of the scrollable content node.
+        let viewport = view.lock().unwrap().nodes(&world).flat_collect()[0];
+        let inner = world.get::<ScrollViewport>(viewport).unwrap().content;
+        assert_eq!(world.get::<Children>(inner).unwrap().len(), 2);
+
+        // Writing the offset moves the content.
+        let offset = ScrollView::scroll_offset_of(entity, &world).unwrap();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut cx = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut scope);
+        cx.write_mutable(offset.id, Vec2::new(0., 100.));
+        world.increment_change_tick();
+        commit_mutables(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(content_style(&world, &view).top, Val::Px(-100.));
+
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(viewport).is_none());
+        assert!(world.get_entity(offset.id).is_none());
+    }
+
+    #[test]
+    fn test_scrollbar() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = ScrollView::new("Content").with_scrollbar(true).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(world.query::<&ScrollbarTrack>().iter(&world).count(), 1);
+        assert_eq!(world.query::<&ScrollbarThumb>().iter(&world).count(), 1);
+
+        // The thumb depends on the layout of the viewport and the content.
+        let plain = ScrollView::new("Content").into_view();
+        let plain = ViewHandle::spawn(&plain, parent, &mut world);
+        let deps = |entity| {
+            world
+                .get::<TrackingScope>(entity)
+                .unwrap()
+                .dependency_count()
+        };
+        assert!(deps(entity) > deps(plain));
+    }
+
+    fn set_size(world: &mut World, entity: Entity, size: Vec2) {
+        let mut node = world.get_mut::<Node>(entity).unwrap();
+        *node.get_field_mut::<Vec2>("calculated_size").unwrap() = size;
+    }
+
+    #[test]
+    fn test_scroll_on_mouse_wheel() {
+        let mut world = World::default();
+        world.init_resource::<Events<MouseWheel>>();
+        let parent = world.spawn_empty().id();
+        let view = ScrollView::new("Content").into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let viewport = view.lock().unwrap().nodes(&world).flat_collect()[0];
+        let inner = world.get::<ScrollViewport>(viewport).unwrap().content;
+        let offset = ScrollView::scroll_offset_of(entity, &world).unwrap();
+
+        // The content can be scrolled down by at most 200 pixels.
+        set_size(&mut world, viewport, Vec2::new(100., 100.));
+        set_size(&mut world, inner, Vec2::new(100., 300.));
+        *world.get_mut::<Interaction>(viewport).unwrap() = Interaction::Hovered;
+
+        let mut system = IntoSystem::into_system(scroll_on_mouse_wheel);
+        system.initialize(&mut world);
+        let mut scroll = |world: &mut World, unit: MouseScrollUnit, y: f32| {
+            world.send_event(MouseWheel {
+                unit,
+                x: 0.,
+                y,
+                window: Entity::PLACEHOLDER,
+            });
+            system.run((), world);
+            let pending = world.get::<MutableValueNext>(offset.id).is_some();
+            world.increment_change_tick();
+            commit_mutables(world);
+            run_reactions(world);
+            (pending, content_style(world, &view).top)
+        };
+
+        // Scrolling up from the top is clamped, so nothing is written.
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Line, 1.),
+            (false, Val::Px(0.))
+        );
+
+        // Scrolling down writes the offset, and readers react once it is committed.
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Line, -3.),
+            (true, Val::Px(-60.))
+        );
+
+        // Scrolling past the bottom is clamped to the end of the content.
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Pixel, -500.),
+            (true, Val::Px(-200.))
+        );
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Pixel, -10.),
+            (false, Val::Px(-200.))
+        );
+
+        // Scrolling back up past the top is clamped to the start.
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Line, 20.),
+            (true, Val::Px(0.))
+        );
+
+        // The viewport only scrolls while it is hovered.
+        *world.get_mut::<Interaction>(viewport).unwrap() = Interaction::None;
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Line, -1.),
+            (false, Val::Px(0.))
+        );
+    }
+}
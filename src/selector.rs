@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{mutable::MutableValue, Rcx, Reaction, ReactiveContext, TrackingScope};
+
+/// A handle to a memoized value which is derived from other reactive data sources. Created
+/// via [`ReactiveContextMut::create_selector`](crate::ReactiveContextMut::create_selector).
+///
+/// The selector is recomputed whenever its dependencies change, but readers of the selector
+/// only react when the computed value is different from the previous value. Handles are
+/// cheap to copy, and can be shared between presenters.
+pub struct Selector<T> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<T>,
+}
+
+impl<T> Selector<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Read the current value of the selector. Calling this function adds the selector to the
+    /// current tracking scope.
+    pub fn get<'p, R: ReactiveContext<'p>>(&self, rc: &R) -> T {
+        rc.read_mutable_clone(self.id)
+    }
+}
+
+impl<T> Clone for Selector<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Selector<T> {}
+
+/// Reaction which recomputes the value of a selector.
+pub(crate) struct SelectorReaction<T, F: Fn(&Rcx) -> T> {
+    pub(crate) func: F,
+    pub(crate) marker: PhantomData<T>,
+}
+
+impl<T, F> Reaction for SelectorReaction<T, F>
+where
+    T: PartialEq + Send + Sync + 'static,
+    F: Fn(&Rcx) -> T,
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let value = (self.func)(&re);
+        // Only update the value if it changed, so that readers don't react needlessly.
+        let mut entt = world.entity_mut(owner);
+        let Some(mut current) = entt.get_mut::<MutableValue>() else {
+            return;
+        };
+        if current.value.downcast_ref::<T>() != Some(&value) {
+            current.value = Box::new(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cx::Cx, run_reactions, ReactiveContextMut};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Settings {
+        volume: u32,
+        name: String,
+    }
+
+    #[test]
+    fn test_selector() {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            volume: 1,
+            name: "a".to_string(),
+        });
+        let mut owner = TrackingScope::new(world.change_tick());
+        let selector = Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner)
+            .create_selector(|re| re.use_resource::<Settings>().volume);
+
+        let mut subscriber = TrackingScope::new(world.change_tick());
+        assert_eq!(
+            selector.get(&Cx::new(
+                &(),
+                &mut world,
+                Entity::PLACEHOLDER,
+                &mut subscriber
+            )),
+            1
+        );
+        world.clear_trackers();
+
+        // Changing a field which the selector doesn't read.
+        world.resource_mut::<Settings>().name = "b".to_string();
+        run_reactions(&mut world);
+        assert!(!subscriber.dependencies_changed(&world));
+        assert_eq!(world.resource::<Settings>().name, "b");
+        world.clear_trackers();
+
+        // Changing the selected field.
+        world.resource_mut::<Settings>().volume = 2;
+        run_reactions(&mut world);
+        assert!(subscriber.dependencies_changed(&world));
+        assert_eq!(
+            selector.get(&Cx::new(
+                &(),
+                &mut world,
+                Entity::PLACEHOLDER,
+                &mut subscriber
+            )),
+            2
+        );
+    }
+}
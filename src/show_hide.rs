@@ -0,0 +1,193 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, IntoView, Rcx, TrackingScope, View,
+    ViewHandle, ViewRef,
+};
+
+/// A view which shows or hides its child depending on a reactive condition. Unlike
+/// [`Cond`](crate::Cond), the child is built only once, and is never razed while the
+/// `ShowHide` lives; hiding only changes the [`Visibility`] of the child's display nodes.
+///
+/// By default, hidden nodes still take up space in the layout. Use
+/// [`with_collapse`](ShowHide::with_collapse) to also remove them from the layout. The
+/// original visibility and display mode of each node are restored when it is shown again.
+pub struct ShowHide<F: Fn(&Rcx) -> bool> {
+    visible_fn: F,
+    child: ViewRef,
+    child_entity: Option<Entity>,
+    visible: bool,
+    collapse: bool,
+    /// The visibility and display mode of each hidden node, from before it was hidden.
+    saved: HashMap<Entity, (Visibility, Option<Display>)>,
+}
+
+impl<F: Fn(&Rcx) -> bool> ShowHide<F> {
+    /// Construct a new `ShowHide` view.
+    pub fn new(visible_fn: F, child: impl IntoView) -> Self {
+        Self {
+            visible_fn,
+            child: child.into_view(),
+            child_entity: None,
+            visible: true,
+            collapse: false,
+            saved: HashMap::default(),
+        }
+    }
+
+    /// Also set [`Display::None`] on the child's nodes while hidden, so that they don't take
+    /// up any space in the layout.
+    pub fn with_collapse(mut self) -> Self {
+        self.collapse = true;
+        self
+    }
+
+    /// Update the display nodes of the child to match the current visibility.
+    fn apply_visibility(&mut self, world: &mut World) {
+        let nodes = self.nodes(world).flat_collect();
+        if self.visible {
+            for node in nodes {
+                if let Some((visibility, display)) = self.saved.remove(&node) {
+                    restore(world, node, visibility, display);
+                }
+            }
+            // Forget nodes which were replaced while hidden.
+            self.saved.clear();
+            return;
+        }
+        for node in nodes {
+            let Some(visibility) = world.get::<Visibility>(node).copied() else {
+                continue;
+            };
+            let display = world.get::<Style>(node).map(|style| style.display);
+            self.saved
+                .entry(node)
+                .or_insert((visibility, display.filter(|_| self.collapse)));
+            let display = self.collapse.then_some(Display::None);
+            restore(world, node, Visibility::Hidden, display);
+        }
+    }
+}
+
+/// Set the visibility and, if given, the display mode of a node, if they differ.
+fn restore(world: &mut World, node: Entity, visibility: Visibility, display: Option<Display>) {
+    if let Some(mut current) = world.get_mut::<Visibility>(node) {
+        if *current != visibility {
+            *current = visibility;
+        }
+    }
+    let (Some(display), Some(mut style)) = (display, world.get_mut::<Style>(node)) else {
+        return;
+    };
+    if style.display != display {
+        style.display = display;
+    }
+}
+
+impl<F: Fn(&Rcx) -> bool> View for ShowHide<F> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.child_entity {
+            Some(entity) => child_nodes(&self.child, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        self.child_entity = Some(ViewHandle::spawn(&self.child, view_entity, world));
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.visible = (self.visible_fn)(&Rcx::new(world, &mut tracking));
+        self.apply_visibility(world);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let visible = (self.visible_fn)(&Rcx::new(world, tracking));
+        if visible != self.visible {
+            self.visible = visible;
+            self.apply_visibility(world);
+        }
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        // New display nodes from the child need to be hidden as well.
+        self.apply_visibility(world);
+        false
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.child_entity.take() {
+            self.child.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<F: Send + Sync + 'static + Fn(&Rcx) -> bool> IntoView for ShowHide<F> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Element, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Shown(bool);
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    #[test]
+    fn test_show_hide() {
+        let mut world = World::default();
+        world.insert_resource(Shown(true));
+        let parent = world.spawn_empty().id();
+        let view = ShowHide::new(
+            |cx| cx.use_resource::<Shown>().0,
+            Element::<NodeBundle>::new().insert(Style {
+                display: Display::Grid,
+                ..default()
+            }),
+        )
+        .with_collapse()
+        .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let nodes = view.lock().unwrap().nodes(&world).flat_collect();
+        assert_eq!(nodes.len(), 1);
+        let node = nodes[0];
+        assert_eq!(
+            *world.get::<Visibility>(node).unwrap(),
+            Visibility::Inherited
+        );
+
+        world.resource_mut::<Shown>().0 = false;
+        react(&view, entity, &mut world);
+        assert_eq!(*world.get::<Visibility>(node).unwrap(), Visibility::Hidden);
+        assert_eq!(world.get::<Style>(node).unwrap().display, Display::None);
+
+        // The child is not rebuilt when shown again.
+        world.resource_mut::<Shown>().0 = true;
+        react(&view, entity, &mut world);
+        assert_eq!(
+            view.lock().unwrap().nodes(&world).flat_collect(),
+            vec![node]
+        );
+        assert_eq!(
+            *world.get::<Visibility>(node).unwrap(),
+            Visibility::Inherited
+        );
+        // The original display mode is restored.
+        assert_eq!(world.get::<Style>(node).unwrap().display, Display::Grid);
+
+        // Razing despawns the child.
+        view.lock().unwrap().raze(entity, &mut world);
+        assert!(world.get_entity(node).is_none());
+    }
+}
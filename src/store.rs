@@ -0,0 +1,219 @@
+use std::{any::TypeId, marker::PhantomData, sync::Arc};
+
+use bevy::{prelude::*, reflect::GetPath, utils::HashMap};
+
+use crate::{
+    mutable::{after_write, MutableValue, MutableValueNext},
+    scope::TrackedProjection,
+    ReactiveContext,
+};
+
+/// Resource which maps each store type to the entity which holds its value.
+#[derive(Resource, Default)]
+pub(crate) struct Stores(pub(crate) HashMap<TypeId, Entity>);
+
+/// A handle to a global reactive store, created via
+/// [`ReactiveContextMut::use_store`](crate::ReactiveContextMut::use_store).
+///
+/// There is a single store for each type `S`, which is shared by all presenters in the app.
+/// Unlike a [`Mutable`](crate::Mutable), the store is not owned by any presenter, and so
+/// it persists when the presenters which use it are razed. Readers of the whole store react
+/// whenever a new value is written, while readers of a single field, via
+/// [`get_field`](StoreHandle::get_field), only react when that field changes.
+pub struct StoreHandle<S> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<S>,
+}
+
+impl<S: Send + Sync + 'static> StoreHandle<S> {
+    /// Return a reference to the current value of the store. Calling this function adds the
+    /// store to the current tracking scope.
+    pub fn get<'a, 'p, R: ReactiveContext<'p>>(&self, rc: &'a R) -> &'a S {
+        rc.tracking().add_mutable(self.id);
+        rc.world()
+            .get::<MutableValue>(self.id)
+            .unwrap()
+            .value
+            .downcast_ref::<S>()
+            .unwrap()
+    }
+
+    /// Return a copy of a field of the store, given its [`Reflect`] path, such as `"volume"` or
+    /// `"audio.volume"`. Calling this function subscribes to that field only, so the caller
+    /// doesn't react when a write leaves the field unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path doesn't name a field of type `F`.
+    pub fn get_field<'p, F>(&self, rc: &impl ReactiveContext<'p>, path: &'static str) -> F
+    where
+        S: Reflect,
+        F: Reflect + PartialEq + Clone,
+    {
+        let lens = Arc::new(move |store: &S| match store.path::<F>(path) {
+            Ok(field) => field.clone(),
+            Err(err) => panic!("Invalid store field {:?}: {}", path, err),
+        });
+        let value = lens(
+            rc.world()
+                .get::<MutableValue>(self.id)
+                .unwrap()
+                .value
+                .downcast_ref::<S>()
+                .unwrap(),
+        );
+        rc.tracking().add_projection(TrackedProjection {
+            source: self.id,
+            lens,
+            value: value.clone(),
+        });
+        value
+    }
+
+    /// Replace the value of the store. Like writes to a [`Mutable`](crate::Mutable), the new
+    /// value is committed by the reactor systems, and is visible on the next update.
+    pub fn set(&self, world: &mut World, value: S) {
+        world
+            .entity_mut(self.id)
+            .insert(MutableValueNext(Box::new(value)));
+        after_write(world, self.id);
+    }
+}
+
+impl<S> Clone for StoreHandle<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for StoreHandle<S> {}
+
+/// Return the entity which holds the store of type `S`, creating it if needed.
+pub(crate) fn store_entity<S: Default + Send + Sync + 'static>(world: &mut World) -> Entity {
+    let type_id = TypeId::of::<S>();
+    if let Some(id) = world
+        .get_resource::<Stores>()
+        .and_then(|stores| stores.0.get(&type_id))
+    {
+        return *id;
+    }
+    let id = world
+        .spawn(MutableValue {
+            value: Box::new(S::default()),
+        })
+        .id();
+    world
+        .get_resource_or_insert_with(Stores::default)
+        .0
+        .insert(type_id, id);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::{testing::TestWorld, Cx, IntoView, Presenter, ReactiveContextMut, ViewHandle};
+
+    use super::*;
+
+    #[derive(Default, Reflect)]
+    struct Settings {
+        volume: u32,
+        muted: bool,
+    }
+
+    static VOLUME_RUNS: AtomicU32 = AtomicU32::new(0);
+    static MUTED_RUNS: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(crate::Presenter)]
+    struct Volume;
+
+    impl Presenter for Volume {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            VOLUME_RUNS.fetch_add(1, Ordering::Relaxed);
+            let store = cx.use_store::<Settings>();
+            format!("volume {}", store.get(cx).volume)
+        }
+    }
+
+    #[derive(crate::Presenter)]
+    struct Muted;
+
+    impl Presenter for Muted {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            MUTED_RUNS.fetch_add(1, Ordering::Relaxed);
+            let store = cx.use_store::<Settings>();
+            format!("muted {}", store.get_field::<bool>(cx, "muted"))
+        }
+    }
+
+    fn text(world: &TestWorld, view: Entity) -> String {
+        let node = world.get_node_entities(view)[0];
+        world.world().get::<Text>(node).unwrap().sections[0]
+            .value
+            .clone()
+    }
+
+    fn store(world: &mut TestWorld) -> StoreHandle<Settings> {
+        StoreHandle {
+            id: store_entity::<Settings>(world.world_mut()),
+            marker: PhantomData,
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let mut world = TestWorld::new();
+        let volume = world.spawn_view(Volume);
+        let muted = world.spawn_view(Muted);
+        world.tick();
+        assert_eq!(text(&world, volume), "volume 0");
+        assert_eq!(text(&world, muted), "muted false");
+        let runs = || {
+            (
+                VOLUME_RUNS.load(Ordering::Relaxed),
+                MUTED_RUNS.load(Ordering::Relaxed),
+            )
+        };
+        assert_eq!(runs(), (1, 1));
+
+        // Both presenters share the same store. The reader of the whole store reacts to every
+        // write, while the reader of a field only reacts when that field changes.
+        let settings = store(&mut world);
+        settings.set(
+            world.world_mut(),
+            Settings {
+                volume: 7,
+                muted: false,
+            },
+        );
+        world.tick();
+        assert_eq!(text(&world, volume), "volume 7");
+        assert_eq!(runs(), (2, 1));
+
+        settings.set(
+            world.world_mut(),
+            Settings {
+                volume: 7,
+                muted: true,
+            },
+        );
+        world.tick();
+        assert_eq!(text(&world, volume), "volume 7");
+        assert_eq!(text(&world, muted), "muted true");
+        assert_eq!(runs(), (3, 2));
+
+        // The store outlives the presenters that use it.
+        let view = world
+            .world()
+            .get::<ViewHandle>(volume)
+            .unwrap()
+            .view
+            .clone();
+        view.lock().unwrap().raze(volume, world.world_mut());
+        let volume = world.spawn_view(Volume);
+        world.tick();
+        assert_eq!(text(&world, volume), "volume 7");
+    }
+}
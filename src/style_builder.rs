@@ -0,0 +1,251 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{prelude::*, ui::Style};
+
+use crate::{
+    bundle::{spawn_reaction, stop_reaction},
+    BundleProducer, Rcx, Reaction, ReactionHandle, ReactionWrite, TrackingScope,
+};
+
+type StyleFn = Box<dyn Fn(&Rcx, &mut Style) + Send + Sync>;
+type ColorFn = Box<dyn Fn(&Rcx) -> Color + Send + Sync>;
+
+/// Reactively updates the [`Style`] of the target entity in place, one field at a time. All of
+/// the fields are computed by a single reaction, so they are updated together, and the
+/// [`Style`] is only written, triggering change detection, if one of the fields changed.
+///
+/// ```ignore
+/// Element::<NodeBundle>::new().insert_producer(
+///     StyleBuilder::new()
+///         .width(|re| Val::Px(re.use_resource::<PanelSize>().width))
+///         .height(|_| Val::Px(100.)),
+/// )
+/// ```
+pub struct StyleBuilder {
+    reaction: Arc<Mutex<StyleReaction>>,
+    reaction_id: Option<Entity>,
+}
+
+impl StyleBuilder {
+    /// Construct a new, empty `StyleBuilder`.
+    pub fn new() -> Self {
+        Self {
+            reaction: Arc::new(Mutex::new(StyleReaction {
+                target: None,
+                updates: Vec::new(),
+                background_color: None,
+            })),
+            reaction_id: None,
+        }
+    }
+
+    /// Reactively modify the style with `update`.
+    pub fn update(self, update: impl Fn(&Rcx, &mut Style) + Send + Sync + 'static) -> Self {
+        self.reaction.lock().unwrap().updates.push(Box::new(update));
+        self
+    }
+
+    /// Reactively compute the width of the style.
+    pub fn width(self, width: impl Fn(&Rcx) -> Val + Send + Sync + 'static) -> Self {
+        self.update(move |re, style| style.width = width(re))
+    }
+
+    /// Reactively compute the height of the style.
+    pub fn height(self, height: impl Fn(&Rcx) -> Val + Send + Sync + 'static) -> Self {
+        self.update(move |re, style| style.height = height(re))
+    }
+
+    /// Reactively compute the [`BackgroundColor`] of the target entity, which is updated by
+    /// the same reaction as the style.
+    pub fn background_color(self, color: impl Fn(&Rcx) -> Color + Send + Sync + 'static) -> Self {
+        self.reaction.lock().unwrap().background_color = Some(Box::new(color));
+        self
+    }
+}
+
+impl Default for StyleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reaction which computes the fields of a [`StyleBuilder`].
+struct StyleReaction {
+    target: Option<Entity>,
+    updates: Vec<StyleFn>,
+    background_color: Option<ColorFn>,
+}
+
+impl Reaction for StyleReaction {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let write = self.compute(owner, world, tracking);
+        write(world);
+    }
+
+    fn write_set(&self) -> Option<Vec<Entity>> {
+        self.target.map(|target| vec![target])
+    }
+
+    fn compute(
+        &mut self,
+        _owner: Entity,
+        world: &World,
+        tracking: &mut TrackingScope,
+    ) -> ReactionWrite {
+        let target = self.target.unwrap();
+        // The updates are run on a copy of the style, since they need to read from the world
+        // at the same time.
+        let Some(mut style) = world.get::<Style>(target).cloned() else {
+            return Box::new(|_| {});
+        };
+        let re = Rcx::new(world, tracking);
+        for update in self.updates.iter() {
+            update(&re, &mut style);
+        }
+        let color = self.background_color.as_ref().map(|color| color(&re));
+
+        Box::new(move |world| {
+            // Only write back the values which changed, so as not to trigger change detection.
+            let Some(mut entt) = world.get_entity_mut(target) else {
+                return;
+            };
+            if let Some(mut current) = entt.get_mut::<Style>() {
+                if *current != style {
+                    *current = style;
+                }
+            }
+            match (color, entt.get_mut::<BackgroundColor>()) {
+                (Some(color), Some(mut current)) => {
+                    if current.0 != color {
+                        current.0 = color;
+                    }
+                }
+                (Some(color), None) => {
+                    entt.insert(BackgroundColor(color));
+                }
+                (None, _) => {}
+            }
+        })
+    }
+}
+
+impl BundleProducer for StyleBuilder {
+    fn start(&mut self, parent_scope: &mut TrackingScope, target: Entity, world: &mut World) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        if world.get::<Style>(target).is_none() {
+            world.entity_mut(target).insert(Style::default());
+        }
+
+        let mut reaction = self.reaction.lock().unwrap();
+        reaction.target = Some(target);
+        let reaction_id = spawn_reaction(ReactionHandle(self.reaction.clone()), target, world);
+        self.reaction_id = Some(reaction_id);
+        reaction.react(reaction_id, world, &mut scope);
+        world.entity_mut(reaction_id).insert(scope);
+        parent_scope.add_owned(reaction_id);
+    }
+
+    fn stop(&mut self, _owner: Entity, _target: Entity, world: &mut World) {
+        stop_reaction(&self.reaction, &mut self.reaction_id, world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::component::Tick;
+
+    use crate::{cx::Cx, run_reactions, DespawnScopes, ReactiveContext};
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Size {
+        width: f32,
+        height: f32,
+    }
+
+    fn style_changed(world: &World, target: Entity, since: Tick) -> bool {
+        world
+            .entity(target)
+            .get_change_ticks::<Style>()
+            .unwrap()
+            .is_changed(since, world.read_change_tick())
+    }
+
+    #[test]
+    fn test_style_builder() {
+        let mut world = World::default();
+        world.insert_resource(Size {
+            width: 10.,
+            height: 20.,
+        });
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let mut producer = StyleBuilder::new()
+            .width(|re| Val::Px(re.use_resource::<Size>().width))
+            .height(|re| Val::Px(re.use_resource::<Size>().height))
+            .background_color(|_| Color::RED);
+        producer.start(&mut scope, target, &mut world);
+        let style = world.get::<Style>(target).unwrap();
+        assert_eq!((style.width, style.height), (Val::Px(10.), Val::Px(20.)));
+        assert_eq!(world.get::<BackgroundColor>(target).unwrap().0, Color::RED);
+
+        // A change which doesn't affect any field doesn't touch the style.
+        let before = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Size>().width = 10.;
+        run_reactions(&mut world);
+        assert!(!style_changed(&world, target, before));
+
+        // Changing one field leaves the others alone.
+        let before = world.change_tick();
+        world.increment_change_tick();
+        world.resource_mut::<Size>().width = 30.;
+        run_reactions(&mut world);
+        assert!(style_changed(&world, target, before));
+        let style = world.get::<Style>(target).unwrap();
+        assert_eq!((style.width, style.height), (Val::Px(30.), Val::Px(20.)));
+
+        // Several fields are updated together.
+        world.increment_change_tick();
+        let mut size = world.resource_mut::<Size>();
+        size.width = 40.;
+        size.height = 50.;
+        run_reactions(&mut world);
+        let style = world.get::<Style>(target).unwrap();
+        assert_eq!((style.width, style.height), (Val::Px(40.), Val::Px(50.)));
+    }
+
+    #[test]
+    fn test_use_computed_style() {
+        let mut world = World::default();
+        world.insert_resource(Size {
+            width: 10.,
+            height: 20.,
+        });
+        let entity = world.spawn_empty().id();
+        let target = world.spawn(Style::default()).id();
+        let mut scope = TrackingScope::new(world.change_tick());
+
+        // The reaction is only created the first time.
+        for _ in 0..2 {
+            let mut cx = Cx::new(&(), &mut world, entity, &mut scope);
+            cx.use_computed_style(target, |re, style| {
+                style.width = Val::Px(re.use_resource::<Size>().width)
+            });
+        }
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(10.));
+        let mut reactions = world.query::<&ReactionHandle>();
+        assert_eq!(reactions.iter(&world).count(), 1);
+
+        world.increment_change_tick();
+        world.resource_mut::<Size>().width = 30.;
+        run_reactions(&mut world);
+        assert_eq!(world.get::<Style>(target).unwrap().width, Val::Px(30.));
+
+        // Razing the presenter stops the reaction.
+        world.entity_mut(entity).insert(scope);
+        world.despawn_owned_recursive(entity);
+        assert_eq!(reactions.iter(&world).count(), 0);
+    }
+}
@@ -0,0 +1,255 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// Component on the view entity of a [`Suspense`] which holds the set of descendant
+/// presenters which are currently suspended.
+#[derive(Component, Default)]
+pub(crate) struct SuspenseState(pub(crate) HashSet<Entity>);
+
+/// A handle which allows a suspended presenter to resume, or suspend again, from outside of
+/// the presenter. Returned by [`Cx::suspend`](crate::Cx::suspend).
+#[derive(Clone, Copy)]
+pub struct SuspendHandle {
+    pub(crate) boundary: Entity,
+    pub(crate) entity: Entity,
+}
+
+impl SuspendHandle {
+    /// Mark the presenter as not ready.
+    pub fn suspend(&self, world: &mut World) {
+        if let Some(mut state) = world.get_mut::<SuspenseState>(self.boundary) {
+            if !state.0.contains(&self.entity) {
+                state.0.insert(self.entity);
+            }
+        }
+    }
+
+    /// Mark the presenter as ready.
+    pub fn resume(&self, world: &mut World) {
+        set_resumed(world, self.boundary, self.entity);
+    }
+}
+
+/// Returns the entity of the nearest [`Suspense`] enclosing the given view entity.
+pub(crate) fn find_boundary(world: &World, entity: Entity) -> Option<Entity> {
+    let mut e = entity;
+    loop {
+        let entt = world.get_entity(e)?;
+        if entt.contains::<SuspenseState>() {
+            return Some(e);
+        }
+        e = entt.get::<Parent>()?.get();
+    }
+}
+
+/// Remove a presenter from the suspended set of a boundary. The state is only modified if
+/// the presenter was suspended, to avoid triggering a needless reaction.
+pub(crate) fn set_resumed(world: &mut World, boundary: Entity, entity: Entity) {
+    if let Some(mut state) = world.get_mut::<SuspenseState>(boundary) {
+        if state.0.contains(&entity) {
+            state.0.remove(&entity);
+        }
+    }
+}
+
+/// A view which displays a placeholder while any of the presenters within its child are
+/// suspended, that is, not yet ready to be shown. Presenters suspend themselves by calling
+/// [`Cx::suspend`](crate::Cx::suspend).
+///
+/// The child is built up front, so that its presenters can run, but its display nodes are
+/// hidden until none of them are suspended. Each presenter only suspends the nearest enclosing
+/// `Suspense`.
+pub struct Suspense {
+    placeholder: ViewRef,
+    child: ViewRef,
+    child_entity: Option<Entity>,
+    placeholder_entity: Option<Entity>,
+}
+
+impl Suspense {
+    /// Construct a new `Suspense` view.
+    pub fn new(placeholder: impl IntoView, child: impl IntoView) -> Self {
+        Self {
+            placeholder: placeholder.into_view(),
+            child: child.into_view(),
+            child_entity: None,
+            placeholder_entity: None,
+        }
+    }
+
+    /// Returns true if the placeholder is currently being displayed.
+    pub fn is_suspended(&self) -> bool {
+        self.placeholder_entity.is_some()
+    }
+
+    /// Hide or show the display nodes of the child.
+    fn set_child_visibility(&self, world: &mut World) {
+        let visibility = if self.is_suspended() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        let Some(child_entity) = self.child_entity else {
+            return;
+        };
+        for node in child_nodes(&self.child, child_entity, world).iter() {
+            if let Some(mut current) = world.get_mut::<Visibility>(node) {
+                if *current != visibility {
+                    *current = visibility;
+                }
+            }
+        }
+    }
+}
+
+impl View for Suspense {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match (self.placeholder_entity, self.child_entity) {
+            (Some(entity), _) => child_nodes(&self.placeholder, entity, world),
+            (None, Some(entity)) => child_nodes(&self.child, entity, world),
+            (None, None) => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert(SuspenseState::default());
+        self.child_entity = Some(ViewHandle::spawn(&self.child, view_entity, world));
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let state_id = world.init_component::<SuspenseState>();
+        tracking.add_component(view_entity, state_id);
+        let suspended = world
+            .get::<SuspenseState>(view_entity)
+            .map(|state| !state.0.is_empty())
+            .unwrap_or(false);
+        if suspended == self.is_suspended() {
+            return;
+        }
+
+        if suspended {
+            self.placeholder_entity =
+                Some(ViewHandle::spawn(&self.placeholder, view_entity, world));
+        } else if let Some(entity) = self.placeholder_entity.take() {
+            self.placeholder.lock().unwrap().raze(entity, world);
+        }
+        self.set_child_visibility(world);
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        // New display nodes from the child need to be hidden as well.
+        self.set_child_visibility(world);
+        false
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.placeholder_entity.take() {
+            self.placeholder.lock().unwrap().raze(entity, world);
+        }
+        if let Some(entity) = self.child_entity.take() {
+            self.child.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl IntoView for Suspense {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        run_reactions, Cx, Presenter, PresenterFn, PresenterView, ReactiveContext,
+        ReactiveContextMut, TextStatic,
+    };
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Loader(SuspendHandle);
+
+    fn loading(cx: &mut Cx) -> TextStatic {
+        let handle = cx.suspend().unwrap();
+        cx.world_mut().insert_resource(Loader(handle));
+        TextStatic::new("Loaded".to_string())
+    }
+
+    #[derive(Resource)]
+    struct Ready(bool);
+
+    struct Waiting;
+
+    impl Presenter for Waiting {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            if !cx.use_resource::<Ready>().0 {
+                cx.suspend();
+            }
+            "Ready"
+        }
+    }
+
+    fn text(world: &World, view: &ViewRef) -> String {
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(world) else {
+            panic!("Expected a single node");
+        };
+        world.get::<Text>(node).unwrap().sections[0].value.clone()
+    }
+
+    #[test]
+    fn test_suspense() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = Suspense::new("Loading", loading.bind(())).into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+
+        // The child suspends on first build.
+        assert_eq!(text(&world, &view), "Loading");
+
+        // The child resolves, so the placeholder is replaced.
+        let handle = world.resource::<Loader>().0;
+        world.increment_change_tick();
+        handle.resume(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(text(&world, &view), "Loaded");
+
+        // The child suspends again.
+        world.increment_change_tick();
+        handle.suspend(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(text(&world, &view), "Loading");
+    }
+
+    #[test]
+    fn test_presenter_view_resumes() {
+        let mut world = World::default();
+        world.insert_resource(Ready(false));
+        let parent = world.spawn_empty().id();
+        let view = Suspense::new("Loading", PresenterView::new(Waiting)).into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(text(&world, &view), "Loading");
+
+        // The presenter reacts without suspending, so the child is shown.
+        world.increment_change_tick();
+        world.resource_mut::<Ready>().0 = true;
+        run_reactions(&mut world);
+        // The suspense boundary reacts to the change in the next pass.
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(text(&world, &view), "Ready");
+    }
+}
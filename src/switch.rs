@@ -0,0 +1,193 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{
+    view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx, TrackingScope, View,
+    ViewHandle, ViewRef,
+};
+
+type ArmFn = Box<dyn Fn() -> ViewRef + Send + Sync>;
+
+struct SwitchState {
+    /// Index of the matching arm; equal to the number of cases for the default arm.
+    arm: usize,
+    view: ViewRef,
+    entity: Entity,
+}
+
+/// A conditional view which renders one of several children, depending on which case
+/// matches the value of the test expression.
+pub struct Switch<T: PartialEq + Send + Sync + 'static, Test: Fn(&Rcx) -> T> {
+    test: Test,
+    cases: Vec<(T, ArmFn)>,
+    fallback: Option<ArmFn>,
+    state: Option<SwitchState>,
+}
+
+impl<T: PartialEq + Send + Sync + 'static, Test: Fn(&Rcx) -> T> Switch<T, Test> {
+    /// Construct a new switch View. Use [`Switch::case`] and [`Switch::default`] to add arms.
+    pub fn new(test: Test) -> Self {
+        Self {
+            test,
+            cases: Vec::new(),
+            fallback: None,
+            state: None,
+        }
+    }
+
+    /// Add an arm which is rendered when the test expression equals `value`.
+    pub fn case<V: IntoView, F: Send + Sync + 'static + Fn() -> V>(
+        mut self,
+        value: T,
+        view_fn: F,
+    ) -> Self {
+        self.cases
+            .push((value, Box::new(move || (view_fn)().into_view())));
+        self
+    }
+
+    /// Set the arm which is rendered when none of the cases match. If there is no default arm,
+    /// nothing is rendered.
+    pub fn default<V: IntoView, F: Send + Sync + 'static + Fn() -> V>(
+        mut self,
+        view_fn: F,
+    ) -> Self {
+        self.fallback = Some(Box::new(move || (view_fn)().into_view()));
+        self
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static, Test: Fn(&Rcx) -> T> View for Switch<T, Test> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.state {
+            Some(ref state) => child_nodes(&state.view, state.entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+        assert!(
+            world.entity_mut(view_entity).get::<Parent>().is_some(),
+            "Switch should have a parent view"
+        );
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let value = (self.test)(&re);
+        let arm = self
+            .cases
+            .iter()
+            .position(|(case, _)| *case == value)
+            .or_else(|| self.fallback.as_ref().map(|_| self.cases.len()));
+        if self.state.as_ref().map(|state| state.arm) == arm {
+            // Same arm as before, do nothing.
+            return;
+        }
+
+        if let Some(prev) = self.state.take() {
+            prev.view.lock().unwrap().raze(prev.entity, world);
+        }
+
+        if let Some(arm) = arm {
+            let view = match self.cases.get(arm) {
+                Some((_, view_fn)) => (view_fn)(),
+                None => (self.fallback.as_ref().unwrap())(),
+            };
+            let entity = ViewHandle::spawn(&view, view_entity, world);
+            self.state = Some(SwitchState { arm, view, entity });
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(state) = self.state.take() {
+            state.view.lock().unwrap().raze(state.entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+/// Creates a switch view.
+pub fn switch<T: PartialEq + Send + Sync + 'static, Test: Send + Sync + Fn(&Rcx) -> T>(
+    test: Test,
+) -> Switch<T, Test> {
+    Switch::new(test)
+}
+
+impl<T: PartialEq + Send + Sync + 'static, Test: Send + Sync + 'static + Fn(&Rcx) -> T> IntoView
+    for Switch<T, Test>
+{
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReactiveContext;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Mode(u32);
+
+    fn displayed_text(view: &ViewRef, world: &World) -> Option<String> {
+        match view.lock().unwrap().nodes(world) {
+            NodeSpan::Node(node) => {
+                Some(world.get::<Text>(node).unwrap().sections[0].value.clone())
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_switch() {
+        let mut world = World::default();
+        world.insert_resource(Mode(0));
+        let parent = world.spawn_empty().id();
+        let view = Switch::new(|cx| cx.use_resource::<Mode>().0)
+            .case(0, || "zero")
+            .case(1, || "one")
+            .case(2, || "two")
+            .default(|| "other")
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(displayed_text(&view, &world), Some("zero".to_string()));
+
+        // Cycle through all arms, including the default, and back again.
+        for (mode, expected) in [(1, "one"), (2, "two"), (7, "other"), (0, "zero")] {
+            world.resource_mut::<Mode>().0 = mode;
+            let mut tracking = TrackingScope::new(world.change_tick());
+            view.lock()
+                .unwrap()
+                .react(entity, &mut world, &mut tracking);
+            assert_eq!(displayed_text(&view, &world), Some(expected.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_switch_no_default() {
+        let mut world = World::default();
+        world.insert_resource(Mode(5));
+        let parent = world.spawn_empty().id();
+        let view = Switch::new(|cx| cx.use_resource::<Mode>().0)
+            .case(0, || "zero")
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(view.lock().unwrap().nodes(&world), NodeSpan::Empty);
+
+        world.resource_mut::<Mode>().0 = 0;
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock()
+            .unwrap()
+            .react(entity, &mut world, &mut tracking);
+        assert_eq!(displayed_text(&view, &world), Some("zero".to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+//! Helpers for unit-testing views without a Bevy `App`.
+
+use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+
+use crate::{
+    cx::Cx, plugin::reactor_systems, IntoView, Mutable, ReactiveContextMut, TrackingScope,
+    ViewHandle,
+};
+
+/// A minimal [`World`] for testing views. Views are spawned with
+/// [`spawn_view`](TestWorld::spawn_view), and the reactive systems are run, in the same order
+/// as [`ReactorPlugin`](crate::ReactorPlugin), by calling [`tick`](TestWorld::tick).
+pub struct TestWorld {
+    world: World,
+
+    /// Entity which is the parent of the spawned views.
+    root: Entity,
+
+    /// Tracking scope which owns the mutables created by the test.
+    owner: TrackingScope,
+
+    /// The reactive systems, in the same order as [`ReactorPlugin`](crate::ReactorPlugin).
+    schedule: Schedule,
+}
+
+impl TestWorld {
+    /// Construct a new, empty `TestWorld`.
+    pub fn new() -> Self {
+        let mut world = World::default();
+        let root = world.spawn_empty().id();
+        let owner = TrackingScope::new(world.change_tick());
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.add_systems(reactor_systems());
+        Self {
+            world,
+            root,
+            owner,
+            schedule,
+        }
+    }
+
+    /// The underlying world.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// The underlying world, mutably.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Build a view, returning its view entity.
+    pub fn spawn_view(&mut self, view: impl IntoView) -> Entity {
+        ViewHandle::spawn(&view.into_view(), self.root, &mut self.world)
+    }
+
+    /// Run the reactive systems once.
+    pub fn tick(&mut self) {
+        // The systems are run by a schedule, so that, as in an app, each system sees the
+        // changes made since its own last run, including those made by later systems.
+        self.schedule.run(&mut self.world);
+        self.world.clear_trackers();
+    }
+
+    /// Returns the display nodes of the view with the given view entity.
+    pub fn get_node_entities(&self, entity: Entity) -> Vec<Entity> {
+        self.world
+            .get::<ViewHandle>(entity)
+            .map(|handle| {
+                handle
+                    .view
+                    .lock()
+                    .unwrap()
+                    .nodes(&self.world)
+                    .flat_collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Create a new [`Mutable`], which lives as long as the `TestWorld`.
+    pub fn create_mutable<T: Send + Sync + 'static>(&mut self, init: T) -> Mutable<T> {
+        Cx::new(&(), &mut self.world, Entity::PLACEHOLDER, &mut self.owner).create_mutable(init)
+    }
+
+    /// Write a new value to a [`Mutable`]. The value is committed by the next
+    /// [`tick`](TestWorld::tick).
+    pub fn set_mutable<T: Send + Sync + Clone + PartialEq + 'static>(
+        &mut self,
+        mutable: &Mutable<T>,
+        value: T,
+    ) {
+        Cx::new(&(), &mut self.world, Entity::PLACEHOLDER, &mut self.owner)
+            .write_mutable_clone(mutable.id, value);
+    }
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{text_computed, ReactiveContext};
+
+    use super::*;
+
+    fn text(world: &TestWorld, entity: Entity) -> String {
+        let node = world.get_node_entities(entity)[0];
+        world.world().get::<Text>(node).unwrap().sections[0]
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_text_computed() {
+        let mut world = TestWorld::new();
+        let name = world.create_mutable("Alice".to_string());
+        let id = name.id;
+        let entity = world.spawn_view(text_computed(move |cx| {
+            format!("Hello, {}", cx.read_mutable_clone::<String>(id))
+        }));
+        assert_eq!(world.get_node_entities(entity).len(), 1);
+        assert_eq!(text(&world, entity), "Hello, Alice");
+
+        world.set_mutable(&name, "Bob".to_string());
+        world.tick();
+        assert_eq!(text(&world, entity), "Hello, Bob");
+    }
+}
@@ -23,7 +23,7 @@ impl TextStatic {
 }
 
 impl View for TextStatic {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, _world: &World) -> NodeSpan {
         NodeSpan::Node(self.node.unwrap())
     }
 
@@ -41,7 +41,7 @@ impl View for TextStatic {
 
     fn raze(&mut self, _view_entity: Entity, world: &mut World) {
         // Delete the display node.
-        let display = self.node.expect("Razing unbuilt DynTextNode");
+        let display = self.node.take().expect("Razing unbuilt DynTextNode");
         world.entity_mut(display).remove_parent();
         world.entity_mut(display).despawn();
     }
@@ -75,7 +75,7 @@ impl IntoView for TextStatic {
 }
 
 impl<F: FnMut(&Rcx) -> String> View for TextComputed<F> {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, _world: &World) -> NodeSpan {
         NodeSpan::Node(self.node.unwrap())
     }
 
@@ -108,7 +108,7 @@ impl<F: FnMut(&Rcx) -> String> View for TextComputed<F> {
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
-        let display = self.node.expect("Razing unbuilt DynTextNode");
+        let display = self.node.take().expect("Razing unbuilt DynTextNode");
         world.entity_mut(display).remove_parent();
         world.entity_mut(display).despawn();
         world.despawn_owned_recursive(view_entity);
@@ -125,3 +125,207 @@ impl<F: Send + Sync + 'static + FnMut(&Rcx) -> String> IntoView for TextComputed
         Arc::new(Mutex::new(self))
     }
 }
+
+/// Creates a text view from a format string. The first argument names the reactive context,
+/// which can be used in the format arguments; if there are no format arguments, this produces
+/// a [`TextStatic`], otherwise a [`TextComputed`] which re-formats the text whenever any of the
+/// values read through the context change.
+///
+/// ```ignore
+/// text!(re, "Score: {}", re.read_mutable::<i32>(score))
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($re:ident, $fmt:literal $(,)?) => {
+        $crate::TextStatic::new(format!($fmt))
+    };
+    ($re:ident, $fmt:literal, $($arg:expr),+ $(,)?) => {
+        $crate::TextComputed::new(move |$re: &$crate::Rcx| format!($fmt, $($arg),+))
+    };
+}
+
+/// A UI element that displays multiple sections of text, where both the text and the style
+/// of each section are dynamically computed.
+pub struct TextRich<F: FnMut(&Rcx) -> Vec<TextSection>> {
+    /// The visible UI node for this element.
+    node: Option<Entity>,
+
+    /// The sections to display
+    sections: F,
+}
+
+impl<F: FnMut(&Rcx) -> Vec<TextSection>> TextRich<F> {
+    /// Construct a new rich text view.
+    pub fn new(sections: F) -> Self {
+        Self {
+            node: None,
+            sections,
+        }
+    }
+}
+
+impl<F: FnMut(&Rcx) -> Vec<TextSection>> View for TextRich<F> {
+    fn nodes(&self, _world: &World) -> NodeSpan {
+        NodeSpan::Node(self.node.unwrap())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.node.is_none());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let re = Rcx::new(world, &mut tracking);
+        let sections = (self.sections)(&re);
+        let node = Some(
+            world
+                .spawn((TextBundle {
+                    text: Text::from_sections(sections),
+                    ..default()
+                },))
+                .id(),
+        );
+        self.node = node;
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let sections = (self.sections)(&re);
+        let mut entt = world.entity_mut(self.node.unwrap());
+        let mut text = entt.get_mut::<Text>().unwrap();
+        // Update existing sections in place, then add or remove sections to match the count.
+        text.sections.truncate(sections.len());
+        for (index, section) in sections.into_iter().enumerate() {
+            match text.sections.get_mut(index) {
+                Some(existing) => {
+                    existing.value = section.value;
+                    existing.style = section.style;
+                }
+                None => text.sections.push(section),
+            }
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let display = self.node.expect("Razing unbuilt TextRich");
+        world.entity_mut(display).remove_parent();
+        world.entity_mut(display).despawn();
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+/// Creates a rich text view with multiple computed sections.
+pub fn text_rich<F: FnMut(&Rcx) -> Vec<TextSection>>(sections: F) -> TextRich<F> {
+    TextRich::new(sections)
+}
+
+impl<F: Send + Sync + 'static + FnMut(&Rcx) -> Vec<TextSection>> IntoView for TextRich<F> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, ReactiveContext};
+
+    use super::*;
+
+    fn text_of(world: &TestWorld, entity: Entity) -> String {
+        let node = world.get_node_entities(entity)[0];
+        world.world().get::<Text>(node).unwrap().sections[0]
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_text_macro_static() {
+        let view: TextStatic = text!(re, "Hello");
+        let mut world = TestWorld::new();
+        let entity = world.spawn_view(view);
+        assert_eq!(text_of(&world, entity), "Hello");
+    }
+
+    #[test]
+    fn test_text_macro_computed() {
+        let mut world = TestWorld::new();
+        let a = world.create_mutable(1);
+        let b = world.create_mutable(2);
+        let (a_id, b_id) = (a.id, b.id);
+        let entity = world.spawn_view(text!(
+            re,
+            "{} + {} = {}",
+            re.read_mutable::<i32>(a_id),
+            re.read_mutable::<i32>(b_id),
+            re.read_mutable::<i32>(a_id) + re.read_mutable::<i32>(b_id)
+        ));
+        assert_eq!(text_of(&world, entity), "1 + 2 = 3");
+
+        world.set_mutable(&a, 3);
+        world.tick();
+        assert_eq!(text_of(&world, entity), "3 + 2 = 5");
+
+        world.set_mutable(&b, 4);
+        world.tick();
+        assert_eq!(text_of(&world, entity), "3 + 4 = 7");
+    }
+
+    fn sections_of(world: &TestWorld, node: Entity) -> Vec<(String, Color)> {
+        world
+            .world()
+            .get::<Text>(node)
+            .unwrap()
+            .sections
+            .iter()
+            .map(|section| (section.value.clone(), section.style.color))
+            .collect()
+    }
+
+    #[test]
+    fn test_text_rich() {
+        let mut world = TestWorld::new();
+        let name = world.create_mutable(Some("World".to_string()));
+        let color = world.create_mutable(Color::WHITE);
+        let (name_id, color_id) = (name.id, color.id);
+        let entity = world.spawn_view(text_rich(move |re| {
+            let style = TextStyle {
+                color: re.read_mutable::<Color>(color_id),
+                ..default()
+            };
+            let mut sections = vec![TextSection::new("Hello", TextStyle::default())];
+            if let Some(name) = re.read_mutable_clone::<Option<String>>(name_id) {
+                sections.push(TextSection::new(name, style));
+            }
+            sections
+        }));
+        world.tick();
+        let node = world.get_node_entities(entity)[0];
+        assert_eq!(
+            sections_of(&world, node),
+            [
+                ("Hello".to_string(), Color::WHITE),
+                ("World".to_string(), Color::WHITE)
+            ]
+        );
+
+        // A style-only change updates the section in place.
+        world.set_mutable(&color, Color::RED);
+        world.tick();
+        assert_eq!(
+            sections_of(&world, node)[1],
+            ("World".to_string(), Color::RED)
+        );
+
+        // Removing and adding sections.
+        world.set_mutable(&name, None);
+        world.tick();
+        assert_eq!(sections_of(&world, node).len(), 1);
+        world.set_mutable(&name, Some("Bevy".to_string()));
+        world.tick();
+        assert_eq!(
+            sections_of(&world, node)[1],
+            ("Bevy".to_string(), Color::RED)
+        );
+
+        // The display node is never replaced.
+        assert_eq!(world.get_node_entities(entity), [node]);
+    }
+}
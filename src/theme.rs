@@ -0,0 +1,167 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, Context, Cx, DespawnScopes, IntoView, TrackingScope,
+    View, ViewHandle, ViewRef,
+};
+
+/// A set of design tokens which can be provided to a view tree with [`ThemeProvider`], and
+/// read by presenters with [`Cx::use_theme_of`]. Usually implemented with
+/// `#[derive(Theme)]`, which also adds accessors for each of the tokens.
+///
+/// The derive needs named fields, since the accessors are named after them:
+///
+/// ```compile_fail
+/// #[derive(Clone, PartialEq, Default, bevy_reactor::Theme)]
+/// struct Tokens(f32);
+/// ```
+pub trait ThemeTokens: Clone + PartialEq + Default + Send + Sync + 'static {
+    /// The context which holds the current theme.
+    const THEME_CTX: Context<Self>;
+}
+
+/// The default set of design tokens.
+#[derive(Clone, PartialEq, Debug, crate::Theme)]
+pub struct Theme {
+    /// Primary accent color.
+    pub primary: Color,
+    /// Secondary accent color.
+    pub secondary: Color,
+    /// Background color of panels and windows.
+    pub background: Color,
+    /// Color of text.
+    pub text: Color,
+    /// Base spacing between elements, in logical pixels.
+    pub spacing: f32,
+    /// Base font size, in logical pixels.
+    pub font_size: f32,
+    /// Font used for text.
+    pub font: Handle<Font>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::rgb(0.2, 0.4, 0.8),
+            secondary: Color::rgb(0.5, 0.5, 0.5),
+            background: Color::rgb(0.1, 0.1, 0.1),
+            text: Color::WHITE,
+            spacing: 8.,
+            font_size: 16.,
+            font: Handle::default(),
+        }
+    }
+}
+
+/// A view which provides a theme to its child, and all descendants of the child. Presenters
+/// read the theme from the nearest enclosing provider.
+pub struct ThemeProvider<T: ThemeTokens = Theme> {
+    theme: T,
+    child: ViewRef,
+    child_entity: Option<Entity>,
+}
+
+impl<T: ThemeTokens> ThemeProvider<T> {
+    /// Construct a new `ThemeProvider`.
+    pub fn new(theme: T, child: impl IntoView) -> Self {
+        Self {
+            theme,
+            child: child.into_view(),
+            child_entity: None,
+        }
+    }
+
+    /// Replace the theme provided by the `ThemeProvider` with the given view entity. Presenters
+    /// which read the theme will react.
+    pub fn set_theme(view_entity: Entity, world: &mut World, theme: T) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        Cx::new(&(), world, view_entity, &mut tracking).provide_context(T::THEME_CTX, theme);
+    }
+}
+
+impl<T: ThemeTokens> View for ThemeProvider<T> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.child_entity {
+            Some(entity) => child_nodes(&self.child, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        // The theme must be provided before the child is built, so that it can be consumed.
+        Self::set_theme(view_entity, world, self.theme.clone());
+        self.child_entity = Some(ViewHandle::spawn(&self.child, view_entity, world));
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.child_entity.take() {
+            self.child.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<T: ThemeTokens> IntoView for ThemeProvider<T> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{propagate_display_node_changed, run_reactions, Presenter};
+
+    use super::*;
+
+    #[derive(crate::Presenter)]
+    struct Swatch;
+
+    impl Presenter for Swatch {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            format!("{}", Theme::spacing(cx))
+        }
+    }
+
+    fn text(world: &World, view: &ViewRef) -> String {
+        let NodeSpan::Node(node) = view.lock().unwrap().nodes(world) else {
+            panic!("Expected a single node");
+        };
+        world.get::<Text>(node).unwrap().sections[0].value.clone()
+    }
+
+    fn spacing(spacing: f32) -> Theme {
+        Theme {
+            spacing,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_nearest_theme() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let inner = ThemeProvider::new(spacing(2.), Swatch);
+        let outer = ThemeProvider::new(spacing(1.), inner).into_view();
+        let outer_entity = ViewHandle::spawn(&outer, parent, &mut world);
+        assert_eq!(text(&world, &outer), "2");
+
+        // Swapping the inner theme re-renders the presenter.
+        world.clear_trackers();
+        let inner_entity = world.get::<Children>(outer_entity).unwrap()[0];
+        ThemeProvider::set_theme(inner_entity, &mut world, spacing(3.));
+        run_reactions(&mut world);
+        propagate_display_node_changed(&mut world);
+        assert_eq!(text(&world, &outer), "3");
+    }
+
+    #[test]
+    fn test_default_theme() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = Swatch.into_view();
+        ViewHandle::spawn(&view, parent, &mut world);
+        assert_eq!(text(&world, &view), "8");
+    }
+}
@@ -0,0 +1,328 @@
+use std::{any::Any, marker::PhantomData, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::ReactiveContext;
+
+/// The phase of a [`Transition`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionPhase {
+    /// No transition is in progress.
+    Idle,
+
+    /// The new state has been committed, and is animating in.
+    Entering,
+
+    /// The current state is animating out, and will be replaced by the new state.
+    Leaving,
+}
+
+/// Type-erased interface to a [`TransitionInner`], used to advance transitions over time.
+pub(crate) trait AnyTransition: Send + Sync {
+    /// Advance the transition. Returns true if the state or phase changed.
+    fn update(&mut self, now: Duration) -> bool;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub(crate) struct TransitionInner<S> {
+    state: S,
+    next: Option<S>,
+    phase: TransitionPhase,
+    started: Duration,
+    /// Duration of each phase of the current transition.
+    duration: Duration,
+    /// Duration used by [`Transition::go_to`].
+    default_duration: Duration,
+}
+
+impl<S: Clone + PartialEq + Send + Sync + 'static> TransitionInner<S> {
+    fn go_to(&mut self, next: S, duration: Duration, now: Duration) {
+        if duration.is_zero() {
+            self.state = next;
+            self.next = None;
+            self.phase = TransitionPhase::Idle;
+            return;
+        }
+        if self.phase == TransitionPhase::Idle && self.state == next {
+            return;
+        }
+        // Interrupting a transition which is entering leaves the new state, while
+        // interrupting one which is leaving just retargets it.
+        self.next = Some(next);
+        self.phase = TransitionPhase::Leaving;
+        self.started = now;
+        self.duration = duration;
+    }
+}
+
+impl<S: Clone + PartialEq + Send + Sync + 'static> AnyTransition for TransitionInner<S> {
+    fn update(&mut self, now: Duration) -> bool {
+        if self.phase == TransitionPhase::Idle || now.saturating_sub(self.started) < self.duration {
+            return false;
+        }
+        match self.phase {
+            TransitionPhase::Leaving => {
+                self.state = self.next.take().unwrap();
+                self.phase = TransitionPhase::Entering;
+                self.started = now;
+            }
+            TransitionPhase::Entering => self.phase = TransitionPhase::Idle,
+            TransitionPhase::Idle => unreachable!(),
+        }
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Component which holds the state of a [`Transition`].
+#[derive(Component)]
+pub(crate) struct TransitionValue(pub(crate) Box<dyn AnyTransition>);
+
+impl TransitionValue {
+    pub(crate) fn new<S: Clone + PartialEq + Send + Sync + 'static>(initial: S) -> Self {
+        Self(Box::new(TransitionInner {
+            state: initial,
+            next: None,
+            phase: TransitionPhase::Idle,
+            started: Duration::ZERO,
+            duration: Duration::ZERO,
+            default_duration: Duration::ZERO,
+        }))
+    }
+
+    fn inner<S: 'static>(&self) -> &TransitionInner<S> {
+        self.0.as_any().downcast_ref().unwrap()
+    }
+
+    fn inner_mut<S: 'static>(&mut self) -> &mut TransitionInner<S> {
+        self.0.as_any_mut().downcast_mut().unwrap()
+    }
+}
+
+/// A handle to a state which moves between values over time, created via
+/// [`Cx::use_transition`](crate::Cx::use_transition).
+///
+/// When the state changes, the transition first spends the transition duration in the
+/// [`Leaving`](TransitionPhase::Leaving) phase, then commits the new state and spends the
+/// same duration in the [`Entering`](TransitionPhase::Entering) phase. Reading the phase from
+/// a reactive style allows animating between states.
+pub struct Transition<S> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<S>,
+}
+
+impl<S: Clone + PartialEq + Send + Sync + 'static> Transition<S> {
+    /// Return the current logical state. The state only changes once the previous state has
+    /// finished leaving. Calling this function adds the transition to the tracking scope.
+    pub fn state<'p, R: ReactiveContext<'p>>(&self, rc: &R) -> S {
+        rc.use_component::<TransitionValue>(self.id)
+            .unwrap()
+            .inner::<S>()
+            .state
+            .clone()
+    }
+
+    /// Return the current phase of the transition. Calling this function adds the
+    /// transition to the tracking scope.
+    pub fn phase<'p, R: ReactiveContext<'p>>(&self, rc: &R) -> TransitionPhase {
+        rc.use_component::<TransitionValue>(self.id)
+            .unwrap()
+            .inner::<S>()
+            .phase
+    }
+
+    /// Start a transition to a new state, using the transition's default duration.
+    pub fn go_to(&self, world: &mut World, next: S) {
+        let duration = world
+            .get::<TransitionValue>(self.id)
+            .unwrap()
+            .inner::<S>()
+            .default_duration;
+        self.go_to_with_duration(world, next, duration);
+    }
+
+    /// Start a transition to a new state, taking `duration` for each phase. A zero duration
+    /// changes the state immediately.
+    pub fn go_to_with_duration(&self, world: &mut World, next: S, duration: Duration) {
+        let now = world
+            .get_resource::<Time>()
+            .map(|time| time.elapsed())
+            .unwrap_or_default();
+        let mut transition = world.get_mut::<TransitionValue>(self.id).unwrap();
+        transition.inner_mut::<S>().go_to(next, duration, now);
+    }
+
+    /// Set the duration used by [`go_to`](Transition::go_to).
+    pub fn set_duration(&self, world: &mut World, duration: Duration) {
+        let mut transition = world.get_mut::<TransitionValue>(self.id).unwrap();
+        transition
+            .bypass_change_detection()
+            .inner_mut::<S>()
+            .default_duration = duration;
+    }
+}
+
+impl<S> Clone for Transition<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Transition<S> {}
+
+/// System which advances transitions whose phase duration has elapsed.
+pub fn update_transitions(world: &mut World) {
+    let now = world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default();
+    for mut transition in world.query::<&mut TransitionValue>().iter_mut(world) {
+        if transition.bypass_change_detection().0.update(now) {
+            transition.set_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cx::Cx, testing::TestWorld, IntoView, Presenter, ReactiveContextMut, TrackingScope,
+    };
+
+    use super::*;
+
+    fn read(transition: &Transition<u32>, world: &mut World) -> (u32, TransitionPhase) {
+        let mut scope = TrackingScope::new(world.change_tick());
+        let cx = Cx::new(&(), world, Entity::PLACEHOLDER, &mut scope);
+        (transition.state(&cx), transition.phase(&cx))
+    }
+
+    fn advance(world: &mut World, millis: u64) {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        update_transitions(world);
+    }
+
+    #[test]
+    fn test_immediate_transition() {
+        let mut world = World::default();
+        let presenter = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let transition = Cx::new(&(), &mut world, presenter, &mut scope).use_transition(0);
+        transition.go_to(&mut world, 1);
+        assert_eq!(read(&transition, &mut world), (1, TransitionPhase::Idle));
+    }
+
+    #[test]
+    fn test_interrupted_transition() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let presenter = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.change_tick());
+        let transition = Cx::new(&(), &mut world, presenter, &mut scope).use_transition(0);
+        transition.set_duration(&mut world, Duration::from_millis(100));
+
+        transition.go_to(&mut world, 1);
+        assert_eq!(read(&transition, &mut world), (0, TransitionPhase::Leaving));
+        advance(&mut world, 50);
+        assert_eq!(read(&transition, &mut world), (0, TransitionPhase::Leaving));
+        advance(&mut world, 60);
+        assert_eq!(
+            read(&transition, &mut world),
+            (1, TransitionPhase::Entering)
+        );
+
+        // Interrupt while entering: the new state starts leaving.
+        transition.go_to(&mut world, 2);
+        assert_eq!(read(&transition, &mut world), (1, TransitionPhase::Leaving));
+
+        // Interrupt while leaving: the target changes and the timer restarts.
+        advance(&mut world, 50);
+        transition.go_to_with_duration(&mut world, 3, Duration::from_millis(200));
+        advance(&mut world, 150);
+        assert_eq!(read(&transition, &mut world), (1, TransitionPhase::Leaving));
+        advance(&mut world, 60);
+        assert_eq!(
+            read(&transition, &mut world),
+            (3, TransitionPhase::Entering)
+        );
+        advance(&mut world, 210);
+        assert_eq!(read(&transition, &mut world), (3, TransitionPhase::Idle));
+    }
+
+    /// The transition from the first run of the presenter, and the number of runs.
+    #[derive(Resource, Default)]
+    struct Fades {
+        transition: Option<Transition<u32>>,
+        runs: u32,
+    }
+
+    #[derive(crate::Presenter)]
+    struct Fader;
+
+    impl Presenter for Fader {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let transition = cx.use_transition(0u32);
+            let mut fades = cx.world_mut().resource_mut::<Fades>();
+            fades.runs += 1;
+            fades.transition.get_or_insert(transition);
+            format!("{} {:?}", transition.state(cx), transition.phase(cx))
+        }
+    }
+
+    #[test]
+    fn test_presenter_transition() {
+        let mut world = TestWorld::new();
+        world.world_mut().init_resource::<Time>();
+        world.world_mut().init_resource::<Fades>();
+        let view = world.spawn_view(Fader);
+        world.tick();
+        let text = |world: &TestWorld| {
+            let node = world.get_node_entities(view)[0];
+            world.world().get::<Text>(node).unwrap().sections[0]
+                .value
+                .clone()
+        };
+        let count = |world: &mut TestWorld| {
+            world
+                .world_mut()
+                .query::<&TransitionValue>()
+                .iter(world.world())
+                .count()
+        };
+        assert_eq!(text(&world), "0 Idle");
+
+        let transition = world.world().resource::<Fades>().transition.unwrap();
+        transition.set_duration(world.world_mut(), Duration::from_millis(100));
+        transition.go_to(world.world_mut(), 1);
+        world.tick();
+        assert_eq!(text(&world), "0 Leaving");
+
+        // Each frame advances the transition which was created by the first run.
+        let frame = |world: &mut TestWorld| {
+            world
+                .world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(60));
+            world.tick();
+            text(world)
+        };
+        assert_eq!(frame(&mut world), "0 Leaving");
+        assert_eq!(frame(&mut world), "1 Entering");
+        assert_eq!(frame(&mut world), "1 Entering");
+        assert_eq!(frame(&mut world), "1 Idle");
+        assert_eq!(world.world().resource::<Fades>().runs, 4);
+        assert_eq!(count(&mut world), 1);
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView, Rcx,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// Component which is present on the display nodes of the child of a [`TransitionView`] while
+/// it is animating in or out. Presenters can read it with `use_component` to apply animated
+/// styles. It is removed once the enter animation has finished.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionState {
+    /// The child has been shown, and is animating in.
+    Entering,
+
+    /// The child has been hidden, and is animating out. It will be razed when the exit
+    /// animation has finished.
+    Exiting,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TimerPhase {
+    Entering,
+    Visible,
+    Exiting,
+    Exited,
+}
+
+/// Component on the view entity of a [`TransitionView`] which tracks the current animation.
+#[derive(Component)]
+pub(crate) struct TransitionTimer {
+    phase: TimerPhase,
+    /// Elapsed time at which the current animation will be finished.
+    end: Duration,
+}
+
+fn elapsed(world: &World) -> Duration {
+    world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default()
+}
+
+/// A view which shows or hides its child depending on a reactive condition, with enter and
+/// exit animations. Unlike [`ShowHide`](crate::ShowHide), the child is built when it is shown
+/// and razed once it has finished exiting; unlike [`AnimatedView`](crate::AnimatedView), the
+/// animations are driven by the condition rather than by the view being built and razed.
+///
+/// While animating, the child's display nodes have a [`TransitionState`] component. Showing
+/// the child again while it is exiting reverses the transition, without rebuilding the child.
+///
+/// Animations are advanced by the [`update_transition_views`] system.
+pub struct TransitionView<F: Fn(&Rcx) -> bool> {
+    visible_fn: F,
+    child: ViewRef,
+    child_entity: Option<Entity>,
+    enter_duration: Duration,
+    exit_duration: Duration,
+    exit_grace_period: Duration,
+}
+
+impl<F: Fn(&Rcx) -> bool> TransitionView<F> {
+    /// Construct a new `TransitionView`.
+    pub fn new(
+        visible_fn: F,
+        child: impl IntoView,
+        enter_duration: Duration,
+        exit_duration: Duration,
+    ) -> Self {
+        Self {
+            visible_fn,
+            child: child.into_view(),
+            child_entity: None,
+            enter_duration,
+            exit_duration,
+            exit_grace_period: Duration::ZERO,
+        }
+    }
+
+    /// Keep the child alive for an additional period after the exit animation, so that a
+    /// child which is hidden and then immediately shown again is not razed and rebuilt.
+    pub fn with_exit_grace_period(mut self, grace_period: Duration) -> Self {
+        self.exit_grace_period = grace_period;
+        self
+    }
+
+    /// Begin an animation phase lasting for `duration`.
+    fn start(&self, view_entity: Entity, world: &mut World, phase: TimerPhase, duration: Duration) {
+        let end = elapsed(world) + duration;
+        // An enter animation with no duration finishes immediately.
+        let phase = match phase {
+            TimerPhase::Entering if duration.is_zero() => TimerPhase::Visible,
+            phase => phase,
+        };
+        // Bypass change detection, since this reaction depends on the timer itself.
+        match world.get_mut::<TransitionTimer>(view_entity) {
+            Some(mut timer) => {
+                let timer = timer.bypass_change_detection();
+                timer.phase = phase;
+                timer.end = end;
+            }
+            None => {
+                world
+                    .entity_mut(view_entity)
+                    .insert(TransitionTimer { phase, end });
+            }
+        }
+    }
+
+    /// Update the [`TransitionState`] of the child's display nodes to match the timer.
+    fn apply_state(&self, view_entity: Entity, world: &mut World) {
+        let state = match world.get::<TransitionTimer>(view_entity).map(|t| t.phase) {
+            Some(TimerPhase::Entering) => Some(TransitionState::Entering),
+            Some(TimerPhase::Exiting) => Some(TransitionState::Exiting),
+            _ => None,
+        };
+        for node in self.nodes(world).iter() {
+            let mut entt = world.entity_mut(node);
+            match state {
+                Some(state) if entt.get::<TransitionState>() != Some(&state) => {
+                    entt.insert(state);
+                }
+                None if entt.contains::<TransitionState>() => {
+                    entt.remove::<TransitionState>();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<F: Fn(&Rcx) -> bool> View for TransitionView<F> {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        match self.child_entity {
+            Some(entity) => child_nodes(&self.child, entity, world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let timer_id = world.init_component::<TransitionTimer>();
+        tracking.add_component(view_entity, timer_id);
+        let visible = (self.visible_fn)(&Rcx::new(world, tracking));
+        let phase = world.get::<TransitionTimer>(view_entity).map(|t| t.phase);
+        match (visible, self.child_entity) {
+            (true, None) => {
+                self.child_entity = Some(ViewHandle::spawn(&self.child, view_entity, world));
+                world.entity_mut(view_entity).insert(DisplayNodeChanged);
+                self.start(
+                    view_entity,
+                    world,
+                    TimerPhase::Entering,
+                    self.enter_duration,
+                );
+            }
+            (true, Some(_)) => {
+                // Reverse a transition which is exiting.
+                if let Some(TimerPhase::Exiting | TimerPhase::Exited) = phase {
+                    self.start(
+                        view_entity,
+                        world,
+                        TimerPhase::Entering,
+                        self.enter_duration,
+                    );
+                }
+            }
+            (false, Some(child_entity)) => match phase {
+                Some(TimerPhase::Exiting) => {}
+                Some(TimerPhase::Exited) => {
+                    self.child.lock().unwrap().raze(child_entity, world);
+                    self.child_entity = None;
+                    world
+                        .entity_mut(view_entity)
+                        .insert(DisplayNodeChanged)
+                        .remove::<TransitionTimer>();
+                    return;
+                }
+                _ => {
+                    let duration = self.exit_duration + self.exit_grace_period;
+                    self.start(view_entity, world, TimerPhase::Exiting, duration);
+                }
+            },
+            (false, None) => {}
+        }
+        self.apply_state(view_entity, world);
+    }
+
+    fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
+        // New display nodes from the child need the transition state as well.
+        self.apply_state(view_entity, world);
+        false
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(entity) = self.child_entity.take() {
+            self.child.lock().unwrap().raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<F: Send + Sync + 'static + Fn(&Rcx) -> bool> IntoView for TransitionView<F> {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+/// System which advances the animations of [`TransitionView`]s. A view whose animation has
+/// finished reacts to the change, removing the [`TransitionState`] or razing its child.
+pub fn update_transition_views(world: &mut World) {
+    let now = elapsed(world);
+    for mut timer in world.query::<&mut TransitionTimer>().iter_mut(world) {
+        if now < timer.end {
+            continue;
+        }
+        match timer.phase {
+            TimerPhase::Entering => timer.phase = TimerPhase::Visible,
+            TimerPhase::Exiting => timer.phase = TimerPhase::Exited,
+            TimerPhase::Visible | TimerPhase::Exited => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestWorld, Element, ReactiveContext};
+
+    use super::*;
+
+    fn advance(world: &mut TestWorld, millis: u64) {
+        world
+            .world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        world.tick();
+    }
+
+    fn state(world: &TestWorld, node: Entity) -> Option<TransitionState> {
+        world.world().get::<TransitionState>(node).copied()
+    }
+
+    #[test]
+    fn test_transition_view() {
+        let mut world = TestWorld::new();
+        world.world_mut().init_resource::<Time>();
+        let visible = world.create_mutable(true);
+        let id = visible.id;
+        let entity = world.spawn_view(TransitionView::new(
+            move |re| re.read_mutable::<bool>(id),
+            Element::<NodeBundle>::new(),
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+        ));
+        let nodes = world.get_node_entities(entity);
+        assert_eq!(nodes.len(), 1);
+        let node = nodes[0];
+        assert_eq!(state(&world, node), Some(TransitionState::Entering));
+
+        // The state is removed once the enter animation has finished.
+        advance(&mut world, 110);
+        assert_eq!(state(&world, node), None);
+
+        // Hiding keeps the child alive for the exit duration.
+        world.set_mutable(&visible, false);
+        world.tick();
+        assert_eq!(state(&world, node), Some(TransitionState::Exiting));
+        advance(&mut world, 150);
+        assert!(world.world().get_entity(node).is_some());
+
+        // Showing again during the exit reverses the transition.
+        world.set_mutable(&visible, true);
+        world.tick();
+        assert_eq!(state(&world, node), Some(TransitionState::Entering));
+        advance(&mut world, 300);
+        assert!(world.world().get_entity(node).is_some());
+        assert_eq!(state(&world, node), None);
+
+        // Once the exit animation has finished, the child is razed.
+        world.set_mutable(&visible, false);
+        world.tick();
+        advance(&mut world, 210);
+        assert!(world.world().get_entity(node).is_none());
+        assert!(world.get_node_entities(entity).is_empty());
+    }
+}
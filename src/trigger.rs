@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use crate::mutable::{after_write, MutableValue, MutableValueNext};
+
+/// A handle which can be used to manually invalidate the tracking scopes that poll it. Created
+/// via [`ReactiveContextMut::create_trigger`](crate::ReactiveContextMut::create_trigger), and
+/// read via [`ReactiveContext::poll_trigger`](crate::ReactiveContext::poll_trigger).
+///
+/// A trigger is a [`Mutable`](crate::Mutable) which holds a generation counter. Unlike events,
+/// firing a trigger doesn't deliver any data, it only causes the readers to react. Like other
+/// mutable writes, firing takes effect on the next update, and firing a trigger several times
+/// before the next update causes a single reaction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Trigger {
+    pub(crate) id: Entity,
+}
+
+impl Trigger {
+    /// Fire the trigger, causing all tracking scopes which polled it to react.
+    pub fn fire(&self, world: &mut World) {
+        fire_trigger(world, self.id);
+    }
+}
+
+/// Increment the generation counter of a trigger.
+pub(crate) fn fire_trigger(world: &mut World, trigger: Entity) {
+    let Some(mut entt) = world.get_entity_mut(trigger) else {
+        return;
+    };
+    if let Some(mut next) = entt.get_mut::<MutableValueNext>() {
+        *next.0.downcast_mut::<u64>().unwrap() += 1;
+    } else if let Some(current) = entt.get::<MutableValue>() {
+        let generation = *current.value.downcast_ref::<u64>().unwrap();
+        entt.insert(MutableValueNext(Box::new(generation + 1)));
+    }
+    after_write(world, trigger);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{
+        cx::Cx, testing::TestWorld, DespawnScopes, Reaction, ReactionHandle, ReactiveContext,
+        ReactiveContextMut, TrackingScope,
+    };
+
+    use super::*;
+
+    struct PollReaction {
+        trigger: Trigger,
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl Reaction for PollReaction {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let cx = Cx::new(&(), world, Entity::PLACEHOLDER, tracking);
+            cx.poll_trigger(&self.trigger);
+            self.runs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_trigger() {
+        let mut world = TestWorld::new();
+        let owner_id = world.world_mut().spawn_empty().id();
+        let mut owner = TrackingScope::new(world.world().read_change_tick());
+        let mut trigger = Cx::new(&(), world.world_mut(), owner_id, &mut owner).create_trigger();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let reaction = PollReaction {
+            trigger,
+            runs: runs.clone(),
+        };
+        ReactionHandle::spawn(reaction, world.world_mut());
+
+        // Polling doesn't dirty the scope, so nothing happens until the trigger is fired.
+        world.tick();
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        // One reaction per fire.
+        Cx::new(&(), world.world_mut(), owner_id, &mut owner).fire(&mut trigger);
+        world.tick();
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+
+        // Several fires before the next update cause a single reaction.
+        trigger.fire(world.world_mut());
+        trigger.fire(world.world_mut());
+        world.tick();
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+        world.tick();
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+        // The trigger is despawned along with its owner.
+        world.world_mut().entity_mut(owner_id).insert(owner);
+        world.world_mut().despawn_owned_recursive(owner_id);
+        assert!(world.world().get_entity(trigger.id).is_none());
+    }
+}
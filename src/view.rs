@@ -1,17 +1,35 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    any::TypeId,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
 
 use bevy::{
+    core::Name,
     ecs::{
         component::Component,
         entity::Entity,
         query::{Added, With},
+        system::Resource,
         world::World,
     },
-    hierarchy::{BuildWorldChildren, Parent},
-    log::warn,
+    hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt, Parent},
+    log::{error, warn},
+    utils::HashSet,
 };
 
-use crate::{node_span::NodeSpan, scope::TrackingScope, text::TextStatic};
+#[cfg(feature = "debug")]
+use bevy::ecs::query::Changed;
+
+use crate::{
+    error_boundary::panic_message,
+    node_span::NodeSpan,
+    scope::{hierarchy_depth, TrackingScope},
+    text::TextStatic,
+    DespawnScopes, ReactorPaused,
+};
 
 /// Trait that defines a view, which is a template that constructs a hierarchy of
 /// entities and components.
@@ -20,8 +38,11 @@ use crate::{node_span::NodeSpan, scope::TrackingScope, text::TextStatic};
 /// and which drives the reaction system. When the view is no longer needed, call [`View::raze`].
 /// This will destroy the view entity, and all of its children and display nodes.
 pub trait View {
-    /// Returns the display nodes produced by this `View`.
-    fn nodes(&self) -> NodeSpan;
+    /// Returns the display nodes produced by this `View`. Views which contain child views
+    /// should use the [`NodeSpan`] cached on each child's view entity, rather than locking
+    /// each child view and calling its `nodes()` recursively. The cached span is cloned, so
+    /// the cost is still proportional to the number of nodes.
+    fn nodes(&self, world: &World) -> NodeSpan;
 
     /// Initialize the view, creating any entities needed.
     ///
@@ -43,6 +64,14 @@ pub trait View {
     fn children_changed(&mut self, _view_entity: Entity, _world: &mut World) -> bool {
         false
     }
+
+    /// A name for this view which is used for diagnostics. Defaults to the name of the type
+    /// which implements the view, without the module path.
+    fn debug_name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        let base = name.split('<').next().unwrap_or(name);
+        base.rsplit("::").next().unwrap_or(base).to_string()
+    }
 }
 
 /// A reference to a view.
@@ -52,24 +81,92 @@ pub type ViewRef = Arc<Mutex<dyn View + Sync + Send + 'static>>;
 pub trait IntoView {
     /// Convert the type into a `ViewRef`.
     fn into_view(self) -> ViewRef;
+
+    /// Append the views for this type to a list of child views. Types such as `None` which
+    /// produce no view append nothing.
+    #[doc(hidden)]
+    fn collect_views(self, out: &mut Vec<ViewRef>)
+    where
+        Self: Sized,
+    {
+        out.push(self.into_view());
+    }
+
+    /// Returns a key which identifies the view that this value produces, for values whose
+    /// view depends only on their content, such as strings. Two values with the same key
+    /// produce identical views, which lets a [`Presenter`](crate::Presenter) keep its output
+    /// rather than rebuilding it. Returns `None` if the view can't be compared.
+    #[doc(hidden)]
+    fn output_key(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Hash a value along with its type, for use as an [`IntoView::output_key`].
+fn hash_output<T: Hash + ?Sized + 'static>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    TypeId::of::<T>().hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl IntoView for ViewRef {
+    fn into_view(self) -> ViewRef {
+        self
+    }
 }
 
 impl IntoView for () {
     fn into_view(self) -> ViewRef {
         Arc::new(Mutex::new(EmptyView))
     }
+
+    fn output_key(&self) -> Option<u64> {
+        Some(hash_output(self))
+    }
 }
 
 impl IntoView for &str {
     fn into_view(self) -> ViewRef {
         Arc::new(Mutex::new(TextStatic::new(self.to_string())))
     }
+
+    fn output_key(&self) -> Option<u64> {
+        // Strings and string slices produce the same view.
+        Some(hash_output::<str>(self))
+    }
 }
 
 impl IntoView for String {
     fn into_view(self) -> ViewRef {
         Arc::new(Mutex::new(TextStatic::new(self)))
     }
+
+    fn output_key(&self) -> Option<u64> {
+        Some(hash_output::<str>(self))
+    }
+}
+
+impl<V: IntoView> IntoView for Option<V> {
+    fn into_view(self) -> ViewRef {
+        match self {
+            Some(view) => view.into_view(),
+            None => Arc::new(Mutex::new(EmptyView)),
+        }
+    }
+
+    fn collect_views(self, out: &mut Vec<ViewRef>) {
+        if let Some(view) = self {
+            view.collect_views(out);
+        }
+    }
+
+    fn output_key(&self) -> Option<u64> {
+        match self {
+            Some(view) => view.output_key(),
+            None => ().output_key(),
+        }
+    }
 }
 
 #[derive(Component)]
@@ -116,14 +213,45 @@ impl ViewHandle {
     /// and the view handle, and then calls [`View::build`] on the view. The resuling entity
     /// is part of the template invocation hierarchy, it is not a display node.
     pub fn spawn(view: &ViewRef, parent: Entity, world: &mut World) -> Entity {
-        let mut child_ent = world.spawn(ViewHandle::from_ref(view.clone()));
+        let name = view.lock().unwrap().debug_name();
+        Self::spawn_with_name(view, parent, world, &name)
+    }
+
+    /// Same as [`ViewHandle::spawn`], except that the view entity is given the specified
+    /// debug name, rather than the default name of the view.
+    pub fn spawn_with_name(
+        view: &ViewRef,
+        parent: Entity,
+        world: &mut World,
+        name: &str,
+    ) -> Entity {
+        let mut child_ent = world.spawn((
+            ViewHandle::from_ref(view.clone()),
+            Name::new(name.to_string()),
+        ));
         child_ent.set_parent(parent);
         let id = child_ent.id();
-        view.lock().unwrap().build(child_ent.id(), world);
+        let mut inner = view.lock().unwrap();
+        inner.build(id, world);
+        let nodes = inner.nodes(world);
+        world.entity_mut(id).insert(nodes);
         id
     }
 }
 
+/// Returns the display nodes of a child view. This clones the [`NodeSpan`] cached on the
+/// child's view entity, unless the child's nodes have changed and the cache has not yet been
+/// updated by [`attach_child_views`].
+pub(crate) fn child_nodes(view: &ViewRef, view_entity: Entity, world: &World) -> NodeSpan {
+    match world.get_entity(view_entity) {
+        Some(entt) if !entt.contains::<DisplayNodeChanged>() => match entt.get::<NodeSpan>() {
+            Some(nodes) => nodes.clone(),
+            None => view.lock().unwrap().nodes(world),
+        },
+        _ => view.lock().unwrap().nodes(world),
+    }
+}
+
 #[derive(Component)]
 /// Marker component used to signal that a view's output nodes have changed.
 pub struct DisplayNodeChanged;
@@ -132,7 +260,7 @@ pub struct DisplayNodeChanged;
 pub struct EmptyView;
 
 impl View for EmptyView {
-    fn nodes(&self) -> NodeSpan {
+    fn nodes(&self, _world: &World) -> NodeSpan {
         NodeSpan::Empty
     }
 
@@ -141,37 +269,195 @@ impl View for EmptyView {
 }
 
 /// System that initializes any views that have been added.
-pub(crate) fn build_added_view_roots(world: &mut World) {
+pub fn build_added_view_roots(world: &mut World) {
     // Need to copy query result to avoid double-borrow of world.
     let mut roots = world.query_filtered::<(Entity, &mut ViewRoot), Added<ViewRoot>>();
-    let roots_copy: Vec<Entity> = roots.iter(world).map(|(e, _)| e).collect();
+    let mut roots_copy: Vec<Entity> = roots.iter(world).map(|(e, _)| e).collect();
+
+    // While paused, remember the new roots so that they can be built on resume.
+    if world.contains_resource::<ReactorPaused>() {
+        if !roots_copy.is_empty() {
+            world
+                .get_resource_or_insert_with(PendingViewRoots::default)
+                .0
+                .append(&mut roots_copy);
+        }
+        return;
+    }
+    if let Some(pending) = world.remove_resource::<PendingViewRoots>() {
+        roots_copy.splice(0..0, pending.0);
+    }
+    build_view_roots(world, roots_copy);
+}
+
+/// System which builds the view roots spawned by `Startup` systems, so that they are displayed
+/// on the first frame, even if the reactive systems run in a later schedule. Added to
+/// `PostStartup` by [`ReactorPlugin`](crate::ReactorPlugin), so that resources inserted by
+/// `Startup` systems are available when the views are built.
+pub fn build_startup_views(world: &mut World) {
+    if world.contains_resource::<ReactorPaused>() {
+        return;
+    }
+    let mut roots = world.query_filtered::<Entity, With<ViewRoot>>();
+    let roots_copy: Vec<Entity> = roots.iter(world).collect();
+    build_view_roots(world, roots_copy);
+    attach_child_views(world);
+}
+
+/// Build the views of the given roots. Roots which have already been built, for example by
+/// [`build_startup_views`], are skipped.
+fn build_view_roots(world: &mut World, roots_copy: Vec<Entity>) {
+    let mut roots = world.query::<&ViewRoot>();
     for root_entity in roots_copy.iter() {
-        let Ok((_, root)) = roots.get(world, *root_entity) else {
+        let Ok(root) = roots.get(world, *root_entity) else {
             continue;
         };
+        let entt = world.entity(*root_entity);
+        if entt.contains::<NodeSpan>() || entt.contains::<ViewBuildFailed>() {
+            continue;
+        }
         let inner = root.view.clone();
-        inner.lock().unwrap().build(*root_entity, world);
+        let mut view = inner.lock().unwrap();
+        let mut entt = world.entity_mut(*root_entity);
+        if !entt.contains::<Name>() {
+            entt.insert(Name::new(view.debug_name()));
+        }
+        // A view which panics while building is skipped, so that one broken view doesn't take
+        // down the rest of the app. The lock is acquired outside of the unwind boundary so that
+        // the mutex isn't poisoned.
+        match catch_unwind(AssertUnwindSafe(|| view.build(*root_entity, world))) {
+            Ok(()) => {
+                let nodes = view.nodes(world);
+                world.entity_mut(*root_entity).insert(nodes);
+            }
+            Err(payload) => {
+                let message = panic_message(payload.as_ref());
+                error!("Failed to build view {:?}: {}", root_entity, message);
+                despawn_partial_view(world, *root_entity);
+                world
+                    .entity_mut(*root_entity)
+                    .insert(ViewBuildFailed(message));
+            }
+        }
+    }
+}
+
+/// Despawn the entities spawned by a view root which panicked while being built: its child
+/// views, and the entities owned by its tracking scope. The root entity itself is kept, to
+/// hold the [`ViewBuildFailed`] component.
+fn despawn_partial_view(world: &mut World, root: Entity) {
+    let children = world
+        .get::<Children>(root)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+    for child in children {
+        let Some(handle) = world.get::<ViewHandle>(child) else {
+            continue;
+        };
+        // The view which panicked, and its ancestors, are poisoned, and are despawned below
+        // without being razed.
+        let inner = handle.view.clone();
+        if let Ok(mut view) = inner.lock() {
+            view.raze(child, world);
+        };
+    }
+    world.entity_mut(root).despawn_descendants();
+    if let Some(scope) = world.entity_mut(root).take::<TrackingScope>() {
+        // Move the scope to a temporary entity, so that its owned entities and cleanups are
+        // released without despawning the root.
+        let holder = world.spawn(scope).id();
+        world.despawn_owned_recursive(holder);
+    }
+}
+
+/// Component which is inserted on a view root whose view panicked while being built. The
+/// value is the panic message. Reactions are never run for entities with this component.
+#[derive(Component, Debug, Clone)]
+pub struct ViewBuildFailed(pub String);
+
+/// Resource which holds the view roots which were added while the reactor was paused.
+#[derive(Resource, Default)]
+pub(crate) struct PendingViewRoots(Vec<Entity>);
+
+/// System which marks each ancestor view entity of a view whose display nodes have changed
+/// with [`DisplayNodeChanged`], since the nodes of the ancestors include those of the view.
+/// Each view on the way up has its cached [`NodeSpan`] refreshed and is notified with
+/// [`View::children_changed`]. Propagation stops at the first ancestor which handles the
+/// change, such as an element which re-attaches its children, since the nodes of that
+/// ancestor are unaffected.
+pub fn propagate_display_node_changed(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<DisplayNodeChanged>>();
+    let mut changed = query
+        .iter(world)
+        .map(|entity| (hierarchy_depth(world, entity), entity))
+        .collect::<Vec<_>>();
+
+    // Visit the deepest views first, so that each view is only notified once, even if both it
+    // and its descendants are marked.
+    changed.sort_by_key(|(depth, _)| std::cmp::Reverse(*depth));
+    let mut visited: HashSet<Entity> = HashSet::new();
+    for (_, entity) in changed {
+        world.entity_mut(entity).insert(DisplayNodeUpdated);
+        let mut e = entity;
+        while visited.insert(e) {
+            let view = match world.get::<ViewHandle>(e) {
+                Some(handle) => Some(handle.view.clone()),
+                None => world.get::<ViewRoot>(e).map(|root| root.view.clone()),
+            };
+            if let Some(view) = view {
+                if update_nodes(&view, e, world) {
+                    break;
+                }
+                world
+                    .entity_mut(e)
+                    .insert((DisplayNodeChanged, DisplayNodeUpdated));
+            }
+            e = match world.get::<Parent>(e) {
+                Some(parent) => parent.get(),
+                None => break,
+            };
+        }
     }
 }
 
+/// Marker component for view entities which have already been updated by
+/// [`propagate_display_node_changed`], and so are skipped by [`attach_child_views`].
+#[derive(Component)]
+pub(crate) struct DisplayNodeUpdated;
+
 /// System that looks for changed child views and replaces the parent's child nodes.
-pub(crate) fn attach_child_views(world: &mut World) {
+pub fn attach_child_views(world: &mut World) {
     let mut query = world.query_filtered::<Entity, With<DisplayNodeChanged>>();
-    let query_copy = query.iter(world).collect::<Vec<Entity>>();
-    for entity in query_copy {
-        world.entity_mut(entity).remove::<DisplayNodeChanged>();
+    let mut query_copy = query
+        .iter(world)
+        .map(|entity| (hierarchy_depth(world, entity), entity))
+        .collect::<Vec<_>>();
+
+    // Visit the deepest views first, so that each view is only updated once, even if both it
+    // and its descendants are marked.
+    query_copy.sort_by_key(|(depth, _)| std::cmp::Reverse(*depth));
+    let mut visited: HashSet<Entity> = HashSet::new();
+    for (_, entity) in query_copy {
+        let mut entt = world.entity_mut(entity);
+        entt.remove::<DisplayNodeChanged>();
+        if entt.take::<DisplayNodeUpdated>().is_some() {
+            continue;
+        }
         let mut e = entity;
         loop {
+            if !visited.insert(e) {
+                break;
+            }
             if let Some(handle) = world.entity(e).get::<ViewHandle>() {
                 let inner = handle.view.clone();
-                if inner.lock().unwrap().children_changed(e, world) {
+                if update_nodes(&inner, e, world) {
                     break;
                 }
             }
 
             if let Some(handle) = world.entity(e).get::<ViewRoot>() {
                 let inner = handle.view.clone();
-                if inner.lock().unwrap().children_changed(e, world) {
+                if update_nodes(&inner, e, world) {
                     break;
                 }
             }
@@ -186,3 +472,448 @@ pub(crate) fn attach_child_views(world: &mut World) {
         }
     }
 }
+
+/// Refresh the cached [`NodeSpan`] of a view whose display nodes, or whose descendants'
+/// display nodes, have changed, and then notify the view. Returns true if the change was
+/// handled.
+fn update_nodes(view: &ViewRef, view_entity: Entity, world: &mut World) -> bool {
+    let mut view = view.lock().unwrap();
+    let nodes = view.nodes(world);
+    world.entity_mut(view_entity).insert(nodes);
+    view.children_changed(view_entity, world)
+}
+
+/// System which prints the hierarchy of view entities to the log, for debugging. This is
+/// registered by [`ReactorPlugin::with_debug_view_tree`](crate::ReactorPlugin), which logs the
+/// tree whenever views are spawned. To log it on demand instead, add it to a schedule with a
+/// run condition, for example
+/// `app.add_systems(Update, debug_view_tree.run_if(input_just_pressed(KeyCode::F12)))`.
+#[cfg(feature = "debug")]
+pub fn debug_view_tree(
+    roots: bevy::ecs::system::Query<Entity, With<ViewRoot>>,
+    children: bevy::ecs::system::Query<&bevy::hierarchy::Children>,
+    names: bevy::ecs::system::Query<&Name>,
+) {
+    fn print_entity(
+        entity: Entity,
+        depth: usize,
+        children: &bevy::ecs::system::Query<&bevy::hierarchy::Children>,
+        names: &bevy::ecs::system::Query<&Name>,
+    ) {
+        let name = names.get(entity).map(|name| name.as_str()).unwrap_or("?");
+        bevy::log::info!("{:indent$}{} {:?}", "", name, entity, indent = depth * 2);
+        if let Ok(child_list) = children.get(entity) {
+            for child in child_list.iter() {
+                print_entity(*child, depth + 1, children, names);
+            }
+        }
+    }
+
+    for root in roots.iter() {
+        print_entity(root, 0, &children, &names);
+    }
+}
+
+/// Run condition for [`debug_view_tree`], which is true when view entities have been spawned
+/// or renamed.
+#[cfg(feature = "debug")]
+pub(crate) fn view_tree_changed(
+    views: bevy::ecs::system::Query<(), (With<ViewHandle>, Changed<Name>)>,
+) -> bool {
+    !views.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bevy::{
+        ecs::{change_detection::DetectChanges, component::Tick, system::Resource},
+        ui::node_bundles::NodeBundle,
+    };
+
+    use crate::{
+        commit_mutables, cx::Cx, run_reactions, Element, ForIndex, Fragment, Presenter, Reaction,
+        ReactionHandle, ReactionTriggered, ReactiveContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_default_name() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let entity = ViewHandle::spawn(&"Hello".into_view(), parent, &mut world);
+        assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "TextStatic");
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_view_tree_changed() {
+        use bevy::ecs::system::{IntoSystem, System};
+
+        let mut world = World::default();
+        let mut changed = IntoSystem::into_system(view_tree_changed);
+        changed.initialize(&mut world);
+        let parent = world.spawn_empty().id();
+        let entity = ViewHandle::spawn(&"Hello".into_view(), parent, &mut world);
+        assert!(changed.run((), &mut world));
+
+        // The tree is only printed again once a view is spawned or renamed.
+        assert!(!changed.run((), &mut world));
+        world.entity_mut(entity).insert(Name::new("Greeting"));
+        assert!(changed.run((), &mut world));
+    }
+
+    #[test]
+    fn test_custom_name() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let entity =
+            ViewHandle::spawn_with_name(&"Hello".into_view(), parent, &mut world, "Greeting");
+        assert_eq!(world.get::<Name>(entity).unwrap().as_str(), "Greeting");
+    }
+
+    #[test]
+    fn test_cached_nodes() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = ("Hello", "World").into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let nodes = view.lock().unwrap().nodes(&world);
+        assert_eq!(nodes.count(), 2);
+        assert_eq!(world.get::<NodeSpan>(entity), Some(&nodes));
+    }
+
+    #[derive(Resource)]
+    struct Items(Vec<u32>);
+
+    static PRESENTS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(crate::Presenter)]
+    struct Wrapper;
+
+    impl Presenter for Wrapper {
+        fn present(_cx: &mut Cx<Self>) -> impl IntoView {
+            PRESENTS.fetch_add(1, Ordering::Relaxed);
+            Fragment::new((ForIndex::new(
+                |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+                |item, _| item.to_string(),
+            ),))
+        }
+    }
+
+    fn only_child(world: &World, entity: Entity) -> Entity {
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 1);
+        children[0]
+    }
+
+    #[test]
+    fn test_propagate_display_node_changed() {
+        let mut world = World::default();
+        world.insert_resource(Items(vec![1, 2]));
+        let parent = world.spawn_empty().id();
+        let presenter = ViewHandle::spawn(&Wrapper.into_view(), parent, &mut world);
+        let fragment = only_child(&world, presenter);
+        let for_index = only_child(&world, fragment);
+        assert_eq!(PRESENTS.load(Ordering::Relaxed), 1);
+
+        world.increment_change_tick();
+        world.resource_mut::<Items>().0.push(3);
+        run_reactions(&mut world);
+        assert!(world.entity(for_index).contains::<DisplayNodeChanged>());
+        assert!(!world.entity(fragment).contains::<DisplayNodeChanged>());
+
+        // Every ancestor view entity is marked, but not the parent, which is not a view.
+        propagate_display_node_changed(&mut world);
+        assert!(world.entity(fragment).contains::<DisplayNodeChanged>());
+        assert!(world.entity(presenter).contains::<DisplayNodeChanged>());
+        assert!(!world.entity(parent).contains::<DisplayNodeChanged>());
+
+        // The ancestors' caches are updated, and the presenter does not re-run.
+        attach_child_views(&mut world);
+        assert_eq!(world.get::<NodeSpan>(presenter).unwrap().count(), 3);
+        assert!(!world.entity(for_index).contains::<DisplayNodeChanged>());
+        world.increment_change_tick();
+        run_reactions(&mut world);
+        assert_eq!(PRESENTS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_propagation_stops_at_element() {
+        let mut world = World::default();
+        world.insert_resource(Items(vec![1]));
+        let parent = world.spawn_empty().id();
+        let inner = Element::<NodeBundle>::new().children((ForIndex::new(
+            |cx| cx.use_resource::<Items>().0.clone().into_iter(),
+            |item, _| item.to_string(),
+        ),));
+        let view = Element::<NodeBundle>::new().children((inner,)).into_view();
+        let outer = ViewHandle::spawn(&view, parent, &mut world);
+        let outer_node = world.get::<NodeSpan>(outer).unwrap().flat_collect()[0];
+        let inner_node = only_child(&world, outer_node);
+        let attached = world
+            .entity(outer_node)
+            .get_ref::<Children>()
+            .unwrap()
+            .last_changed();
+
+        world.increment_change_tick();
+        world.resource_mut::<Items>().0.push(2);
+        run_reactions(&mut world);
+        propagate_display_node_changed(&mut world);
+        assert!(!world.entity(outer).contains::<DisplayNodeChanged>());
+        attach_child_views(&mut world);
+
+        // The inner element re-attaches its children, and the outer element is untouched.
+        assert_eq!(world.get::<Children>(inner_node).unwrap().len(), 2);
+        let children = world.entity(outer_node).get_ref::<Children>().unwrap();
+        assert_eq!(children.last_changed(), attached);
+    }
+
+    static REACTIONS: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountReactions;
+
+    impl Reaction for CountReactions {
+        fn react(&mut self, _owner: Entity, _world: &mut World, _tracking: &mut TrackingScope) {
+            REACTIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// A view which registers a reaction on its entity, and then panics.
+    struct BrokenView;
+
+    impl View for BrokenView {
+        fn nodes(&self, _world: &World) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn build(&mut self, view_entity: Entity, world: &mut World) {
+            let tick = world.change_tick();
+            world.entity_mut(view_entity).insert((
+                TrackingScope::new(tick),
+                ReactionHandle::new(CountReactions),
+                ReactionTriggered,
+            ));
+            panic!("Broken view");
+        }
+
+        fn raze(&mut self, _view_entity: Entity, _world: &mut World) {}
+    }
+
+    #[test]
+    fn test_build_failure_is_skipped() {
+        let mut world = World::default();
+        let first = world.spawn(ViewRoot::new(TextStatic::new("A".into()))).id();
+        let broken = world.spawn(ViewRoot::new(BrokenView)).id();
+        let last = world.spawn(ViewRoot::new(TextStatic::new("B".into()))).id();
+        build_added_view_roots(&mut world);
+
+        assert!(world.entity(first).contains::<NodeSpan>());
+        assert!(world.entity(last).contains::<NodeSpan>());
+        assert!(!world.entity(broken).contains::<NodeSpan>());
+        assert_eq!(
+            world.get::<ViewBuildFailed>(broken).unwrap().0,
+            "Broken view"
+        );
+
+        run_reactions(&mut world);
+        assert_eq!(REACTIONS.load(Ordering::Relaxed), 0);
+    }
+
+    #[derive(Resource, Clone, Copy)]
+    struct PartialEntities(Entity, Entity, Entity);
+
+    /// A view which builds a child view and an owned entity, and then panics.
+    struct PartialView;
+
+    impl View for PartialView {
+        fn nodes(&self, _world: &World) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn build(&mut self, view_entity: Entity, world: &mut World) {
+            let child = ViewHandle::spawn(&"Partial".into_view(), view_entity, world);
+            let node = world.get::<NodeSpan>(child).unwrap().flat_collect()[0];
+            let owned = world.spawn_empty().id();
+            let mut scope = TrackingScope::new(world.change_tick());
+            scope.add_owned(owned);
+            world.entity_mut(view_entity).insert(scope);
+            world.insert_resource(PartialEntities(child, node, owned));
+            panic!("Partial view");
+        }
+
+        fn raze(&mut self, _view_entity: Entity, _world: &mut World) {}
+    }
+
+    #[test]
+    fn test_build_failure_despawns_partial_view() {
+        let mut world = World::default();
+        let root = world.spawn(ViewRoot::new(PartialView)).id();
+        build_added_view_roots(&mut world);
+
+        let PartialEntities(child, node, owned) = *world.resource::<PartialEntities>();
+        assert!(world.get_entity(child).is_none());
+        assert!(world.get_entity(node).is_none());
+        assert!(world.get_entity(owned).is_none());
+        assert!(world.get::<Children>(root).is_none());
+        assert!(world.entity(root).contains::<ViewBuildFailed>());
+    }
+
+    #[test]
+    fn test_option_in_tuple() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = Fragment::new(("A", None::<&str>, Some("B"))).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        // `None` does not add a child view.
+        assert_eq!(world.get::<Children>(entity).unwrap().len(), 2);
+        assert_eq!(view.lock().unwrap().nodes(&world).count(), 2);
+    }
+
+    #[derive(crate::Presenter)]
+    struct Optional {
+        show: Entity,
+    }
+
+    impl Presenter for Optional {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            cx.read_mutable::<bool>(cx.props.show).then_some("Shown")
+        }
+    }
+
+    #[derive(crate::Presenter)]
+    struct Listed {
+        len: Entity,
+    }
+
+    impl Presenter for Listed {
+        fn present(cx: &mut Cx<Self>) -> impl IntoView {
+            let len = cx.read_mutable::<usize>(cx.props.len);
+            (0..len).map(|i| i.to_string()).collect::<Vec<_>>()
+        }
+    }
+
+    /// Commit mutables and run reactions, returning whether the view's display nodes changed,
+    /// then finish the frame and return the number of children of the containing element.
+    fn step(world: &mut crate::testing::TestWorld, view: Entity, element: Entity) -> (bool, usize) {
+        commit_mutables(world.world_mut());
+        world.world_mut().increment_change_tick();
+        run_reactions(world.world_mut());
+        let changed = world.world().entity(view).contains::<DisplayNodeChanged>();
+        world.tick();
+        (changed, child_count(world, element))
+    }
+
+    fn child_count(world: &crate::testing::TestWorld, entity: Entity) -> usize {
+        world
+            .world()
+            .get::<Children>(entity)
+            .map_or(0, |children| children.len())
+    }
+
+    #[test]
+    fn test_option_view() {
+        let mut world = crate::testing::TestWorld::new();
+        let show = world.create_mutable(false);
+        let root = world.spawn_view(
+            crate::Element::<bevy::prelude::NodeBundle>::new().children(Optional { show: show.id }),
+        );
+        world.tick();
+        let element = world.get_node_entities(root)[0];
+        let view = world.world().get::<Children>(root).unwrap()[0];
+        assert_eq!(child_count(&world, element), 0);
+
+        // None -> Some
+        world.set_mutable(&show, true);
+        assert_eq!(step(&mut world, view, element), (true, 1));
+
+        // Some -> None
+        world.set_mutable(&show, false);
+        assert_eq!(step(&mut world, view, element), (true, 0));
+    }
+
+    #[test]
+    fn test_vec_view() {
+        let mut world = crate::testing::TestWorld::new();
+        let len = world.create_mutable(0usize);
+        let root = world.spawn_view(
+            crate::Element::<bevy::prelude::NodeBundle>::new().children(Listed { len: len.id }),
+        );
+        world.tick();
+        let element = world.get_node_entities(root)[0];
+        let view = world.world().get::<Children>(root).unwrap()[0];
+        assert_eq!(child_count(&world, element), 0);
+
+        // Empty -> populated
+        world.set_mutable(&len, 3);
+        assert_eq!(step(&mut world, view, element), (true, 3));
+
+        // Populated -> empty
+        world.set_mutable(&len, 0);
+        assert_eq!(step(&mut world, view, element), (true, 0));
+    }
+
+    /// Ticks recorded by [`Staleness`]: the tick of its previous update, and the current tick.
+    #[derive(Resource, Default)]
+    struct Updates(Vec<(Tick, Tick)>);
+
+    /// View which records how stale its last update was each time it reacts.
+    struct Staleness {
+        source: Entity,
+    }
+
+    impl View for Staleness {
+        fn nodes(&self, _world: &World) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn build(&mut self, view_entity: Entity, world: &mut World) {
+            let mut tracking = TrackingScope::new(world.change_tick());
+            tracking.add_mutable(self.source);
+            world.entity_mut(view_entity).insert(tracking);
+        }
+
+        fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            // While reacting, the scope on the view entity is still the one from the last update.
+            let previous = world.get::<TrackingScope>(view_entity).unwrap().tick();
+            let now = world.change_tick();
+            world.resource_mut::<Updates>().0.push((previous, now));
+            tracking.add_mutable(self.source);
+        }
+
+        fn raze(&mut self, view_entity: Entity, world: &mut World) {
+            world.despawn_owned_recursive(view_entity);
+        }
+    }
+
+    impl IntoView for Staleness {
+        fn into_view(self) -> ViewRef {
+            Arc::new(Mutex::new(self))
+        }
+    }
+
+    #[test]
+    fn test_scope_tick() {
+        let mut world = crate::testing::TestWorld::new();
+        world.world_mut().init_resource::<Updates>();
+        let source = world.create_mutable(0);
+        world.spawn_view(Staleness { source: source.id });
+        world.set_mutable(&source, 1);
+        world.tick();
+        world.tick();
+        world.set_mutable(&source, 2);
+        world.tick();
+
+        // Each update sees the tick of the one before it, which is older than the current tick.
+        let updates = &world.world().resource::<Updates>().0;
+        assert_eq!(updates.len(), 2);
+        let this_run = world.world().read_change_tick();
+        for (previous, now) in updates {
+            assert!(now.is_newer_than(*previous, this_run));
+        }
+        assert_eq!(updates[1].0, updates[0].1);
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    node_span::NodeSpan, view::child_nodes, DespawnScopes, DisplayNodeChanged, IntoView,
+    TrackingScope, View, ViewHandle, ViewRef,
+};
+
+/// Builder for a [`ViewGroup`].
+#[derive(Default)]
+pub struct ViewGroupBuilder {
+    slots: Vec<(&'static str, ViewRef)>,
+}
+
+impl ViewGroupBuilder {
+    /// Add a named slot. Slots are displayed in the order in which they are added.
+    pub fn slot(mut self, name: &'static str, view: impl IntoView) -> Self {
+        self.slots.retain(|(n, _)| *n != name);
+        self.slots.push((name, view.into_view()));
+        self
+    }
+
+    /// Construct the `ViewGroup`.
+    pub fn build(self) -> ViewGroup {
+        ViewGroup {
+            order: self.slots.iter().map(|(name, _)| *name).collect(),
+            slots: self
+                .slots
+                .into_iter()
+                .map(|(name, view)| (name, (None, view)))
+                .collect(),
+        }
+    }
+}
+
+/// Component on the view entity of a [`ViewGroup`] which holds the slot replacements that
+/// have not been applied yet.
+#[derive(Component, Default)]
+pub(crate) struct PendingSlots(Vec<(&'static str, ViewRef)>);
+
+/// A view which is composed of named slots, such as the header, body and footer of a dialog.
+/// The display nodes of the slots are concatenated in slot order, and enclosing components
+/// can retrieve the nodes of an individual slot with [`slot_nodes`](ViewGroup::slot_nodes).
+///
+/// Once the group has been built, the view in a slot can be swapped with
+/// [`ViewGroup::replace_slot`]. When the group reacts, only the replaced slots are rebuilt.
+#[derive(Default)]
+pub struct ViewGroup {
+    /// Display order of the slots.
+    order: Vec<&'static str>,
+    /// The view for each slot, along with its view entity once built.
+    slots: HashMap<&'static str, (Option<Entity>, ViewRef)>,
+}
+
+impl ViewGroup {
+    /// Returns a builder for a new `ViewGroup`, whose slots are added with
+    /// [`slot`](ViewGroupBuilder::slot).
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> ViewGroupBuilder {
+        ViewGroupBuilder::default()
+    }
+
+    /// Construct a `ViewGroup` with no slots.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Returns the display nodes of the named slot, or `Empty` if there is no such slot.
+    pub fn slot_nodes(&self, name: &str, world: &World) -> NodeSpan {
+        match self.slots.get(name) {
+            Some((Some(entity), view)) => child_nodes(view, *entity, world),
+            Some((None, view)) => view.lock().unwrap().nodes(world),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    /// Replace the view in the named slot of the group whose view entity is `view_entity`.
+    /// The slot is rebuilt when the group next reacts; the other slots are left untouched. A
+    /// slot which doesn't exist yet is added after the existing slots.
+    pub fn replace_slot(
+        view_entity: Entity,
+        world: &mut World,
+        name: &'static str,
+        view: impl IntoView,
+    ) {
+        let view = view.into_view();
+        if let Some(mut pending) = world.get_mut::<PendingSlots>(view_entity) {
+            pending.0.push((name, view));
+        }
+    }
+
+    /// Rebuild the named slot with the given view.
+    fn set_slot(
+        &mut self,
+        name: &'static str,
+        view: ViewRef,
+        view_entity: Entity,
+        world: &mut World,
+    ) {
+        if let Some((Some(entity), prev)) = self.slots.remove(name) {
+            prev.lock().unwrap().raze(entity, world);
+        }
+        if !self.order.contains(&name) {
+            self.order.push(name);
+        }
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.slots.insert(name, (Some(entity), view));
+    }
+}
+
+impl View for ViewGroup {
+    fn nodes(&self, world: &World) -> NodeSpan {
+        NodeSpan::merge(self.order.iter().map(|name| self.slot_nodes(name, world)))
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        for name in self.order.iter() {
+            let (entity, view) = self.slots.get_mut(name).unwrap();
+            *entity = Some(ViewHandle::spawn(view, view_entity, world));
+        }
+        world
+            .entity_mut(view_entity)
+            .insert(PendingSlots::default());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let pending_id = world.init_component::<PendingSlots>();
+        tracking.add_component(view_entity, pending_id);
+        let Some(mut pending) = world.get_mut::<PendingSlots>(view_entity) else {
+            return;
+        };
+        let replacements = std::mem::take(&mut pending.bypass_change_detection().0);
+        if replacements.is_empty() {
+            return;
+        }
+        for (name, view) in replacements {
+            self.set_slot(name, view, view_entity, world);
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        for (entity, view) in self.slots.values_mut() {
+            if let Some(entity) = entity.take() {
+                view.lock().unwrap().raze(entity, world);
+            }
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl IntoView for ViewGroup {
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+impl IntoView for ViewGroupBuilder {
+    fn into_view(self) -> ViewRef {
+        self.build().into_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run_reactions;
+
+    use super::*;
+
+    fn text(world: &World, span: NodeSpan) -> String {
+        let NodeSpan::Node(node) = span else {
+            panic!("Expected a single node");
+        };
+        world.get::<Text>(node).unwrap().sections[0].value.clone()
+    }
+
+    #[test]
+    fn test_view_group() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let mut dialog = ViewGroup::new()
+            .slot("header", "Title")
+            .slot("body", "Hello")
+            .build();
+        let entity = world.spawn_empty().set_parent(parent).id();
+        dialog.build(entity, &mut world);
+        assert_eq!(dialog.nodes(&world).count(), 2);
+        assert_eq!(text(&world, dialog.slot_nodes("header", &world)), "Title");
+        assert_eq!(text(&world, dialog.slot_nodes("body", &world)), "Hello");
+        assert_eq!(dialog.slot_nodes("footer", &world), NodeSpan::Empty);
+
+        // Swapping the body does not rebuild the header.
+        let header = dialog.slot_nodes("header", &world);
+        let body = dialog.slot_nodes("body", &world);
+        ViewGroup::replace_slot(entity, &mut world, "body", "Goodbye");
+        let mut tracking = TrackingScope::new(world.change_tick());
+        dialog.react(entity, &mut world, &mut tracking);
+        assert_eq!(dialog.slot_nodes("header", &world), header);
+        assert_ne!(dialog.slot_nodes("body", &world), body);
+        assert_eq!(text(&world, dialog.slot_nodes("body", &world)), "Goodbye");
+        assert!(world.entity(entity).contains::<DisplayNodeChanged>());
+
+        // Slots are displayed in order.
+        let nodes = dialog.nodes(&world).flat_collect();
+        assert_eq!(nodes[0], header.flat_collect()[0]);
+    }
+
+    #[test]
+    fn test_replace_slot_via_view_ref() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = ViewGroup::new()
+            .slot("header", "Title")
+            .slot("body", "Hello")
+            .into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        let before = view.lock().unwrap().nodes(&world).flat_collect();
+
+        // The replacement is applied when the group reacts.
+        world.increment_change_tick();
+        ViewGroup::replace_slot(entity, &mut world, "body", "Goodbye");
+        run_reactions(&mut world);
+        let after = view.lock().unwrap().nodes(&world).flat_collect();
+        assert_eq!(after.len(), 2);
+        assert_eq!(after[0], before[0]);
+        assert_ne!(after[1], before[1]);
+        assert_eq!(
+            world.get::<Text>(after[1]).unwrap().sections[0].value,
+            "Goodbye"
+        );
+
+        // A new slot is added at the end.
+        world.increment_change_tick();
+        ViewGroup::replace_slot(entity, &mut world, "footer", "OK");
+        run_reactions(&mut world);
+        assert_eq!(view.lock().unwrap().nodes(&world).count(), 3);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{IntoView, ViewRef};
+use crate::{Fragment, IntoView, ViewRef};
 use impl_trait_for_tuples::*;
 
 // ViewTuple
@@ -10,22 +10,104 @@ pub trait ViewTuple {
 
 impl<I: IntoView> ViewTuple for I {
     fn get_handles(self, out: &mut Vec<ViewRef>) {
-        out.push(self.into_view());
+        self.collect_views(out);
     }
 }
 
-impl<I: IntoView> ViewTuple for Option<I> {
-    fn get_handles(self, out: &mut Vec<ViewRef>) {
-        if let Some(view) = self {
-            out.push(view.into_view());
-        }
+// A tuple of views renders as a fragment containing each of the tuple members. When the tuple
+// is itself a member of a view tuple, its members are added directly rather than nested.
+#[impl_for_tuples(1, 12)]
+#[tuple_types_custom_trait_bound(IntoView)]
+impl IntoView for Tuple {
+    fn into_view(self) -> ViewRef {
+        let mut views: Vec<ViewRef> = Vec::new();
+        self.collect_views(&mut views);
+        Fragment::from_refs(views).into_view()
+    }
+
+    fn collect_views(self, out: &mut Vec<ViewRef>) {
+        for_tuples!(#( self.Tuple.collect_views(out); )*);
     }
 }
 
-#[impl_for_tuples(1, 15)]
-#[tuple_types_custom_trait_bound(IntoView)]
-impl ViewTuple for Tuple {
-    fn get_handles(self, out: &mut Vec<ViewRef>) {
-        for_tuples!(#( out.push(self.Tuple.into_view()); )*)
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        testing::TestWorld, Element, ForIndex, ReactiveContext, TextComputed, TextStatic,
+        ViewHandle,
+    };
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Items(Vec<u32>);
+
+    fn texts(world: &TestWorld, node: Entity) -> Vec<String> {
+        world
+            .world()
+            .get::<Children>(node)
+            .map(|children| {
+                children
+                    .iter()
+                    .map(|child| {
+                        world.world().get::<Text>(*child).unwrap().sections[0]
+                            .value
+                            .clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_nested_tuple() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let view = ("A", ("B", "C")).into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+        // The inner tuple is flattened into the outer fragment.
+        assert_eq!(world.get::<Children>(entity).unwrap().len(), 3);
+        assert_eq!(view.lock().unwrap().nodes(&world).count(), 3);
+    }
+
+    #[test]
+    fn test_tuple_view() {
+        let mut world = TestWorld::new();
+        world.world_mut().insert_resource(Items(vec![1]));
+        let count = world.create_mutable(0);
+        let id = count.id;
+        let root = world.spawn_view(Element::<NodeBundle>::new().children((
+            TextStatic::new("Start".to_string()),
+            ForIndex::new(
+                |re| re.use_resource::<Items>().0.clone().into_iter(),
+                |item, _| item.to_string(),
+            ),
+            TextComputed::new(move |re| format!("{}", re.read_mutable::<i32>(id))),
+        )));
+        world.tick();
+        let element = world.get_node_entities(root)[0];
+        assert_eq!(world.world().get::<Children>(root).unwrap().len(), 3);
+        assert_eq!(texts(&world, element), ["Start", "1", "0"]);
+
+        // Changing the number of nodes of one member updates the parent's children.
+        world.world_mut().resource_mut::<Items>().0.push(2);
+        world.tick();
+        assert_eq!(texts(&world, element), ["Start", "1", "2", "0"]);
+
+        // Members react to their own dependencies.
+        world.set_mutable(&count, 5);
+        world.tick();
+        assert_eq!(texts(&world, element), ["Start", "1", "2", "5"]);
+
+        // Razing removes all of the nodes.
+        let view = world.world().get::<ViewHandle>(root).unwrap().view.clone();
+        let nodes: Vec<Entity> = world.world().get::<Children>(element).unwrap().to_vec();
+        view.lock().unwrap().raze(root, world.world_mut());
+        assert!(world.world().get_entity(element).is_none());
+        for node in nodes {
+            assert!(world.world().get_entity(node).is_none());
+        }
     }
 }
@@ -0,0 +1,379 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    mutable::{after_write, MutableValue, MutableValueNext},
+    node_span::NodeSpan,
+    view::child_nodes,
+    DespawnScopes, DisplayNodeChanged, IntoView, Rcx, ReactiveContext, TrackingScope, View,
+    ViewHandle, ViewRef,
+};
+
+/// Component on the view entity of a [`VirtualList`] which holds the scroll offset mutable.
+#[derive(Component)]
+pub(crate) struct VirtualListScroll(pub(crate) Entity);
+
+struct VirtualItem<Item> {
+    value: Item,
+    view: ViewRef,
+    entity: Entity,
+}
+
+/// A list view which only builds views for the items that are within the visible area.
+///
+/// All items are assumed to have the same height. The list renders a spacer node before and
+/// after the visible items, so that the total height of the list is the same as if all of the
+/// items were rendered. The scroll offset is held in a [`Mutable`](crate::Mutable) which is
+/// created by the list; use [`VirtualList::set_scroll`] to change it.
+///
+/// When an item scrolls out of view, its view is razed, and its view entity is kept in a pool
+/// so that it can be reused by an item which scrolls into view.
+pub struct VirtualList<
+    Item: PartialEq + Clone + 'static,
+    ItemFn: Fn(&Rcx) -> Vec<Item>,
+    AreaFn: Fn(&Rcx) -> f32,
+    V: IntoView,
+    F: Fn(&Item, usize) -> V,
+> {
+    items_fn: ItemFn,
+    item_height: f32,
+    visible_area_fn: AreaFn,
+    each_fn: F,
+    visible_range: Range<usize>,
+    items: Vec<VirtualItem<Item>>,
+    scroll: Option<Entity>,
+    spacers: Option<(Entity, Entity)>,
+    pool: Vec<Entity>,
+}
+
+impl<
+        Item: PartialEq + Clone + 'static,
+        ItemFn: Fn(&Rcx) -> Vec<Item>,
+        AreaFn: Fn(&Rcx) -> f32,
+        V: IntoView,
+        F: Fn(&Item, usize) -> V,
+    > VirtualList<Item, ItemFn, AreaFn, V, F>
+{
+    /// Construct a new `VirtualList`.
+    ///
+    /// Arguments:
+    /// * `items_fn`: Reactive function which returns the list items.
+    /// * `item_height`: The height of each item, in logical pixels. Must be greater than zero.
+    /// * `visible_area_fn`: Reactive function which returns the height of the visible area.
+    /// * `each_fn`: Function which renders an item, given the item and its index.
+    pub fn new(items_fn: ItemFn, item_height: f32, visible_area_fn: AreaFn, each_fn: F) -> Self {
+        assert!(
+            item_height > 0.,
+            "VirtualList item height must be greater than zero, got {}",
+            item_height
+        );
+        Self {
+            items_fn,
+            item_height,
+            visible_area_fn,
+            each_fn,
+            visible_range: 0..0,
+            items: Vec::new(),
+            scroll: None,
+            spacers: None,
+            pool: Vec::new(),
+        }
+    }
+
+    /// Set the scroll offset of the virtual list whose view entity is `view_entity`. Like
+    /// other mutable writes, the new offset takes effect on the next update.
+    pub fn set_scroll(view_entity: Entity, world: &mut World, offset: f32) {
+        let Some(scroll) = world.get::<VirtualListScroll>(view_entity).map(|s| s.0) else {
+            return;
+        };
+        world
+            .entity_mut(scroll)
+            .insert(MutableValueNext(Box::new(offset)));
+        after_write(world, scroll);
+    }
+
+    /// Returns the range of item indices which are currently rendered.
+    pub fn visible_range(&self) -> Range<usize> {
+        self.visible_range.clone()
+    }
+
+    /// Build the view for an item, reusing a pooled view entity if there is one.
+    fn spawn_item(&mut self, view: &ViewRef, view_entity: Entity, world: &mut World) -> Entity {
+        let Some(entity) = self.pool.pop() else {
+            return ViewHandle::spawn(view, view_entity, world);
+        };
+        let mut inner = view.lock().unwrap();
+        world.entity_mut(entity).insert((
+            ViewHandle::from_ref(view.clone()),
+            Name::new(inner.debug_name()),
+        ));
+        inner.build(entity, world);
+        let nodes = inner.nodes(world);
+        world.entity_mut(entity).insert(nodes);
+        entity
+    }
+
+    /// Raze the view of an item, and return its view entity to the pool. Views which
+    /// despawn their own view entity when razed can't be pooled.
+    fn raze_item(&mut self, item: VirtualItem<Item>, world: &mut World) {
+        item.view.lock().unwrap().raze(item.entity, world);
+        if let Some(mut entt) = world.get_entity_mut(item.entity) {
+            entt.remove::<(ViewHandle, NodeSpan, DisplayNodeChanged)>();
+            self.pool.push(item.entity);
+        }
+    }
+
+    fn set_spacer_height(world: &mut World, spacer: Entity, height: f32) {
+        if let Some(mut style) = world.get_mut::<Style>(spacer) {
+            if style.height != Val::Px(height) {
+                style.height = Val::Px(height);
+            }
+        }
+    }
+}
+
+impl<
+        Item: PartialEq + Clone + 'static,
+        ItemFn: Fn(&Rcx) -> Vec<Item>,
+        AreaFn: Fn(&Rcx) -> f32,
+        V: IntoView,
+        F: Fn(&Item, usize) -> V,
+    > View for VirtualList<Item, ItemFn, AreaFn, V, F>
+{
+    fn nodes(&self, world: &World) -> NodeSpan {
+        let Some((top, bottom)) = self.spacers else {
+            return NodeSpan::Empty;
+        };
+        let mut spans = Vec::with_capacity(self.items.len() + 2);
+        spans.push(NodeSpan::Node(top));
+        spans.extend(
+            self.items
+                .iter()
+                .map(|item| child_nodes(&item.view, item.entity, world)),
+        );
+        spans.push(NodeSpan::Node(bottom));
+        NodeSpan::Fragment(spans.into_boxed_slice())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.scroll.is_none());
+        let mut tracking = TrackingScope::new(world.change_tick());
+        let scroll = world
+            .spawn(MutableValue {
+                value: Box::new(0f32),
+            })
+            .id();
+        tracking.add_owned(scroll);
+        self.scroll = Some(scroll);
+        world
+            .entity_mut(view_entity)
+            .insert(VirtualListScroll(scroll));
+        self.spacers = Some((
+            world.spawn(NodeBundle::default()).id(),
+            world.spawn(NodeBundle::default()).id(),
+        ));
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let items = (self.items_fn)(&re);
+        let offset = re.read_mutable::<f32>(self.scroll.unwrap()).max(0.);
+        let area = (self.visible_area_fn)(&re).max(0.);
+
+        // Compute the window of visible items.
+        let start = ((offset / self.item_height) as usize).min(items.len());
+        let count = (area / self.item_height).ceil() as usize + 1;
+        let range = start..(start + count).min(items.len());
+
+        let prev_range = std::mem::replace(&mut self.visible_range, range.clone());
+        let mut prev: Vec<Option<VirtualItem<Item>>> = self.items.drain(..).map(Some).collect();
+        let mut changed = false;
+
+        // Reuse the views for items which are still visible and unchanged.
+        let reused: Vec<Option<VirtualItem<Item>>> = range
+            .clone()
+            .map(|index| {
+                if prev_range.contains(&index) {
+                    prev[index - prev_range.start].take_if_eq(&items[index])
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Raze items which scrolled out of view, so that their entities can be reused.
+        for item in prev.into_iter().flatten() {
+            self.raze_item(item, world);
+            changed = true;
+        }
+
+        // Build views for items which scrolled into view.
+        for (index, existing) in range.clone().zip(reused) {
+            let item = match existing {
+                Some(item) => item,
+                None => {
+                    changed = true;
+                    let value = &items[index];
+                    let view = (self.each_fn)(value, index).into_view();
+                    let entity = self.spawn_item(&view, view_entity, world);
+                    VirtualItem {
+                        value: value.clone(),
+                        view,
+                        entity,
+                    }
+                }
+            };
+            self.items.push(item);
+        }
+
+        // Size the spacers to account for the items which are not rendered.
+        let (top, bottom) = self.spacers.unwrap();
+        Self::set_spacer_height(world, top, range.start as f32 * self.item_height);
+        Self::set_spacer_height(
+            world,
+            bottom,
+            (items.len() - range.end) as f32 * self.item_height,
+        );
+
+        if changed {
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let items = std::mem::take(&mut self.items);
+        for item in items {
+            self.raze_item(item, world);
+        }
+        for entity in self.pool.drain(..) {
+            if let Some(entt) = world.get_entity_mut(entity) {
+                entt.despawn();
+            }
+        }
+        if let Some((top, bottom)) = self.spacers.take() {
+            world.entity_mut(top).remove_parent();
+            world.entity_mut(top).despawn();
+            world.entity_mut(bottom).remove_parent();
+            world.entity_mut(bottom).despawn();
+        }
+        self.scroll = None;
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+trait TakeIfEq<Item> {
+    fn take_if_eq(&mut self, value: &Item) -> Option<VirtualItem<Item>>;
+}
+
+impl<Item: PartialEq> TakeIfEq<Item> for Option<VirtualItem<Item>> {
+    /// Take the item if its value equals `value`.
+    fn take_if_eq(&mut self, value: &Item) -> Option<VirtualItem<Item>> {
+        match self {
+            Some(item) if item.value == *value => self.take(),
+            _ => None,
+        }
+    }
+}
+
+impl<
+        Item: Send + Sync + PartialEq + Clone + 'static,
+        ItemFn: Send + Sync + 'static + Fn(&Rcx) -> Vec<Item>,
+        AreaFn: Send + Sync + 'static + Fn(&Rcx) -> f32,
+        V: 'static + IntoView,
+        F: Send + Sync + 'static + Fn(&Item, usize) -> V,
+    > IntoView for VirtualList<Item, ItemFn, AreaFn, V, F>
+{
+    fn into_view(self) -> ViewRef {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commit_mutables;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Rows(Vec<u32>);
+
+    type TestList =
+        VirtualList<u32, fn(&Rcx) -> Vec<u32>, fn(&Rcx) -> f32, String, fn(&u32, usize) -> String>;
+
+    fn rows(cx: &Rcx) -> Vec<u32> {
+        cx.use_resource::<Rows>().0.clone()
+    }
+
+    fn area(_cx: &Rcx) -> f32 {
+        400.
+    }
+
+    fn row(value: &u32, _index: usize) -> String {
+        value.to_string()
+    }
+
+    fn react(view: &ViewRef, entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.change_tick());
+        view.lock().unwrap().react(entity, world, &mut tracking);
+    }
+
+    fn item_nodes(view: &ViewRef, world: &World) -> Vec<Entity> {
+        let nodes = view.lock().unwrap().nodes(world).flat_collect();
+        // Skip the spacers.
+        nodes[1..nodes.len() - 1].to_vec()
+    }
+
+    #[test]
+    fn test_virtual_list() {
+        let mut world = World::default();
+        world.insert_resource(Rows((0..10_000).collect()));
+        let parent = world.spawn_empty().id();
+        let list: TestList = VirtualList::new(rows, 20., area, row);
+        let view = list.into_view();
+        let entity = ViewHandle::spawn(&view, parent, &mut world);
+
+        // Only the visible rows are rendered.
+        let initial = item_nodes(&view, &world);
+        assert_eq!(initial.len(), 21);
+        let entity_count = world.entities().len();
+        let view_count = world.query::<&ViewHandle>().iter(&world).count();
+
+        // Scroll by 100px: five rows scroll out of the top, and five scroll in at the bottom.
+        TestList::set_scroll(entity, &mut world, 100.);
+        commit_mutables(&mut world);
+        react(&view, entity, &mut world);
+        let scrolled = item_nodes(&view, &world);
+        // The view entities of the rows which scrolled out are reused.
+        assert_eq!(
+            world.query::<&ViewHandle>().iter(&world).count(),
+            view_count
+        );
+        assert_eq!(world.entities().len(), entity_count);
+        assert_eq!(scrolled.len(), 21);
+        assert_eq!(scrolled[..16], initial[5..]);
+        assert!(world.get_entity(initial[0]).is_none());
+        assert_eq!(
+            world.get::<Text>(scrolled[20]).unwrap().sections[0].value,
+            "25"
+        );
+
+        // Shrinking the list while scrolled.
+        world.resource_mut::<Rows>().0.truncate(10);
+        react(&view, entity, &mut world);
+        let shrunk = item_nodes(&view, &world);
+        assert_eq!(shrunk.len(), 5);
+        assert_eq!(shrunk[..], scrolled[..5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than zero")]
+    fn test_zero_item_height() {
+        let _: TestList = VirtualList::new(rows, 0., area, row);
+    }
+}
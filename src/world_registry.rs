@@ -0,0 +1,120 @@
+use bevy::{ecs::world::WorldId, prelude::*, utils::HashMap};
+
+/// Resource which holds secondary worlds, such as the server world in a client/server split.
+/// Reactions can read resources from a secondary world via
+/// [`ReactiveContext::use_secondary_resource`](crate::ReactiveContext::use_secondary_resource),
+/// and will react when those resources change.
+#[derive(Resource, Default)]
+pub struct WorldRegistry {
+    worlds: HashMap<WorldId, World>,
+}
+
+impl WorldRegistry {
+    /// Add a secondary world to the registry, and return its id.
+    pub fn insert(&mut self, world: World) -> WorldId {
+        let id = world.id();
+        self.worlds.insert(id, world);
+        id
+    }
+
+    /// Returns the secondary world with the given id, if it is registered.
+    pub fn get(&self, id: WorldId) -> Option<&World> {
+        self.worlds.get(&id)
+    }
+
+    /// Returns a mutable reference to the secondary world with the given id, if it is
+    /// registered.
+    pub fn get_mut(&mut self, id: WorldId) -> Option<&mut World> {
+        self.worlds.get_mut(&id)
+    }
+
+    /// Remove a secondary world from the registry. Reactions which depend on resources in
+    /// the world will react once more.
+    pub fn remove(&mut self, id: WorldId) -> Option<World> {
+        self.worlds.remove(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        commit_mutables, cx::Cx, run_reactions, Rcx, Reaction, ReactionHandle, ReactiveContext,
+        ReactiveContextMut, TrackingScope,
+    };
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Score(u32);
+
+    struct ReadBoth {
+        server: WorldId,
+        mutable: Entity,
+        seen: Arc<Mutex<Vec<(u32, u32)>>>,
+    }
+
+    impl Reaction for ReadBoth {
+        fn react(&mut self, _owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+            let re = Rcx::new(world, tracking);
+            let score = re.use_secondary_resource::<Score>(self.server).0;
+            let local = re.read_mutable::<u32>(self.mutable);
+            self.seen.lock().unwrap().push((score, local));
+        }
+    }
+
+    fn set_score(world: &mut World, server: WorldId, score: u32) {
+        world
+            .resource_mut::<WorldRegistry>()
+            .get_mut(server)
+            .unwrap()
+            .resource_mut::<Score>()
+            .0 = score;
+    }
+
+    #[test]
+    fn test_secondary_resource() {
+        let mut server = World::default();
+        server.insert_resource(Score(0));
+        let mut world = World::default();
+        let mut registry = WorldRegistry::default();
+        let server = registry.insert(server);
+        world.insert_resource(registry);
+
+        let mut owner = TrackingScope::new(world.change_tick());
+        let mutable =
+            Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).create_mutable(0u32);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut reaction = ReadBoth {
+            server,
+            mutable: mutable.id,
+            seen: seen.clone(),
+        };
+        let mut scope = TrackingScope::new(world.change_tick());
+        let reaction_id = world.spawn_empty().id();
+        reaction.react(reaction_id, &mut world, &mut scope);
+        world
+            .entity_mut(reaction_id)
+            .insert((ReactionHandle::new(reaction), scope));
+        world.increment_change_tick();
+
+        // A change in the secondary world causes a reaction.
+        set_score(&mut world, server, 1);
+        run_reactions(&mut world);
+        assert_eq!(*seen.lock().unwrap(), vec![(0, 0), (1, 0)]);
+        world.increment_change_tick();
+
+        // No change, no reaction.
+        run_reactions(&mut world);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+        world.increment_change_tick();
+
+        // Simultaneous changes in both worlds cause a single reaction.
+        set_score(&mut world, server, 2);
+        Cx::new(&(), &mut world, Entity::PLACEHOLDER, &mut owner).write_mutable(mutable.id, 5u32);
+        commit_mutables(&mut world);
+        run_reactions(&mut world);
+        assert_eq!(*seen.lock().unwrap(), vec![(0, 0), (1, 0), (2, 5)]);
+    }
+}